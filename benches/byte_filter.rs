@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Wyrand};
+
+/// Two-pass scans with and without the byte-presence prefilter, on haystacks with
+/// common anchor bytes
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 64 * 1024 * 1024;
+
+    let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? 48 85 c0 74 ??");
+    let anchor = pattern.selectivity().anchor.unwrap().byte;
+    let scanner = Scanner::from(pattern);
+
+    // random data with the anchor every 16 bytes
+    let mut rand = Wyrand::default();
+    let random = (0..SIZE)
+        .map(|i| match rand.next_u16() as u8 {
+            _ if i % 16 == 0 => anchor,
+            byte if byte == anchor => !anchor,
+            byte => byte,
+        })
+        .collect::<Vec<_>>();
+
+    // machine code, taken from the benchmark executable itself
+    let executable = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+    let code = executable
+        .iter()
+        .copied()
+        .cycle()
+        .take(SIZE)
+        .collect::<Vec<_>>();
+
+    // the same instructions followed by two `ud2`, which never occurs in compiled code
+    let absent = Scanner::new("48 8b 05 ?? ?? ?? ?? 48 85 c0 74 ?? 0f 0b 0f 0b");
+    assert_eq!(absent.find_two_pass(&executable), None);
+
+    let mut group = c.benchmark_group("byte filter");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(20);
+
+    for (name, scanner, data) in [("random", &scanner, &random), ("code", &absent, &code)] {
+        group.bench_with_input(BenchmarkId::new("two pass", name), data, |b, data| {
+            b.iter(|| scanner.find_two_pass(data));
+        });
+        group.bench_with_input(BenchmarkId::new("filtered", name), data, |b, data| {
+            b.iter(|| scanner.find_two_pass_filtered(data));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use lightningscanner::deadline::ScanOptions;
+use lightningscanner::Scanner;
+use std::time::{Duration, Instant};
+
+/// Overhead of checking the deadline, compared to the same scan without one
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 64 * 1024 * 1024;
+
+    let data = vec![0x00; SIZE];
+    let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+
+    let mut group = c.benchmark_group("deadline scan");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+
+    group.bench_function("no deadline", |b| {
+        b.iter(|| scanner.find_all_offsets(None, &data));
+    });
+
+    group.bench_function("far deadline", |b| {
+        let options = ScanOptions::new().with_deadline(Instant::now() + Duration::from_secs(3600));
+        b.iter(|| scanner.find_all_with_options(&data, &options));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+/// Number of concrete bytes past the first unit of the patterns
+const SPREAD_BYTES: usize = 8;
+
+/// Gathered verification against the unit loop of the AVX2 backend, for sparse patterns
+/// spanning a growing number of units
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    // every position of the haystack is a candidate passing the first unit of the patterns
+    let haystack = vec![0x00; SIZE];
+
+    let mut group = c.benchmark_group("gather verify");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(20);
+
+    for units in [1, 2, 4, 8, 16] {
+        // the first unit starts with three concrete bytes, the others spread over the rest
+        let size = 32 * (units + 1);
+        let spread = (0..SPREAD_BYTES)
+            .map(|i| 32 + (i + 1) * units * 32 / SPREAD_BYTES - 1)
+            .collect::<Vec<_>>();
+
+        // candidates fail at the first or at the last of the spread bytes
+        for (name, failing) in [("early", spread[0]), ("late", spread[SPREAD_BYTES - 1])] {
+            let mut data = vec![0x00; size];
+            let mut mask = vec![0x00; size];
+            for offset in [0, 1, 2].into_iter().chain(spread.iter().copied()) {
+                mask[offset] = 0xff;
+            }
+            data[failing] = 0x01;
+            let scanner = Scanner::from(Pattern::from_masked_bytes(&data, &mask));
+
+            for (method, gather) in [("units", false), ("gather", true)] {
+                lightningscanner::set_gather_verification(gather);
+
+                let id = BenchmarkId::new(format!("{} {}", method, name), units);
+                group.bench_with_input(id, &haystack, |b, haystack| {
+                    // SAFETY: the pointer and size come from a valid slice
+                    b.iter(|| unsafe {
+                        scanner.find(Some(ScanMode::Avx2), haystack.as_ptr(), haystack.len())
+                    });
+                });
+            }
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
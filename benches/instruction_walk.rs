@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Wyrand};
+
+/// Length decoder of the toy instruction set of the benchmark: the low two bits of the
+/// opcode plus one
+fn decode_len(addr: *const u8, _remaining: usize) -> usize {
+    // SAFETY: the scanner only passes addresses within the binary
+    (unsafe { *addr } & 3) as usize + 1
+}
+
+/// Instruction walking against candidate scanning followed by decoding, for patterns
+/// which are rare and common in the haystack
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 16 * 1024 * 1024;
+
+    // random instructions, with opcodes of the form `0b1xxx_xxyy` and operands which never
+    // have the top bit set, so a pattern starting with an opcode only matches at boundaries
+    let mut rand = Wyrand::default();
+    let mut code = Vec::with_capacity(SIZE + 4);
+    while code.len() < SIZE {
+        let opcode = rand.next_u16() as u8 | 0x80;
+        code.push(opcode);
+        code.extend((0..opcode & 3).map(|_| rand.next_u16() as u8 & 0x7f));
+    }
+    code.truncate(SIZE);
+
+    let patterns = [
+        // never occurs, the operands not having the top bit set
+        ("rare", Scanner::new("7f 80 80 80 80")),
+        // occurs all over the operands, but never at an instruction boundary
+        ("common", Scanner::new("00")),
+    ];
+
+    let mut group = c.benchmark_group("instruction walk");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(20);
+
+    for (name, scanner) in &patterns {
+        group.bench_with_input(BenchmarkId::new("candidates", name), &code, |b, code| {
+            // SAFETY: the pointer and size come from a valid slice
+            b.iter(|| unsafe {
+                scanner.find_aligned_to_instruction(None, code.as_ptr(), code.len(), |addr| {
+                    decode_len(addr, 0)
+                })
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("walk", name), &code, |b, code| {
+            // SAFETY: the pointer and size come from a valid slice
+            b.iter(|| unsafe {
+                scanner.find_instruction_aligned(code.as_ptr(), code.len(), decode_len)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::{set_step_override, ScanMode, Scanner};
+use tinyrand::{Rand, Wyrand};
+
+/// Vector scans advancing more than one byte per candidate offset, which scales the work
+/// per byte down until the scan comes close to the memory bandwidth
+///
+/// Only meant to characterize the scan loops, the larger steps miss matches.
+fn benchmark(c: &mut Criterion) {
+    const STEPS: [usize; 6] = [1, 2, 4, 8, 16, 32];
+
+    let mut rand = Wyrand::default();
+    for (name, size) in [("l2", 256 * 1024), ("ram", 256 * 1024 * 1024)] {
+        let data = (0..size).map(|_| rand.next_u16() as u8).collect::<Vec<_>>();
+
+        // a miss scans the whole haystack
+        let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c 24 ?? 41 56");
+
+        let mut group = c.benchmark_group(format!("scan step {}", name));
+        group.throughput(Throughput::Bytes(size as u64));
+
+        for step in STEPS {
+            for (mode_name, mode) in [("sse4.2", ScanMode::Sse42), ("avx2", ScanMode::Avx2)] {
+                set_step_override(Some(step));
+
+                group.bench_with_input(BenchmarkId::new(mode_name, step), &data, |b, data| {
+                    b.iter(|| {
+                        // SAFETY: data is a valid slice
+                        unsafe { scanner.find(Some(mode), data.as_ptr(), data.len()) }
+                    });
+                });
+            }
+        }
+        set_step_override(None);
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
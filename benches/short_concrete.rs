@@ -0,0 +1,40 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Wyrand};
+
+/// SSE4.2 scans for fully concrete patterns of up to one unit, against scalar and AVX2
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    let mut rand = Wyrand::default();
+    let data = (0..SIZE).map(|_| rand.next_u16() as u8).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("short concrete pattern scan");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+
+    for pattern in [
+        "48 89",
+        "48 89 5c 24",
+        "48 89 5c 24 08 48 89 6c",
+        "48 89 5c 24 08 48 89 6c 24 10 48 89 74 24 18 57",
+    ] {
+        let scanner = Scanner::new(pattern);
+        let len = (pattern.len() + 1) / 3;
+
+        for (name, mode) in [
+            ("scalar", ScanMode::Scalar),
+            ("sse4.2", ScanMode::Sse42),
+            ("avx2", ScanMode::Avx2),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, len), &data, |b, data| {
+                // a miss scans the whole haystack
+                b.iter(|| scanner.find_all_offsets(Some(mode), data));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
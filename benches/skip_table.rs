@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Wyrand};
+
+/// Scalar scans around the size where building the skip table starts to pay off
+fn benchmark(c: &mut Criterion) {
+    const SIZES: [usize; 8] = [256, 1024, 2048, 4096, 8192, 16384, 32768, 65536];
+
+    let mut rand = Wyrand::default();
+    let data = (0..SIZES[SIZES.len() - 1])
+        .map(|_| rand.next_u16() as u8)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("skip table");
+
+    // concrete bytes right before the key allow long shifts,
+    // a wildcard right before it limits every shift to two bytes
+    for (name, pattern) in [
+        ("long shifts", "48 89 5c 24 ?? 48 89 6c 24 18 41 56"),
+        ("short shifts", "48 89 5c 24 ?? 48 89 6c 24 ?? 41 56"),
+    ] {
+        let scanner = Scanner::new(pattern);
+
+        for size in SIZES {
+            let haystack = &data[..size];
+            group.throughput(Throughput::Bytes(size as u64));
+
+            group.bench_with_input(BenchmarkId::new(name, size), haystack, |b, haystack| {
+                b.iter(|| {
+                    // SAFETY: haystack is a valid slice
+                    unsafe {
+                        scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len())
+                    }
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
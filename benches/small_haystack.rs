@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Wyrand};
+
+/// Compares the backends on haystacks around the scalar fallback threshold
+fn benchmark(c: &mut Criterion) {
+    const SIZES: [usize; 12] = [16, 20, 24, 28, 32, 64, 128, 256, 512, 1024, 2048, 4096];
+
+    let mut rand = Wyrand::default();
+    let data = (0..SIZES[SIZES.len() - 1])
+        .map(|_| rand.next_u16() as u8)
+        .collect::<Vec<_>>();
+
+    // a miss scans the whole haystack, which is the worst case for every backend
+    let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c 24 ?? 41 56");
+
+    let mut group = c.benchmark_group("small haystack scan");
+
+    for size in SIZES {
+        let haystack = &data[..size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        for (name, mode) in [
+            ("scalar", ScanMode::Scalar),
+            ("sse4.2", ScanMode::Sse42),
+            ("avx2", ScanMode::Avx2),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, size), haystack, |b, haystack| {
+                b.iter(|| {
+                    // SAFETY: haystack is a valid slice
+                    unsafe { scanner.find(Some(mode), haystack.as_ptr(), haystack.len()) }
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
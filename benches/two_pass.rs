@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Wyrand};
+
+/// Two-pass scans against the single-pass one, for anchors of decreasing density
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 64 * 1024 * 1024;
+
+    let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? 48 85 c0 74 ??");
+    let anchor = pattern.selectivity().anchor.unwrap().byte;
+    let scanner = Scanner::from(pattern);
+
+    let mut group = c.benchmark_group("two pass scan");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(20);
+
+    for spacing in [16, 256, 4096, 65536] {
+        // random data without the anchor, except every `spacing` bytes
+        let mut rand = Wyrand::default();
+        let data = (0..SIZE)
+            .map(|i| match rand.next_u16() as u8 {
+                _ if i % spacing == 0 => anchor,
+                byte if byte == anchor => !anchor,
+                byte => byte,
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("single", spacing), &data, |b, data| {
+            // SAFETY: the pointer and size come from a valid slice
+            b.iter(|| unsafe { scanner.find(None, data.as_ptr(), data.len()) });
+        });
+        group.bench_with_input(BenchmarkId::new("two pass", spacing), &data, |b, data| {
+            b.iter(|| scanner.find_two_pass(data));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use lightningscanner::{ScanMode, Scanner};
+
+/// Haystack full of near-misses, so most of the time is spent verifying candidates
+fn benchmark(c: &mut Criterion) {
+    const SIZE: usize = 1 << 20;
+
+    // every 64 bytes the haystack matches the pattern except for a single byte near its end
+    let near_miss = (0..64u8).map(|i| i ^ 0x5a).collect::<Vec<_>>();
+    let pattern = near_miss
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| match i {
+            32 => "??".to_string(),
+            48 => format!("{:02x}", !byte),
+            _ => format!("{:02x}", byte),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let data = near_miss
+        .iter()
+        .copied()
+        .cycle()
+        .take(SIZE)
+        .collect::<Vec<_>>();
+
+    let scanner = Scanner::new(&pattern);
+
+    let mut group = c.benchmark_group("verify heavy scan");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+
+    for (name, mode) in [
+        ("scalar", ScanMode::Scalar),
+        ("sse4.2", ScanMode::Sse42),
+        ("avx2", ScanMode::Avx2),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                // SAFETY: data is a valid slice
+                unsafe { scanner.find(Some(mode), data.as_ptr(), data.len()) }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,185 @@
+//! Typed addresses, keeping file offsets, virtual addresses and RVAs apart
+//!
+//! Converting between them always requires the base they're relative to, so a value
+//! can't accidentally be rebased twice, e.g. by adding the image base to an address
+//! which already includes it.
+
+use crate::file::Section;
+use crate::{ScanMode, ScanResult, Scanner};
+use std::fmt;
+
+/// Offset from the beginning of a file, or of a buffer holding its contents
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileOffset(pub usize);
+
+/// Absolute address in the memory of a process
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VirtualAddress(pub usize);
+
+/// Address relative to the base of a loaded image
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rva(pub usize);
+
+impl FileOffset {
+    /// Translate into an RVA using the section containing the offset
+    ///
+    /// Returns `None` if the offset lies outside of every section.
+    pub fn to_rva(self, sections: &[Section]) -> Option<Rva> {
+        sections
+            .iter()
+            .find_map(|section| section.rva_of(self.0))
+            .map(Rva)
+    }
+
+    /// Address of the offset in a copy of the file contents starting at `buffer_base`
+    ///
+    /// This is for contents mapped or read as-is, use [`FileOffset::to_rva`] and
+    /// [`Rva::to_virtual_address`] for images loaded by the OS.
+    pub fn to_virtual_address(self, buffer_base: VirtualAddress) -> Option<VirtualAddress> {
+        buffer_base.0.checked_add(self.0).map(VirtualAddress)
+    }
+}
+
+impl VirtualAddress {
+    /// Translate into an RVA relative to `image_base`
+    ///
+    /// Returns `None` if the address lies below the image base.
+    pub fn to_rva(self, image_base: VirtualAddress) -> Option<Rva> {
+        self.0.checked_sub(image_base.0).map(Rva)
+    }
+
+    /// Offset of the address within a copy of the file contents starting at `buffer_base`
+    ///
+    /// Returns `None` if the address lies below the buffer. This is the inverse of
+    /// [`FileOffset::to_virtual_address`].
+    pub fn to_file_offset(self, buffer_base: VirtualAddress) -> Option<FileOffset> {
+        self.0.checked_sub(buffer_base.0).map(FileOffset)
+    }
+}
+
+impl Rva {
+    /// Translate into an absolute address of the image loaded at `image_base`
+    ///
+    /// Returns `None` if the address overflows.
+    pub fn to_virtual_address(self, image_base: VirtualAddress) -> Option<VirtualAddress> {
+        image_base.0.checked_add(self.0).map(VirtualAddress)
+    }
+
+    /// Translate into a file offset using the section containing the RVA
+    ///
+    /// Returns `None` if the RVA lies outside of the raw data of every section.
+    /// This is the inverse of [`FileOffset::to_rva`].
+    pub fn to_file_offset(self, sections: &[Section]) -> Option<FileOffset> {
+        sections.iter().find_map(|section| {
+            let offset = self.0.checked_sub(section.rva)?;
+            (offset < section.file_size).then(|| FileOffset(section.file_offset + offset))
+        })
+    }
+}
+
+macro_rules! impl_hex {
+    ($($ty:ty),*) => {
+        $(
+            impl fmt::LowerHex for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::LowerHex::fmt(&self.0, f)
+                }
+            }
+
+            impl fmt::UpperHex for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::UpperHex::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_hex!(FileOffset, VirtualAddress, Rva);
+
+impl Scanner {
+    /// Find the file offset of the first occurrence of the pattern in the contents of a file
+    ///
+    /// Like the other offset-based methods, this is the offset of the match start,
+    /// regardless of the pattern's [cursor](crate::pattern::Pattern::cursor).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::address::{FileOffset, VirtualAddress};
+    /// use lightningscanner::Scanner;
+    ///
+    /// let contents = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let offset = scanner.find_file_offset(None, &contents).unwrap();
+    ///
+    /// assert_eq!(offset, FileOffset(2));
+    /// assert_eq!(
+    ///     offset.to_virtual_address(VirtualAddress(0x1000)),
+    ///     Some(VirtualAddress(0x1002))
+    /// );
+    /// ```
+    pub fn find_file_offset(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        contents: &[u8],
+    ) -> Option<FileOffset> {
+        self.find_in_slice(preferred_scan_mode, contents, 0)
+            .map(FileOffset)
+    }
+
+    /// Find the RVA of the first occurrence of the pattern in the contents of an executable file
+    ///
+    /// Matches are translated using the section which contains the first byte of the match,
+    /// matches outside of every section are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::address::{Rva, VirtualAddress};
+    /// use lightningscanner::file::Section;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let contents = [0x48, 0x89, 0x5c, 0x24, 0x48, 0x89, 0x5c, 0x24];
+    /// let text = Section {
+    ///     file_offset: 4,
+    ///     file_size: 4,
+    ///     rva: 0x1000,
+    /// };
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let rva = scanner.find_rva(None, &contents, &[text]).unwrap();
+    ///
+    /// assert_eq!(rva, Rva(0x1000));
+    /// assert_eq!(
+    ///     rva.to_virtual_address(VirtualAddress(0x140000000)),
+    ///     Some(VirtualAddress(0x140001000))
+    /// );
+    /// ```
+    pub fn find_rva(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        contents: &[u8],
+        sections: &[Section],
+    ) -> Option<Rva> {
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(preferred_scan_mode, contents, start) {
+            if let Some(rva) = FileOffset(offset).to_rva(sections) {
+                return Some(rva);
+            }
+
+            start = offset + 1;
+        }
+
+        None
+    }
+}
+
+impl ScanResult {
+    /// Get the address of this scan result, `None` if it's invalid
+    pub fn virtual_address(&self) -> Option<VirtualAddress> {
+        self.is_valid()
+            .then(|| VirtualAddress(self.get_addr() as usize))
+    }
+}
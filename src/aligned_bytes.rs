@@ -9,36 +9,33 @@ pub struct AlignedBytes<const N: usize>(Align<N>, [u8])
 where
     Align<N>: Alignment;
 
-#[repr(C)]
-struct AlignedByte<const N: usize>(Align<N>, u8)
-where
-    Align<N>: Alignment;
-
 impl<const N: usize> AlignedBytes<N>
 where
     Align<N>: Alignment,
 {
     /// Create a new `AlignedBytes` instance from a slice
+    ///
+    /// The data is always copied. Taking over the allocation of a `Vec<u8>` isn't possible
+    /// even when it happens to be aligned, as the allocator requires memory to be freed with
+    /// the alignment it was allocated with, which is 1 for a vector of bytes.
+    ///
+    /// An empty slice yields `N` zero bytes instead of an empty buffer, so the buffer can
+    /// always be read as a whole vector of up to `N` bytes.
     pub fn new(data: &[u8]) -> Box<AlignedBytes<N>> {
         if data.is_empty() {
-            // SAFETY: The pointer isn't null and is aligned because it was returned from
-            // `NonNull::dangling`. The length is zero, so no other requirements apply.
-            unsafe {
-                Self::from_byte_ptr(
-                    ptr::NonNull::<AlignedByte<N>>::dangling()
-                        .as_ptr()
-                        .cast::<u8>(),
-                    0,
-                )
-            }
+            Self::new(&[0; N])
         } else {
-            if data.len().checked_next_multiple_of(N).unwrap_or(usize::MAX) > isize::MAX as usize {
+            let size = data.len().checked_next_multiple_of(N).unwrap_or(usize::MAX);
+            if size > isize::MAX as usize {
                 panic!("unable to allocate {} bytes (overflows isize)", data.len());
             }
 
-            // SAFETY: The alignment `N` is not zero and is a power of two. `data.len()`'s next
-            // multiple of N does not overflow an `isize`.
-            let layout = unsafe { alloc::Layout::from_size_align_unchecked(data.len(), N) };
+            // The box is deallocated with the layout of the whole struct, which is padded
+            // to a multiple of its alignment, so the allocation has to cover the padding too.
+            //
+            // SAFETY: The alignment `N` is not zero and is a power of two. `size` is a multiple
+            // of N and does not overflow an `isize`.
+            let layout = unsafe { alloc::Layout::from_size_align_unchecked(size, N) };
 
             // SAFETY: `layout`'s size is not zero.
             let ptr = unsafe { alloc::alloc(layout) };
@@ -65,8 +62,8 @@ where
     /// # Safety
     ///
     /// `ptr` must be non-null, aligned to N bytes, and valid for reads and writes for `len` bytes.
-    /// The data pointed to must be initialized. If `len` is non-zero, `ptr` must be currently
-    /// allocated by the global allocator and valid to deallocate.
+    /// The data pointed to must be initialized. `ptr` must be currently allocated by the global
+    /// allocator and valid to deallocate.
     unsafe fn from_byte_ptr(ptr: *mut u8, len: usize) -> Box<AlignedBytes<N>> {
         let slice_ptr = ptr::slice_from_raw_parts_mut(ptr, len);
 
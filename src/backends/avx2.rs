@@ -1,12 +1,95 @@
 //! AVX2 pattern scanning backend
 
+use crate::backends::{gather_verification, scalar, scan_step};
 use crate::pattern::Pattern;
 use crate::ScanResult;
 use std::arch::x86_64::{
-    _mm256_blendv_epi8, _mm256_cmpeq_epi8, _mm256_load_si256, _mm256_loadu_si256,
-    _mm256_movemask_epi8, _mm256_set1_epi8,
+    _mm256_i32gather_epi32, _mm256_load_si256, _mm256_loadu_si256, _mm256_testz_si256,
+    _mm256_xor_si256,
 };
-use std::ptr;
+
+const UNIT_SIZE: usize = 32;
+
+/// Number of dwords loaded by a single gather
+const GATHER_LANES: usize = 8;
+
+/// Fewest units the unit loop loads on average to reach a concrete byte past the first unit,
+/// for a match to be verified with a gather instead
+///
+/// The unit loop stops at the first mismatching unit while a gather costs the same however
+/// early the match fails, so its advantage depends on where candidates fail. In the
+/// `gather_verify` benchmark, the gather takes about as long as loading 1.6 units. It's
+/// 1.35 times slower than the loop for candidates failing in the second unit, and 2.1 and
+/// 6.1 times faster for those failing 4 and 16 units in. Assuming candidates are as likely
+/// to fail at any of the concrete bytes, the gather pays off from about 2 units on average.
+const MIN_GATHER_UNITS: usize = 2;
+
+/// Concrete bytes of a pattern past its first unit, as dwords loaded by a single gather
+///
+/// Long sparse patterns verified unit by unit load every unit between their concrete bytes,
+/// even those made of wildcards only.
+struct GatherPlan {
+    /// Offsets of the dwords from the match start
+    offsets: [i32; GATHER_LANES],
+    /// Pattern data of the dwords
+    data: [u32; GATHER_LANES],
+    /// Pattern mask of the dwords, zero for unused lanes
+    mask: [u32; GATHER_LANES],
+}
+
+impl GatherPlan {
+    /// Plan the verification of `pattern`, `None` if its concrete bytes past the first unit
+    /// don't fit into a single gather or are too close to the start to benefit from one,
+    /// see [`MIN_GATHER_UNITS`]
+    fn new(pattern: &Pattern) -> Option<Self> {
+        let size = pattern.unpadded_size;
+        let loaded_size = size.div_ceil(UNIT_SIZE) * UNIT_SIZE;
+
+        if size <= UNIT_SIZE || i32::try_from(loaded_size).is_err() || !gather_verification() {
+            return None;
+        }
+
+        let mut plan = GatherPlan {
+            offsets: [0; GATHER_LANES],
+            data: [0; GATHER_LANES],
+            mask: [0; GATHER_LANES],
+        };
+        let dword = |bytes: &[u8], start: usize| {
+            u32::from_le_bytes(bytes[start..start + 4].try_into().unwrap())
+        };
+
+        let concrete = (UNIT_SIZE..size).filter(|&offset| pattern.mask[offset] != 0x00);
+        let (count, loaded_units) = concrete.fold((0, 0), |(count, loaded_units), offset| {
+            (count + 1, loaded_units + offset / UNIT_SIZE)
+        });
+        if count == 0 || loaded_units < MIN_GATHER_UNITS * count {
+            return None;
+        }
+
+        let mut lanes = 0;
+        let mut offset = UNIT_SIZE;
+        while offset < size {
+            if pattern.mask[offset] == 0x00 {
+                offset += 1;
+                continue;
+            }
+            if lanes == GATHER_LANES {
+                return None;
+            }
+
+            // the dwords may not reach past the units the scan leaves room for
+            let start = offset.min(loaded_size - 4);
+            plan.offsets[lanes] = start as i32;
+            plan.data[lanes] = dword(&pattern.data, start);
+            plan.mask[lanes] = dword(&pattern.mask, start);
+
+            lanes += 1;
+            offset = start + 4;
+        }
+
+        Some(plan)
+    }
+}
 
 /// Find the first occurrence of a pattern in the binary
 /// using AVX2 instructions
@@ -20,47 +103,93 @@ use std::ptr;
 /// * Currently running CPU supports AVX2
 #[target_feature(enable = "avx2")]
 pub unsafe fn find(pattern_data: &Pattern, binary: *const u8, binary_size: usize) -> ScanResult {
-    const UNIT_SIZE: usize = 32;
+    // vector loads cover whole units, so the last few positions
+    // are left to the scalar backend to avoid reading past the end of the binary
+    let loaded_size = pattern_data.unpadded_size.div_ceil(UNIT_SIZE) * UNIT_SIZE;
+    let Some(last_chunk) = binary_size.checked_sub(loaded_size) else {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { scalar::find(pattern_data, binary, binary_size) };
+    };
 
-    let mut processed_size = 0;
+    let plan = GatherPlan::new(pattern_data);
 
     // SAFETY: this function is only called if the CPU supports AVX2
     unsafe {
-        let mut pattern = _mm256_load_si256(pattern_data.data.as_ptr() as *const _);
-        let mut mask = _mm256_load_si256(pattern_data.mask.as_ptr() as *const _);
-        let all_zeros = _mm256_set1_epi8(0x00);
+        let pattern = _mm256_load_si256(pattern_data.data.as_ptr() as *const _);
+        let mask = _mm256_load_si256(pattern_data.mask.as_ptr() as *const _);
+
+        let gather = plan.as_ref().map(|plan| {
+            (
+                _mm256_loadu_si256(plan.offsets.as_ptr() as *const _),
+                _mm256_loadu_si256(plan.data.as_ptr() as *const _),
+                _mm256_loadu_si256(plan.mask.as_ptr() as *const _),
+            )
+        });
 
+        let step = scan_step();
         let mut chunk = 0;
-        while chunk < binary_size {
+        while chunk <= last_chunk {
             let chunk_data = _mm256_loadu_si256(binary.add(chunk) as *const _);
 
-            let blend = _mm256_blendv_epi8(all_zeros, chunk_data, mask);
-            let eq = _mm256_cmpeq_epi8(pattern, blend);
-
-            if _mm256_movemask_epi8(eq) as u32 == 0xffffffff {
-                processed_size += UNIT_SIZE;
-
-                if processed_size < pattern_data.unpadded_size {
-                    chunk += UNIT_SIZE - 1;
+            // a unit matches if no masked bit differs, `(data ^ pattern) & mask == 0`
+            if _mm256_testz_si256(_mm256_xor_si256(chunk_data, pattern), mask) == 1 {
+                let matched = match gather {
+                    // the gathered dwords are all within the units loaded at `chunk`
+                    Some((offsets, data, mask)) => {
+                        let gathered =
+                            _mm256_i32gather_epi32::<1>(binary.add(chunk) as *const _, offsets);
+                        _mm256_testz_si256(_mm256_xor_si256(gathered, data), mask) == 1
+                    }
+                    // SAFETY: the units at `chunk` are within the binary
+                    None => verify_units(pattern_data, binary.add(chunk)),
+                };
 
-                    pattern = _mm256_load_si256(
-                        pattern_data.data.as_ptr().add(processed_size) as *const _
-                    );
-                    mask = _mm256_load_si256(
-                        pattern_data.mask.as_ptr().add(processed_size) as *const _
-                    );
-                } else {
-                    let addr = binary.add(chunk).sub(processed_size).add(UNIT_SIZE);
-                    return ScanResult { addr };
+                if matched {
+                    let addr = binary.add(chunk);
+                    return ScanResult {
+                        addr,
+                        writable: false,
+                    };
                 }
-            } else {
-                pattern = _mm256_load_si256(pattern_data.data.as_ptr() as *const _);
-                mask = _mm256_load_si256(pattern_data.mask.as_ptr() as *const _);
-                processed_size = 0;
             }
-            chunk += 1;
+            chunk += step;
+        }
+        let chunk = chunk.min(last_chunk + 1);
+
+        // SAFETY: `chunk` is within the binary as it's at most `last_chunk + 1`
+        scalar::find(pattern_data, binary.add(chunk), binary_size - chunk)
+    }
+}
+
+/// Check if the units of the pattern past its first one match at `binary`
+///
+/// # Safety
+///
+/// * `binary` - is valid for reads of the pattern's size rounded up to whole units
+///
+/// * Currently running CPU supports AVX2
+#[target_feature(enable = "avx2")]
+unsafe fn verify_units(pattern_data: &Pattern, binary: *const u8) -> bool {
+    let mut processed_size = UNIT_SIZE;
+
+    while processed_size < pattern_data.unpadded_size {
+        // SAFETY: the pattern buffers are padded to whole units, the caller guarantees
+        // `binary` is valid for them as well
+        let units_match = unsafe {
+            let pattern =
+                _mm256_load_si256(pattern_data.data.as_ptr().add(processed_size) as *const _);
+            let mask =
+                _mm256_load_si256(pattern_data.mask.as_ptr().add(processed_size) as *const _);
+            let chunk_data = _mm256_loadu_si256(binary.add(processed_size) as *const _);
+
+            _mm256_testz_si256(_mm256_xor_si256(chunk_data, pattern), mask) == 1
+        };
+
+        if !units_match {
+            return false;
         }
+        processed_size += UNIT_SIZE;
     }
 
-    ScanResult { addr: ptr::null() }
+    true
 }
@@ -2,13 +2,110 @@
 
 use crate::pattern::Pattern;
 use crate::{ScanMode, ScanResult};
+use std::ops::Range;
+#[cfg(feature = "test-hooks")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+use std::sync::OnceLock;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
 mod avx2;
+#[cfg(all(feature = "portable-simd", not(feature = "no-simd")))]
+mod portable;
 mod scalar;
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
 mod sse42;
 
+/// Haystacks smaller than this are scanned with the scalar backend unless a scan mode is preferred
+///
+/// The threshold is measured rather than derived from a backend's unit size. AVX2 can't load a
+/// single 32 byte unit from a smaller haystack and goes straight to its scalar tail handling.
+/// SSE4.2 does run its 16 byte vector loop from 16 bytes on, but the `small_haystack` benchmark
+/// still shows it behind scalar for 16 to 28 bytes and only ahead from 32 bytes on, so CPUs
+/// without AVX2 use the same threshold. Its PCMPESTRI search for short concrete patterns is
+/// rarely affected, [`Scanner`](crate::Scanner) scans fully concrete patterns with a substring
+/// search before they get to a backend.
+pub const SMALL_HAYSTACK_THRESHOLD: usize = 32;
+
+/// CPU features the x86-64 backends depend on
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+#[derive(Debug, Copy, Clone)]
+struct CpuFeatures {
+    avx2: bool,
+    sse42: bool,
+}
+
+/// Number of times the CPU features were detected, see [`feature_detections`]
+#[cfg(feature = "test-hooks")]
+static FEATURE_DETECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Get the features of the running CPU, detecting them on the first call only
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+fn cpu_features() -> CpuFeatures {
+    static FEATURES: OnceLock<CpuFeatures> = OnceLock::new();
+
+    *FEATURES.get_or_init(|| {
+        #[cfg(feature = "test-hooks")]
+        FEATURE_DETECTIONS.fetch_add(1, Ordering::Relaxed);
+
+        CpuFeatures {
+            avx2: is_x86_feature_detected!("avx2"),
+            sse42: is_x86_feature_detected!("sse4.2"),
+        }
+    })
+}
+
+/// Number of times the CPU features were detected, at most once per process
+#[cfg(feature = "test-hooks")]
+pub fn feature_detections() -> usize {
+    FEATURE_DETECTIONS.load(Ordering::Relaxed)
+}
+
+/// Whether the AVX2 backend may verify long sparse patterns with a gather
+#[cfg(feature = "test-hooks")]
+static GATHER_VERIFICATION: AtomicBool = AtomicBool::new(true);
+
+/// Allow or forbid gathered verification in the AVX2 backend, see [`gather_verification`]
+#[cfg(feature = "test-hooks")]
+pub fn set_gather_verification(enabled: bool) {
+    GATHER_VERIFICATION.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if the AVX2 backend may verify long sparse patterns with a gather, which is the
+/// default, only turned off to compare against verifying them unit by unit
+#[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+pub fn gather_verification() -> bool {
+    #[cfg(feature = "test-hooks")]
+    return GATHER_VERIFICATION.load(Ordering::Relaxed);
+
+    #[cfg(not(feature = "test-hooks"))]
+    true
+}
+
+/// Number of bytes the vector backends advance by after every candidate offset, 0 for one
+#[cfg(feature = "test-hooks")]
+static STEP_OVERRIDE: AtomicUsize = AtomicUsize::new(0);
+
+/// Override the advance of the vector backends' scan loops, see [`scan_step`]
+#[cfg(feature = "test-hooks")]
+pub fn set_step_override(step: Option<usize>) {
+    STEP_OVERRIDE.store(step.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Number of bytes the vector backends advance by after every candidate offset, which is
+/// one unless overridden to study how the scan loops are bound
+#[cfg(any(
+    all(target_arch = "x86_64", not(feature = "no-simd")),
+    all(feature = "portable-simd", not(feature = "no-simd"))
+))]
+pub fn scan_step() -> usize {
+    #[cfg(feature = "test-hooks")]
+    return STEP_OVERRIDE.load(Ordering::Relaxed).max(1);
+
+    #[cfg(not(feature = "test-hooks"))]
+    1
+}
+
 /// Find the first occurrence of a pattern in the binary
 ///
 /// # Safety
@@ -21,10 +118,16 @@ pub unsafe fn find(
     binary: *const u8,
     binary_size: usize,
 ) -> ScanResult {
-    #[cfg(target_arch = "x86_64")]
-    {
-        let avx2 = is_x86_feature_detected!("avx2");
-        let sse42 = is_x86_feature_detected!("sse4.2");
+    let preferred_scan_mode = ScanMode::preference(preferred_scan_mode);
+
+    if preferred_scan_mode.is_none() && binary_size < SMALL_HAYSTACK_THRESHOLD {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { scalar::find(pattern, binary, binary_size) };
+    }
+
+    #[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+    if pattern.unpadded_size != 0 {
+        let CpuFeatures { avx2, sse42 } = cpu_features();
 
         match (preferred_scan_mode, avx2, sse42) {
             (Some(ScanMode::Avx2) | None, true, _) => {
@@ -39,6 +142,89 @@ pub unsafe fn find(
         }
     }
 
+    // the portable backend is used when no architecture-specific backend applies
+    #[cfg(all(feature = "portable-simd", not(feature = "no-simd")))]
+    if pattern.unpadded_size != 0 && preferred_scan_mode != Some(ScanMode::Scalar) {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { portable::find(pattern, binary, binary_size) };
+    }
+
     // SAFETY: safe to call as long as the safety conditions were met for this function
     unsafe { scalar::find(pattern, binary, binary_size) }
 }
+
+/// Scan modes supported by the running CPU, the scalar one always being the first
+pub fn supported_modes() -> Vec<ScanMode> {
+    #[allow(unused_mut)]
+    let mut modes = vec![ScanMode::Scalar];
+
+    #[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+    {
+        let features = cpu_features();
+        if features.sse42 {
+            modes.push(ScanMode::Sse42);
+        }
+        if features.avx2 {
+            modes.push(ScanMode::Avx2);
+        }
+    }
+
+    #[cfg(all(feature = "portable-simd", not(feature = "no-simd")))]
+    modes.push(ScanMode::Portable);
+
+    modes
+}
+
+/// Find the first occurrence of a pattern in `binary`, starting at `start`
+///
+/// Returns the offset of the match from the beginning of `binary`
+pub fn find_in_slice(
+    pattern: &Pattern,
+    preferred_scan_mode: Option<ScanMode>,
+    binary: &[u8],
+    start: usize,
+) -> Option<usize> {
+    find_in_range(pattern, preferred_scan_mode, binary, start..binary.len())
+}
+
+/// Find the first occurrence of a pattern which lies entirely within `range` of `binary`
+///
+/// Returns the offset of the match from the beginning of `binary`
+pub fn find_in_range(
+    pattern: &Pattern,
+    preferred_scan_mode: Option<ScanMode>,
+    binary: &[u8],
+    range: Range<usize>,
+) -> Option<usize> {
+    let start = range.start;
+    let rest = binary.get(range)?;
+
+    // SAFETY: `rest` is a valid slice
+    let result = unsafe { find(pattern, preferred_scan_mode, rest.as_ptr(), rest.len()) };
+
+    if result.is_valid() {
+        Some(start + (result.get_addr() as usize - rest.as_ptr() as usize))
+    } else {
+        None
+    }
+}
+
+/// Check if a pattern matches at `offset` of `binary`
+///
+/// Matches that would extend past the end of `binary` are rejected
+pub fn matches_at(pattern: &Pattern, binary: &[u8], offset: usize) -> bool {
+    match binary.get(offset..) {
+        Some(rest) if rest.len() >= pattern.unpadded_size => {
+            // SAFETY: `rest` is valid for reads of the whole pattern
+            unsafe { scalar::matches_at(pattern, rest.as_ptr()) }
+        }
+        _ => false,
+    }
+}
+
+/// Check if `bytes` equal `data` in every bit set in `mask`
+///
+/// Slices of different lengths never match
+pub fn masked_eq(bytes: &[u8], data: &[u8], mask: &[u8]) -> bool {
+    bytes.len() == data.len() && bytes.len() == mask.len() && scalar::masked_eq(bytes, data, mask)
+}
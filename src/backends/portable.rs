@@ -0,0 +1,75 @@
+//! Portable SIMD pattern scanning backend
+//!
+//! Follows the same compare-and-movemask flow as the architecture-specific backends,
+//! using `std::simd` instead of intrinsics.
+
+use crate::backends::{scalar, scan_step};
+use crate::pattern::Pattern;
+use crate::ScanResult;
+use std::simd::cmp::SimdPartialEq;
+use std::simd::Simd;
+use std::slice;
+
+const UNIT_SIZE: usize = 32;
+
+type Unit = Simd<u8, UNIT_SIZE>;
+
+/// Find the first occurrence of a pattern in the binary
+/// using portable SIMD instructions
+///
+/// # Safety
+///
+/// * `binary` - is a valid pointer
+///
+/// * `binary_size` - corresponds to a valid size of `binary`
+pub unsafe fn find(pattern_data: &Pattern, binary: *const u8, binary_size: usize) -> ScanResult {
+    // vector loads cover whole units, so the last few positions
+    // are left to the scalar backend to avoid reading past the end of the binary
+    let loaded_size = pattern_data.unpadded_size.div_ceil(UNIT_SIZE) * UNIT_SIZE;
+    let Some(last_chunk) = binary_size.checked_sub(loaded_size) else {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { scalar::find(pattern_data, binary, binary_size) };
+    };
+
+    // SAFETY: the caller guarantees `binary` is valid for `binary_size` bytes
+    let haystack = unsafe { slice::from_raw_parts(binary, binary_size) };
+
+    let unit_matches = |chunk: usize, processed_size: usize| {
+        let range = processed_size..processed_size + UNIT_SIZE;
+
+        let pattern = Unit::from_slice(&pattern_data.data[range.clone()]);
+        let mask = Unit::from_slice(&pattern_data.mask[range]);
+        let chunk_data = Unit::from_slice(&haystack[chunk + processed_size..]);
+
+        // a unit matches if no masked bit differs, `(data ^ pattern) & mask == 0`
+        let eq = ((chunk_data ^ pattern) & mask).simd_eq(Unit::splat(0));
+        eq.to_bitmask() == u32::MAX as u64
+    };
+
+    let step = scan_step();
+    let mut chunk = 0;
+    while chunk <= last_chunk {
+        if unit_matches(chunk, 0) {
+            let mut processed_size = UNIT_SIZE;
+
+            while processed_size < pattern_data.unpadded_size {
+                if !unit_matches(chunk, processed_size) {
+                    break;
+                }
+                processed_size += UNIT_SIZE;
+            }
+
+            if processed_size >= pattern_data.unpadded_size {
+                return ScanResult {
+                    addr: haystack[chunk..].as_ptr(),
+                    writable: false,
+                };
+            }
+        }
+        chunk += step;
+    }
+    let chunk = chunk.min(last_chunk + 1);
+
+    // SAFETY: `chunk` is within the binary as it's at most `last_chunk + 1`
+    unsafe { scalar::find(pattern_data, binary.add(chunk), binary_size - chunk) }
+}
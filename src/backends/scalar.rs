@@ -2,50 +2,197 @@
 
 use crate::pattern::Pattern;
 use crate::ScanResult;
-use std::ptr;
+use std::{ptr, slice};
 
 /// Find the first occurrence of a pattern in the binary
 /// using scalar instructions
 ///
+/// Mismatching windows are skipped using a Boyer-Moore-Horspool shift table
+/// keyed on the last fully-masked byte of the pattern, see [`key_position`] and [`skip_table`].
+///
 /// # Safety
 ///
 /// * `binary` - is a valid pointer
 ///
 /// * `binary_size` - corresponds to a valid size of `binary`
 pub unsafe fn find(pattern: &Pattern, binary: *const u8, binary_size: usize) -> ScanResult {
-    // SAFETY: safe to call as the pointer will be exactly one byte past the end of the binary
-    let binary_end = unsafe { binary.add(binary_size) };
+    if binary_size < pattern.unpadded_size {
+        return ScanResult {
+            addr: ptr::null(),
+            writable: false,
+        };
+    }
+
+    let last_offset = binary_size - pattern.unpadded_size;
+
+    let Some(key) = key_position(pattern) else {
+        // without a single masked bit the pattern matches at the very first position
+        return ScanResult {
+            addr: binary,
+            writable: false,
+        };
+    };
+
+    if binary_size < SKIP_TABLE_MIN_SIZE {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { find_naive(pattern, key, binary, last_offset) };
+    }
+
+    let skip = skip_table(pattern, key);
 
-    for binary_offset in 0..binary_size {
-        let mut found = true;
+    let mut binary_offset = 0;
+    while binary_offset <= last_offset {
+        // SAFETY: binary_offset + key is always below binary_size
+        // because the last checked offset leaves room for the whole pattern
+        let key_value = unsafe { binary.add(binary_offset + key).read_volatile() };
 
-        for pattern_offset in 0..pattern.data.len() {
-            if pattern.mask[pattern_offset] == 0x00 {
-                continue;
+        if (key_value ^ pattern.data[key]) & pattern.mask[key] == 0 {
+            // SAFETY: safe to call because binary offset never gets out of binary+binary_size space
+            let addr = unsafe { binary.add(binary_offset) };
+
+            // SAFETY: binary_offset is never above last_offset, which leaves room for the whole pattern
+            if unsafe { matches_at(pattern, addr) } {
+                return ScanResult {
+                    addr,
+                    writable: false,
+                };
             }
+        }
+
+        binary_offset += skip[key_value as usize];
+    }
+    ScanResult {
+        addr: ptr::null(),
+        writable: false,
+    }
+}
+
+/// Smallest binary for which building the skip table pays off
+///
+/// Filling the table costs about as much as checking a few thousand positions directly,
+/// the `skip_table` benchmark puts the break-even point at around 4 KiB even for patterns
+/// allowing long shifts.
+const SKIP_TABLE_MIN_SIZE: usize = 4096;
+
+/// Check every position up to `last_offset` for a match, without a skip table
+///
+/// # Safety
+///
+/// * `binary` - is valid for reads of `last_offset + pattern.unpadded_size` bytes
+///
+/// * `key` - is below `pattern.unpadded_size`
+unsafe fn find_naive(
+    pattern: &Pattern,
+    key: usize,
+    binary: *const u8,
+    last_offset: usize,
+) -> ScanResult {
+    for binary_offset in 0..=last_offset {
+        // SAFETY: binary_offset is never above last_offset, which leaves room for the whole pattern
+        let addr = unsafe { binary.add(binary_offset) };
+
+        // SAFETY: key is within the pattern, so it's readable as well
+        let key_value = unsafe { addr.add(key).read_volatile() };
 
-            // SAFETY: safe to call as further behavior doesn't rely on overflows
-            // further reads from this address are safe because of the min call
-            // ensuring the pointer is always in bounds
-            let checked_addr = unsafe {
-                binary
-                    .add(binary_offset)
-                    .add(pattern_offset)
-                    .min(binary_end)
+        if (key_value ^ pattern.data[key]) & pattern.mask[key] == 0
+            // SAFETY: binary_offset is never above last_offset, which leaves room for the whole pattern
+            && unsafe { matches_at(pattern, addr) }
+        {
+            return ScanResult {
+                addr,
+                writable: false,
             };
+        }
+    }
+    ScanResult {
+        addr: ptr::null(),
+        writable: false,
+    }
+}
 
-            // SAFETY: checked addr is always in binary bounds
-            if unsafe { checked_addr.read_volatile() } != pattern.data[pattern_offset] {
-                found = false;
-                break;
-            }
+/// Check if the pattern matches at `binary`
+///
+/// # Safety
+///
+/// * `binary` - is valid for reads of `pattern.unpadded_size` bytes
+pub unsafe fn matches_at(pattern: &Pattern, binary: *const u8) -> bool {
+    // SAFETY: the caller guarantees `binary` is valid for the whole pattern
+    let bytes = unsafe { slice::from_raw_parts(binary, pattern.unpadded_size) };
+
+    masked_eq(
+        bytes,
+        &pattern.data[..pattern.unpadded_size],
+        &pattern.mask[..pattern.unpadded_size],
+    )
+}
+
+/// Check if `bytes` equal `data` in every bit set in `mask`, all three being of the same length
+///
+/// Differing masked bits are accumulated with `(bytes ^ data) & mask` a word at a time,
+/// so there's only a single branch per word.
+pub fn masked_eq(bytes: &[u8], data: &[u8], mask: &[u8]) -> bool {
+    const WORD_SIZE: usize = size_of::<u64>();
+
+    debug_assert!(bytes.len() == data.len() && bytes.len() == mask.len());
+
+    let word = |bytes: &[u8]| u64::from_ne_bytes(bytes.try_into().unwrap());
+
+    let bytes_words = bytes.chunks_exact(WORD_SIZE);
+    let data_words = data.chunks_exact(WORD_SIZE);
+    let mask_words = mask.chunks_exact(WORD_SIZE);
+
+    let mut difference = 0;
+    for ((&value, &data), &mask) in bytes_words
+        .remainder()
+        .iter()
+        .zip(data_words.remainder())
+        .zip(mask_words.remainder())
+    {
+        difference |= (value ^ data) & mask;
+    }
+
+    for ((value, data), mask) in bytes_words.zip(data_words).zip(mask_words) {
+        if (word(value) ^ word(data)) & word(mask) != 0 {
+            return false;
         }
+    }
 
-        if found {
-            // SAFETY: safe to call because binary offset never gets out of binary+binary_size space
-            let addr = unsafe { binary.add(binary_offset) };
-            return ScanResult { addr };
+    difference == 0
+}
+
+/// Position of the last fully-masked byte in the pattern
+///
+/// Patterns without one are keyed on their last partially masked byte, `None` is only
+/// returned if the pattern doesn't mask a single bit.
+fn key_position(pattern: &Pattern) -> Option<usize> {
+    let mask = &pattern.mask[..pattern.unpadded_size];
+
+    mask.iter()
+        .rposition(|&mask| mask == 0xff)
+        .or_else(|| mask.iter().rposition(|&mask| mask != 0x00))
+}
+
+/// Build a Boyer-Moore-Horspool bad-character table for the byte at `key`
+///
+/// For every byte value the table holds how far the pattern can be shifted when that value
+/// is found under the key position. Every byte before the key that could match the value
+/// caps the shift at its distance from the key, so a wildcard caps it for all values and a
+/// partially masked byte for all values sharing its masked bits.
+/// A trailing wildcard run does not take part, as those bytes match anything after the shift.
+fn skip_table(pattern: &Pattern, key: usize) -> [usize; 256] {
+    let mut skip = [key + 1; 256];
+
+    for offset in 0..key {
+        let shift = key - offset;
+
+        let (data, mask) = (pattern.data[offset], pattern.mask[offset]);
+
+        for (value, skip) in skip.iter_mut().enumerate() {
+            if (value as u8 ^ data) & mask == 0 {
+                *skip = shift;
+            }
         }
     }
-    ScanResult { addr: ptr::null() }
+
+    skip
 }
@@ -1,12 +1,12 @@
 //! SSE4.2 pattern scanning backend
 //!
+use crate::backends::{scalar, scan_step};
 use crate::pattern::Pattern;
 use crate::ScanResult;
 use std::arch::x86_64::{
-    _mm_blendv_epi8, _mm_cmpeq_epi8, _mm_load_si128, _mm_loadu_si128, _mm_movemask_epi8,
-    _mm_set1_epi8,
+    _mm_cmpestri, _mm_load_si128, _mm_loadu_si128, _mm_testz_si128, _mm_xor_si128,
+    _SIDD_CMP_EQUAL_ORDERED, _SIDD_UBYTE_OPS,
 };
-use std::ptr;
 
 /// Find the first occurrence of a pattern in the binary
 /// using SSE4.2 instructions
@@ -22,42 +22,124 @@ use std::ptr;
 pub unsafe fn find(pattern_data: &Pattern, binary: *const u8, binary_size: usize) -> ScanResult {
     const UNIT_SIZE: usize = 16;
 
-    let mut processed_size = 0;
+    // the `short_concrete` benchmark shows string search about four times ahead of the
+    // compare-and-verify loop for these
+    if is_short_concrete(pattern_data) {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { find_concrete(pattern_data, binary, binary_size) };
+    }
+
+    // vector loads cover whole units, so the last few positions
+    // are left to the scalar backend to avoid reading past the end of the binary
+    let loaded_size = pattern_data.unpadded_size.div_ceil(UNIT_SIZE) * UNIT_SIZE;
+    let Some(last_chunk) = binary_size.checked_sub(loaded_size) else {
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        return unsafe { scalar::find(pattern_data, binary, binary_size) };
+    };
 
     // SAFETY: this function is only called if the CPU supports SSE4.2
     unsafe {
-        let mut pattern = _mm_load_si128(pattern_data.data.as_ptr() as *const _);
-        let mut mask = _mm_load_si128(pattern_data.mask.as_ptr() as *const _);
-        let all_zeros = _mm_set1_epi8(0x00);
+        let pattern = _mm_load_si128(pattern_data.data.as_ptr() as *const _);
+        let mask = _mm_load_si128(pattern_data.mask.as_ptr() as *const _);
 
+        let step = scan_step();
         let mut chunk = 0;
 
-        while chunk < binary_size {
+        while chunk <= last_chunk {
             let chunk_data = _mm_loadu_si128(binary.add(chunk) as *const _);
-            let blend = _mm_blendv_epi8(all_zeros, chunk_data, mask);
-            let eq = _mm_cmpeq_epi8(pattern, blend);
 
-            if _mm_movemask_epi8(eq) == 0xffff {
-                processed_size += UNIT_SIZE;
+            // a unit matches if no masked bit differs, `(data ^ pattern) & mask == 0`
+            if _mm_testz_si128(_mm_xor_si128(chunk_data, pattern), mask) == 1 {
+                let mut processed_size = UNIT_SIZE;
 
-                if processed_size < pattern_data.unpadded_size {
-                    chunk += UNIT_SIZE - 1;
-                    pattern =
+                while processed_size < pattern_data.unpadded_size {
+                    let pattern =
                         _mm_load_si128(pattern_data.data.as_ptr().add(processed_size) as *const _);
-                    mask =
+                    let mask =
                         _mm_load_si128(pattern_data.mask.as_ptr().add(processed_size) as *const _);
-                } else {
-                    let addr = binary.add(chunk).sub(processed_size).add(UNIT_SIZE);
-                    return ScanResult { addr };
+                    let chunk_data =
+                        _mm_loadu_si128(binary.add(chunk + processed_size) as *const _);
+
+                    if _mm_testz_si128(_mm_xor_si128(chunk_data, pattern), mask) == 0 {
+                        break;
+                    }
+                    processed_size += UNIT_SIZE;
+                }
+
+                if processed_size >= pattern_data.unpadded_size {
+                    let addr = binary.add(chunk);
+                    return ScanResult {
+                        addr,
+                        writable: false,
+                    };
                 }
-            } else {
-                pattern = _mm_load_si128(pattern_data.data.as_ptr() as *const _);
-                mask = _mm_load_si128(pattern_data.mask.as_ptr() as *const _);
-                processed_size = 0;
             }
 
-            chunk += 1;
+            chunk += step;
         }
+        let chunk = chunk.min(last_chunk + 1);
+
+        // SAFETY: `chunk` is within the binary as it's at most `last_chunk + 1`
+        scalar::find(pattern_data, binary.add(chunk), binary_size - chunk)
+    }
+}
+
+/// Check if the pattern fits into a single unit and has no wildcards
+fn is_short_concrete(pattern_data: &Pattern) -> bool {
+    let size = pattern_data.unpadded_size;
+
+    size != 0 && size <= 16 && pattern_data.mask[..size].iter().all(|&mask| mask == 0xff)
+}
+
+/// Find the first occurrence of a short concrete pattern using `PCMPESTRI` substring search
+///
+/// # Safety
+///
+/// * `binary` - is a valid pointer
+///
+/// * `binary_size` - corresponds to a valid size of `binary`
+///
+/// * Currently running CPU supports SSE4.2
+///
+/// * The pattern is non-empty, at most 16 bytes long and fully concrete
+#[target_feature(enable = "sse4.2")]
+unsafe fn find_concrete(
+    pattern_data: &Pattern,
+    binary: *const u8,
+    binary_size: usize,
+) -> ScanResult {
+    const UNIT_SIZE: usize = 16;
+    const MODE: i32 = _SIDD_UBYTE_OPS | _SIDD_CMP_EQUAL_ORDERED;
+
+    let size = pattern_data.unpadded_size as i32;
+
+    // SAFETY: this function is only called if the CPU supports SSE4.2
+    unsafe {
+        let pattern = _mm_load_si128(pattern_data.data.as_ptr() as *const _);
+
+        let mut chunk = 0;
+        while chunk + UNIT_SIZE <= binary_size {
+            let chunk_data = _mm_loadu_si128(binary.add(chunk) as *const _);
+
+            // index of the first position where the pattern matches, either entirely or
+            // with the part which still fits into the unit, 16 if there's none
+            let index = _mm_cmpestri::<MODE>(pattern, size, chunk_data, UNIT_SIZE as i32) as usize;
+
+            if index == UNIT_SIZE {
+                chunk += UNIT_SIZE;
+            } else if index + size as usize <= UNIT_SIZE {
+                let addr = binary.add(chunk + index);
+                return ScanResult {
+                    addr,
+                    writable: false,
+                };
+            } else {
+                // a partial match at the end of the unit continues in the next load
+                chunk += index;
+            }
+        }
+
+        // SAFETY: `chunk` is within the binary, every earlier position was ruled out
+        scalar::find(pattern_data, binary.add(chunk), binary_size - chunk)
     }
-    ScanResult { addr: ptr::null() }
 }
@@ -0,0 +1,109 @@
+//! Measuring scan throughput
+
+use crate::{backends, ScanMode, Scanner};
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// Bytes in a GiB, the unit all throughputs are reported in
+const GIB: f64 = (1u64 << 30) as f64;
+
+/// Throughput of the default scan, measured by [`Scanner::benchmark_find`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScanBench {
+    /// Number of bytes scanned in total, over all iterations
+    pub bytes: usize,
+    /// Time the timed scans took, without the warm-up
+    pub elapsed: Duration,
+    /// Throughput in GiB/s, 2^30 bytes per second, like [`Scanner::benchmark`]
+    pub gibps: f64,
+}
+
+impl Scanner {
+    /// Measure the throughput of every scan mode supported by the running CPU on `haystack`
+    ///
+    /// Each mode scans the whole haystack `iterations` times after a short warm-up,
+    /// returning the throughput in GiB/s. Scalar is always measured first.
+    /// The throughput depends on where, if at all, the pattern matches,
+    /// so it's best measured on representative data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = vec![0xcc; 0x10000];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 57");
+    /// for (mode, throughput) in scanner.benchmark(&haystack, 10) {
+    ///     println!("{:?}: {:.2} GiB/s", mode, throughput);
+    /// }
+    /// ```
+    pub fn benchmark(&self, haystack: &[u8], iterations: usize) -> Vec<(ScanMode, f64)> {
+        let iterations = iterations.max(1);
+
+        backends::supported_modes()
+            .into_iter()
+            .map(|mode| {
+                let elapsed = time_scans(iterations, || {
+                    hint::black_box(self.find_in_slice(Some(mode), hint::black_box(haystack), 0));
+                });
+                // a coarse clock may not register a scan of a tiny haystack at all
+                let elapsed = elapsed.as_secs_f64().max(1e-9);
+
+                let scanned = (haystack.len() * iterations) as f64;
+                (mode, scanned / GIB / elapsed)
+            })
+            .collect()
+    }
+
+    /// Measure the throughput of [`Scanner::find`] without a preferred scan mode on `haystack`
+    ///
+    /// This is the scan a plain `find(None, ..)` runs, using whichever backend or substring
+    /// search it picks for this pattern and haystack, so it shows whether a fast backend is
+    /// engaged. The haystack is scanned `iterations` times after a short warm-up.
+    /// See [`Scanner::benchmark`] to compare the scan modes instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = vec![0xcc; 0x10000];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 57");
+    /// let bench = scanner.benchmark_find(&haystack, 10);
+    ///
+    /// assert_eq!(bench.bytes, 10 * haystack.len());
+    /// println!("{:.2} GiB/s", bench.gibps);
+    /// ```
+    pub fn benchmark_find(&self, haystack: &[u8], iterations: usize) -> ScanBench {
+        let iterations = iterations.max(1);
+
+        let elapsed = time_scans(iterations, || {
+            hint::black_box(self.find_in_slice(None, hint::black_box(haystack), 0));
+        });
+
+        let bytes = haystack.len() * iterations;
+        // a coarse clock may not register a scan of a tiny haystack at all
+        let gibps = bytes as f64 / GIB / elapsed.as_secs_f64().max(1e-9);
+
+        ScanBench {
+            bytes,
+            elapsed,
+            gibps,
+        }
+    }
+}
+
+/// Time `iterations` runs of `scan`, after warming up with a tenth as many
+fn time_scans(iterations: usize, mut scan: impl FnMut()) -> Duration {
+    for _ in 0..iterations.div_ceil(10) {
+        scan();
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        scan();
+    }
+    start.elapsed()
+}
@@ -0,0 +1,193 @@
+//! Rescanning data which mostly stays the same between scans
+
+use crate::pattern::Pattern;
+use crate::Scanner;
+
+/// Cache of the matches of a previous scan, rescanning only blocks whose contents changed
+///
+/// The haystack is split into blocks of a fixed size. Along with the matches starting in
+/// every block, the cache remembers a hash of the block's contents. The next scan only
+/// rescans blocks which changed, or whose matches could extend into a block which did.
+///
+/// Blocks are compared by a 64-bit non-cryptographic hash, so a change which happens to
+/// keep the hash of a block the same goes unnoticed. This is practically impossible for
+/// accidental changes, but data crafted to collide can hide matches from the cache.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::cache::ScanCache;
+/// use lightningscanner::Scanner;
+///
+/// let mut memory = vec![0x00; 1024 * 1024];
+/// memory[0x100..0x104].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+///
+/// let scanner = Scanner::new("48 89 5c 24");
+/// let mut cache = ScanCache::new();
+/// assert_eq!(cache.scan(&scanner, &memory), [0x100]);
+///
+/// memory[0x8_0000..0x8_0004].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+/// assert_eq!(cache.scan(&scanner, &memory), [0x100, 0x8_0000]);
+///
+/// // the changed block, and the one before it whose matches may extend into it
+/// assert_eq!(cache.rescanned_blocks(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScanCache {
+    block_size: usize,
+    /// Pattern the cached matches belong to
+    pattern: Option<Pattern>,
+    /// Length of the previous haystack
+    len: usize,
+    hashes: Vec<u64>,
+    /// Offsets of the matches starting within every block
+    block_matches: Vec<Vec<usize>>,
+    matches: Vec<usize>,
+    rescanned_blocks: usize,
+}
+
+impl ScanCache {
+    /// Default size of the blocks the haystack is split into, 64 KiB
+    pub const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+
+    /// Create a new empty cache using blocks of [`ScanCache::DEFAULT_BLOCK_SIZE`]
+    pub fn new() -> Self {
+        ScanCache {
+            block_size: Self::DEFAULT_BLOCK_SIZE,
+            pattern: None,
+            len: 0,
+            hashes: Vec::new(),
+            block_matches: Vec::new(),
+            matches: Vec::new(),
+            rescanned_blocks: 0,
+        }
+    }
+
+    /// Use blocks of `block_size` bytes, clearing the cache
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        assert!(block_size != 0, "block size must not be zero");
+
+        self.clear();
+        self.block_size = block_size;
+        self
+    }
+
+    /// Find the offsets of all occurrences of the scanner's pattern in `haystack`
+    ///
+    /// The result is the same as that of [`Scanner::find_all_offsets`]. Matches of blocks
+    /// which didn't change since the previous scan are reused, scanning with a scanner for
+    /// a different pattern than the previous one rescans everything.
+    pub fn scan(&mut self, scanner: &Scanner, haystack: &[u8]) -> &[usize] {
+        if self.pattern.as_ref() != Some(&scanner.pattern) {
+            self.clear();
+            self.pattern = Some(scanner.pattern.clone());
+        }
+
+        let block_size = self.block_size;
+        let overlap = scanner.pattern.unpadded_size.saturating_sub(1);
+        // empty haystacks still have a single empty block, empty patterns match in it
+        let block_count = haystack.len().div_ceil(block_size).max(1);
+
+        let hashes = (0..block_count)
+            .map(|block| hash_block(&haystack[block_range(block, block_size, haystack.len())]))
+            .collect::<Vec<_>>();
+        let changed = (0..block_count)
+            .map(|block| self.hashes.get(block) != Some(&hashes[block]))
+            .collect::<Vec<_>>();
+
+        self.block_matches.resize_with(block_count, Vec::new);
+        self.rescanned_blocks = 0;
+
+        for block in 0..block_count {
+            let start = block * block_size;
+            let end = start + block_size;
+
+            // bytes the matches starting within the block may cover, before and now
+            let window_end = end.saturating_add(overlap).min(haystack.len());
+            let previous_window_end = end.saturating_add(overlap).min(self.len);
+            let last_covered = window_end.saturating_sub(1).max(start) / block_size;
+
+            // the last block also owns the matches of empty patterns at the very end
+            let last = block + 1 == block_count;
+            let previously_last = block + 1 == self.hashes.len();
+
+            let stale = window_end != previous_window_end
+                || last != previously_last
+                || changed[block..=last_covered.min(block_count - 1)]
+                    .iter()
+                    .any(|&changed| changed);
+            if !stale {
+                continue;
+            }
+
+            let end = if last { haystack.len() + 1 } else { end };
+
+            self.block_matches[block] = scanner.find_all_starting_in(None, haystack, start, end);
+            self.rescanned_blocks += 1;
+        }
+
+        self.hashes = hashes;
+        self.len = haystack.len();
+
+        self.matches.clear();
+        for matches in &self.block_matches {
+            self.matches.extend_from_slice(matches);
+        }
+
+        &self.matches
+    }
+
+    /// The matches found by the latest scan
+    pub fn matches(&self) -> &[usize] {
+        &self.matches
+    }
+
+    /// Number of blocks the latest scan had to rescan
+    pub fn rescanned_blocks(&self) -> usize {
+        self.rescanned_blocks
+    }
+
+    /// Forget all cached matches, so the next scan rescans everything
+    pub fn clear(&mut self) {
+        self.pattern = None;
+        self.len = 0;
+        self.hashes.clear();
+        self.block_matches.clear();
+        self.matches.clear();
+    }
+}
+
+impl Default for ScanCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Byte range of a block within a haystack of `len` bytes
+fn block_range(block: usize, block_size: usize, len: usize) -> std::ops::Range<usize> {
+    let start = (block * block_size).min(len);
+    start..(start + block_size).min(len)
+}
+
+/// Hash the contents of a block, a word at a time
+fn hash_block(block: &[u8]) -> u64 {
+    const MULTIPLIER: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    let mut words = block.chunks_exact(8);
+    let mut hash = (block.len() as u64).wrapping_mul(MULTIPLIER);
+
+    for word in &mut words {
+        let word = u64::from_le_bytes(word.try_into().unwrap());
+        hash = (hash ^ word).wrapping_mul(MULTIPLIER).rotate_left(29);
+    }
+
+    let mut tail = [0; 8];
+    tail[..words.remainder().len()].copy_from_slice(words.remainder());
+    hash = (hash ^ u64::from_le_bytes(tail)).wrapping_mul(MULTIPLIER);
+
+    hash ^ (hash >> 32)
+}
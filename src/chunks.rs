@@ -0,0 +1,155 @@
+//! Scanning memory split across several buffers
+
+use crate::Scanner;
+
+impl Scanner {
+    /// Find the first occurrence of the pattern in the concatenation of `chunks`
+    ///
+    /// The chunks are scanned in place, matches straddling chunk boundaries are found
+    /// by stitching together the last `pattern_len - 1` bytes before a boundary
+    /// with the bytes following it. The returned offset is relative to the beginning
+    /// of the first chunk, as if all chunks were one contiguous buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let pages = [vec![0x00, 0x48, 0x89], vec![0x5c, 0x24, 0x00]];
+    /// let chunks = pages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// assert_eq!(scanner.find_in_chunks(&chunks), Some(1));
+    /// ```
+    pub fn find_in_chunks(&self, chunks: &[&[u8]]) -> Option<u64> {
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+
+        // tail of the logical buffer preceding the current chunk, which may still start a match
+        let mut carry = Vec::with_capacity(overlap * 2);
+        let mut chunk_start = 0u64;
+
+        for chunk in chunks {
+            let carry_start = chunk_start - carry.len() as u64;
+            let chunk_len = chunk.len() as u64;
+
+            if !carry.is_empty() {
+                // matches starting in the carried bytes end within the first `overlap` bytes
+                carry.extend_from_slice(&chunk[..overlap.min(chunk.len())]);
+
+                if let Some(offset) = self.find_in_slice(None, &carry, 0) {
+                    return Some(carry_start + offset as u64);
+                }
+
+                carry.truncate((chunk_start - carry_start) as usize);
+            }
+
+            if let Some(offset) = self.find_in_slice(None, chunk, 0) {
+                return Some(chunk_start + offset as u64);
+            }
+
+            carry.extend_from_slice(&chunk[chunk.len().saturating_sub(overlap)..]);
+            carry.drain(..carry.len().saturating_sub(overlap));
+
+            chunk_start += chunk_len;
+        }
+
+        None
+    }
+
+    /// Find the first occurrence of the pattern in memory segments loaded at known base addresses
+    ///
+    /// Segments are given as `(base, bytes)` pairs and scanned in order. Consecutive segments
+    /// which are contiguous in memory (`prev_base + prev_len == next_base`) are treated as one
+    /// buffer like in [`Scanner::find_in_chunks`], so matches can span them. Other segments are
+    /// scanned independently. The returned value is the absolute address of the match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let text = [0x00, 0x48, 0x89];
+    /// let rodata = [0x5c, 0x24, 0x00];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// assert_eq!(scanner.find_scattered(&[(0x1000, &text), (0x1003, &rodata)]), Some(0x1001));
+    /// assert_eq!(scanner.find_scattered(&[(0x1000, &text), (0x2000, &rodata)]), None);
+    /// ```
+    pub fn find_scattered(&self, segments: &[(usize, &[u8])]) -> Option<usize> {
+        let mut chunks = Vec::new();
+
+        let mut rest = segments;
+        while let Some(&(base, first)) = rest.first() {
+            chunks.clear();
+            chunks.push(first);
+
+            let mut end = base.checked_add(first.len());
+            let contiguous = rest[1..]
+                .iter()
+                .take_while(|&&(next_base, next)| {
+                    let is_contiguous = end == Some(next_base);
+                    end = next_base.checked_add(next.len());
+                    is_contiguous
+                })
+                .count();
+
+            chunks.extend(rest[1..=contiguous].iter().map(|&(_, segment)| segment));
+            rest = &rest[1 + contiguous..];
+
+            if let Some(offset) = self.find_in_chunks(&chunks) {
+                return Some(base + offset as usize);
+            }
+        }
+
+        None
+    }
+
+    /// Split `haystack` into windows which can be scanned independently
+    ///
+    /// Windows start every `window` bytes and extend `pattern_len - 1` bytes into the next
+    /// one, so every match of a non-empty pattern lies in exactly one window and is found
+    /// exactly once when the windows are scanned separately. Each window is yielded along
+    /// with its offset from the beginning of `haystack`, which is added to the offsets found
+    /// within it to merge the results. An empty haystack has no windows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0x00, 0x00, 0x48, 0x89, 0x5c, 0x24, 0x00, 0x00];
+    /// let scanner = Scanner::new("48 89 5c 24");
+    ///
+    /// let matches = scanner
+    ///     .windows(&haystack, 3)
+    ///     .flat_map(|(base, window)| {
+    ///         let offsets = scanner.find_all_offsets(None, window);
+    ///         offsets.into_iter().map(move |offset| base + offset)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(matches, [2]);
+    /// ```
+    pub fn windows<'a>(
+        &self,
+        haystack: &'a [u8],
+        window: usize,
+    ) -> impl Iterator<Item = (usize, &'a [u8])> + 'a {
+        assert!(window != 0, "window size must not be zero");
+
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+
+        (0..haystack.len()).step_by(window).map(move |start| {
+            let end = start
+                .saturating_add(window)
+                .saturating_add(overlap)
+                .min(haystack.len());
+
+            (start, &haystack[start..end])
+        })
+    }
+}
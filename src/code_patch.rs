@@ -0,0 +1,360 @@
+//! Patching code in the memory of the current process
+//!
+//! Only available with the `patch` feature. Changing memory protection is supported
+//! on Windows, Linux and Android, other platforms report [`io::ErrorKind::Unsupported`].
+
+use crate::ScanResult;
+use std::{io, ptr};
+
+impl ScanResult {
+    /// Overwrite the bytes at the result address, even if the memory isn't writable
+    ///
+    /// The pages containing the patched bytes are made writable for the duration of the copy,
+    /// using `VirtualProtect` on Windows and `mprotect` elsewhere. Their original protection
+    /// is restored afterwards, and the instruction cache is flushed for the patched range
+    /// on architectures which require it.
+    ///
+    /// # Safety
+    ///
+    /// **This writes straight into the memory of the running process. Nothing about the
+    /// target is checked besides it being mapped, violating any of the following conditions
+    /// corrupts the process or crashes it, possibly long after the call.**
+    ///
+    /// * The result points into memory of the current process, and `new_bytes.len()` bytes
+    ///   starting at it are mapped.
+    ///
+    /// * No other thread executes, reads or writes the patched bytes while they are copied,
+    ///   and none of them changes the protection of the affected pages during the call.
+    ///   The calling thread must not be executing code on the affected pages either.
+    ///
+    /// * If the bytes are code, the patch leaves them valid to execute from every instruction
+    ///   boundary any thread can reach them at, e.g. by replacing whole instructions.
+    ///
+    /// * Nothing else relies on the patched memory staying unchanged, such as Rust references
+    ///   into it or read-only data the compiler may assume to be constant.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] for an invalid result, or if the patched
+    /// range isn't fully mapped. Errors reported by the OS while changing the protection are
+    /// returned as is, in which case nothing was written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lightningscanner::Scanner;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// # let (code, code_len) = (std::ptr::null::<u8>(), 0);
+    /// let result = unsafe { Scanner::new("74 ?? 48 8b").find(None, code, code_len) };
+    ///
+    /// // SAFETY: the match is in the code of this process, and no thread runs it yet
+    /// unsafe { result.patch_bytes(&[0xeb])? };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn patch_bytes(&self, new_bytes: &[u8]) -> io::Result<()> {
+        if !self.is_valid() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "can't patch an invalid scan result",
+            ));
+        }
+
+        if new_bytes.is_empty() {
+            return Ok(());
+        }
+
+        let start = self.addr as usize;
+        let end = start.checked_add(new_bytes.len()).ok_or_else(unmapped)?;
+
+        let changed = os::make_writable(start..end)?;
+
+        // SAFETY: the caller guarantees the range is mapped memory no one else accesses,
+        // and it was made writable above
+        unsafe { ptr::copy_nonoverlapping(new_bytes.as_ptr(), start as *mut u8, new_bytes.len()) };
+
+        let restored = os::restore(&changed);
+        os::flush_instruction_cache(start..end);
+
+        restored
+    }
+}
+
+/// Error for a patched range which isn't fully mapped
+fn unmapped() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "patched range isn't mapped memory",
+    )
+}
+
+/// Part of a patched range that was made writable, with the protection it had before
+#[cfg(any(target_os = "linux", target_os = "android", windows))]
+struct Changed<P> {
+    range: std::ops::Range<usize>,
+    protection: P,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod os {
+    use super::{unmapped, Changed};
+    use crate::process;
+    use std::io;
+    use std::ops::Range;
+
+    /// Make every page overlapping `range` writable, keeping read and execute permissions
+    ///
+    /// Protection changed before a failure is restored before returning the error.
+    pub fn make_writable(range: Range<usize>) -> io::Result<Vec<Changed<libc::c_int>>> {
+        // SAFETY: querying the page size has no preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+
+        let pages_start = range.start / page_size * page_size;
+        let pages_end = range
+            .end
+            .checked_next_multiple_of(page_size)
+            .ok_or_else(unmapped)?;
+
+        let mut changed = Vec::new();
+        let mut address = pages_start;
+
+        for region in process::regions(std::process::id())? {
+            if region.end <= address {
+                continue;
+            }
+            if region.start > address || address >= pages_end {
+                break;
+            }
+
+            let end = region.end.min(pages_end);
+
+            let mut protection = libc::PROT_NONE;
+            if region.readable {
+                protection |= libc::PROT_READ;
+            }
+            if region.writable {
+                protection |= libc::PROT_WRITE;
+            }
+            if region.executable {
+                protection |= libc::PROT_EXEC;
+            }
+
+            if !region.writable {
+                let writable = protection | libc::PROT_READ | libc::PROT_WRITE;
+
+                if let Err(err) = protect(address..end, writable) {
+                    restore(&changed)?;
+                    return Err(err);
+                }
+
+                changed.push(Changed {
+                    range: address..end,
+                    protection,
+                });
+            }
+
+            address = end;
+        }
+
+        if address < pages_end {
+            restore(&changed)?;
+            return Err(unmapped());
+        }
+
+        Ok(changed)
+    }
+
+    /// Restore the protection of every part changed by [`make_writable`]
+    pub fn restore(changed: &[Changed<libc::c_int>]) -> io::Result<()> {
+        changed
+            .iter()
+            .try_for_each(|changed| protect(changed.range.clone(), changed.protection))
+    }
+
+    fn protect(pages: Range<usize>, protection: libc::c_int) -> io::Result<()> {
+        // SAFETY: the range is page aligned and within mappings of the process,
+        // the caller of `patch_bytes` guarantees no one else relies on its protection
+        let result = unsafe {
+            libc::mprotect(
+                pages.start as *mut libc::c_void,
+                pages.end - pages.start,
+                protection,
+            )
+        };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Make the patched bytes visible to instruction fetches
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn flush_instruction_cache(_range: Range<usize>) {
+        // the instruction cache is coherent with data writes on x86
+    }
+
+    /// Make the patched bytes visible to instruction fetches
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    pub fn flush_instruction_cache(range: Range<usize>) {
+        extern "C" {
+            fn __clear_cache(start: *mut libc::c_char, end: *mut libc::c_char);
+        }
+
+        // SAFETY: the range was just written to, so it's mapped memory of the process
+        unsafe { __clear_cache(range.start as *mut _, range.end as *mut _) };
+    }
+}
+
+#[cfg(windows)]
+mod os {
+    use super::{unmapped, Changed};
+    use std::ffi::c_void;
+    use std::io;
+    use std::ops::Range;
+    use std::{mem, ptr};
+
+    const MEM_COMMIT: u32 = 0x1000;
+    const PAGE_EXECUTE_READWRITE: u32 = 0x40;
+
+    // `PartitionId` is left out, it occupies what would be padding before `RegionSize`
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct MemoryBasicInformation {
+        base_address: *mut c_void,
+        allocation_base: *mut c_void,
+        allocation_protect: u32,
+        region_size: usize,
+        state: u32,
+        protect: u32,
+        kind: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn VirtualQuery(
+            address: *const c_void,
+            buffer: *mut MemoryBasicInformation,
+            length: usize,
+        ) -> usize;
+        fn VirtualProtect(
+            address: *const c_void,
+            size: usize,
+            new_protect: u32,
+            old_protect: *mut u32,
+        ) -> i32;
+        fn GetCurrentProcess() -> *mut c_void;
+        fn FlushInstructionCache(process: *mut c_void, address: *const c_void, size: usize) -> i32;
+    }
+
+    /// Make every page overlapping `range` writable and executable
+    ///
+    /// Protection changed before a failure is restored before returning the error.
+    pub fn make_writable(range: Range<usize>) -> io::Result<Vec<Changed<u32>>> {
+        let mut changed = Vec::new();
+        let mut address = range.start;
+
+        while address < range.end {
+            let mut info = mem::MaybeUninit::<MemoryBasicInformation>::uninit();
+
+            // SAFETY: `info` is a valid buffer of the passed size
+            let written = unsafe {
+                VirtualQuery(
+                    address as *const c_void,
+                    info.as_mut_ptr(),
+                    mem::size_of::<MemoryBasicInformation>(),
+                )
+            };
+            if written == 0 {
+                restore(&changed)?;
+                return Err(io::Error::last_os_error());
+            }
+
+            // SAFETY: `VirtualQuery` filled in the buffer
+            let info = unsafe { info.assume_init() };
+            if info.state != MEM_COMMIT {
+                restore(&changed)?;
+                return Err(unmapped());
+            }
+
+            let end = (info.base_address as usize + info.region_size).min(range.end);
+
+            match protect(address..end, PAGE_EXECUTE_READWRITE) {
+                Ok(protection) => changed.push(Changed {
+                    range: address..end,
+                    protection,
+                }),
+                Err(err) => {
+                    restore(&changed)?;
+                    return Err(err);
+                }
+            }
+
+            address = end;
+        }
+
+        Ok(changed)
+    }
+
+    /// Restore the protection of every part changed by [`make_writable`]
+    pub fn restore(changed: &[Changed<u32>]) -> io::Result<()> {
+        changed
+            .iter()
+            .try_for_each(|changed| protect(changed.range.clone(), changed.protection).map(|_| ()))
+    }
+
+    /// Change the protection of the pages overlapping `range`, returning the previous one
+    fn protect(range: Range<usize>, protection: u32) -> io::Result<u32> {
+        let mut previous = 0;
+
+        // SAFETY: the range is within a committed region of the process,
+        // the caller of `patch_bytes` guarantees no one else relies on its protection
+        let result = unsafe {
+            VirtualProtect(
+                range.start as *const c_void,
+                range.end - range.start,
+                protection,
+                ptr::addr_of_mut!(previous),
+            )
+        };
+
+        if result != 0 {
+            Ok(previous)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Make the patched bytes visible to instruction fetches
+    pub fn flush_instruction_cache(range: Range<usize>) {
+        // SAFETY: flushing any range of the current process is sound
+        unsafe {
+            FlushInstructionCache(
+                GetCurrentProcess(),
+                range.start as *const c_void,
+                range.end - range.start,
+            );
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+mod os {
+    use std::io;
+    use std::ops::Range;
+
+    pub fn make_writable(_range: Range<usize>) -> io::Result<Vec<()>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "changing memory protection isn't supported on this platform",
+        ))
+    }
+
+    pub fn restore(_changed: &[()]) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn flush_instruction_cache(_range: Range<usize>) {}
+}
@@ -0,0 +1,162 @@
+//! Compile-time parser for IDA-style patterns
+//!
+//! This is an implementation detail of the `create_pattern!` and `find_pattern_const!` macros.
+//! All functions inside are `const` and are evaluated at compile time.
+
+// A struct to hold the result of the compile-time parsing.
+// The arrays have a fixed size, and `len` holds the actual pattern length.
+// This is a common pattern for `const` contexts where dynamic allocation is not possible.
+// Bytes past the capacity `N` are only counted, `pattern_len` sizes the arrays to fit.
+pub struct ParsedPattern<const N: usize> {
+    pub data: [u8; N],
+    pub mask: [u8; N],
+    pub len: usize,
+    pub cursor: usize,
+}
+
+// A `const` version of `char_to_byte` from the original `Pattern::new`.
+const fn char_to_byte(c: u8) -> u8 {
+    if c >= b'a' && c <= b'z' {
+        c - b'a' + 0xA
+    } else if c >= b'A' && c <= b'Z' {
+        c - b'A' + 0xA
+    } else if c >= b'0' && c <= b'9' {
+        c - b'0'
+    } else {
+        0
+    }
+}
+
+// The number of bytes of the pattern, the capacity `parse_pattern` needs to store all of them.
+pub const fn pattern_len(pattern: &str) -> usize {
+    parse_pattern::<0>(pattern).len
+}
+
+// The main `const` function to parse the pattern string.
+// Wildcards are left as the zeroed data and mask the arrays start out with.
+pub const fn parse_pattern<const N: usize>(pattern: &str) -> ParsedPattern<N> {
+    let pattern = pattern.as_bytes();
+    let mut data = [0u8; N];
+    let mut mask = [0u8; N];
+    let mut len: usize = 0;
+    let mut concrete = false;
+    let mut cursor = None;
+    let mut i = 0;
+
+    while i < pattern.len() {
+        let symbol = pattern[i];
+        let next_symbol = if i + 1 < pattern.len() {
+            pattern[i + 1]
+        } else {
+            b'\0'
+        };
+
+        i += 1;
+
+        match symbol {
+            b' ' | b'\t' | b'\n' | b'\r' => continue,
+            b'/' if next_symbol == b'/' => {
+                while i < pattern.len() && pattern[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if next_symbol == b'*' => {
+                i += 1;
+                loop {
+                    if i + 1 >= pattern.len() {
+                        panic!("Unterminated comment");
+                    }
+                    if pattern[i] == b'*' && pattern[i + 1] == b'/' {
+                        i += 2;
+                        break;
+                    }
+                    i += 1;
+                }
+                continue;
+            }
+            b'^' => {
+                if cursor.is_some() {
+                    panic!("Pattern contains more than one cursor marker");
+                }
+                cursor = Some(len);
+                continue;
+            }
+            b'@' => {
+                panic!("Label markers are not supported at compile time, use `Pattern::new`");
+            }
+            b'?' => {
+                // `?{n}` expands into `n` wildcard bytes
+                if next_symbol == b'{' {
+                    i += 1;
+
+                    let mut count: usize = 0;
+                    let mut digits = 0;
+                    while i < pattern.len() && pattern[i] >= b'0' && pattern[i] <= b'9' {
+                        count = match count.checked_mul(10) {
+                            Some(count) => match count.checked_add((pattern[i] - b'0') as usize) {
+                                Some(count) => count,
+                                None => panic!("Malformed wildcard quantifier"),
+                            },
+                            None => panic!("Malformed wildcard quantifier"),
+                        };
+                        digits += 1;
+                        i += 1;
+                    }
+
+                    if digits == 0 || count == 0 || i >= pattern.len() || pattern[i] != b'}' {
+                        panic!("Malformed wildcard quantifier");
+                    }
+                    i += 1;
+
+                    len = match len.checked_add(count) {
+                        Some(len) => len,
+                        None => panic!("Malformed wildcard quantifier"),
+                    };
+                    continue;
+                }
+
+                len += 1;
+
+                if next_symbol == b'?' {
+                    i += 1;
+                }
+                continue;
+            }
+            _ => {
+                if symbol.is_ascii_hexdigit()
+                    && matches!(next_symbol, b'\0' | b' ' | b'\t' | b'\n' | b'\r')
+                {
+                    panic!("Pattern contains a lone hex digit");
+                }
+                if !symbol.is_ascii_hexdigit() || !next_symbol.is_ascii_hexdigit() {
+                    panic!("Malformed hex byte");
+                }
+
+                if len < N {
+                    data[len] = (char_to_byte(symbol) << 4) | char_to_byte(next_symbol);
+                    mask[len] = 0xff;
+                }
+                len += 1;
+                concrete = true;
+                i += 1;
+            }
+        }
+    }
+
+    if len != 0 && !concrete {
+        panic!("Pattern consists of wildcards only");
+    }
+
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => 0,
+    };
+
+    ParsedPattern {
+        data,
+        mask,
+        len,
+        cursor,
+    }
+}
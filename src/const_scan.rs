@@ -0,0 +1,41 @@
+//! Compile-time scanning
+
+/// Find the first occurrence of a pattern in `haystack` in a `const` context
+///
+/// `data` and `mask` hold the unpadded pattern bytes and must be of the same length.
+/// Matches follow the same rules as the runtime scanners: only matches that fit entirely
+/// within `haystack` are reported, and an empty pattern matches at offset 0.
+///
+/// This uses the naive algorithm, it's meant for data embedded with `include_bytes!`.
+/// See [`find_pattern_const!`](crate::find_pattern_const) for a version taking a pattern string.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::const_scan::const_find;
+///
+/// const OFFSET: Option<usize> = const_find(&[0x00, 0xde, 0xad, 0xbe], &[0xde, 0x00], &[0xff, 0x00]);
+/// assert_eq!(OFFSET, Some(1));
+/// ```
+pub const fn const_find(haystack: &[u8], data: &[u8], mask: &[u8]) -> Option<usize> {
+    assert!(data.len() == mask.len(), "data and mask lengths differ");
+
+    if haystack.len() < data.len() {
+        return None;
+    }
+
+    let mut offset = 0;
+    while offset <= haystack.len() - data.len() {
+        let mut i = 0;
+        while i < data.len() && haystack[offset + i] & mask[i] == data[i] & mask[i] {
+            i += 1;
+        }
+
+        if i == data.len() {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+
+    None
+}
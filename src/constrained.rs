@@ -0,0 +1,121 @@
+//! Matches rejected by nearby patterns
+
+use crate::pattern::Pattern;
+use crate::{ScanMode, Scanner};
+use std::ops::Range;
+
+/// Window of bytes relative to the start of a match, see [`ConstrainedScanner::with_exclusion`]
+///
+/// Negative offsets are before the match start. The window covers `start..end`,
+/// so `-1..0` is the byte right before the match.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RelativeRange {
+    /// Offset of the first byte of the window
+    pub start: isize,
+    /// Offset one past the last byte of the window
+    pub end: isize,
+}
+
+impl From<Range<isize>> for RelativeRange {
+    fn from(value: Range<isize>) -> Self {
+        RelativeRange {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+/// Scanner skipping matches around which any of a set of excluded patterns occurs
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::constrained::ConstrainedScanner;
+/// use lightningscanner::pattern::Pattern;
+///
+/// let binary = [0xcc, 0x48, 0x8b, 0x05, 0x90, 0x48, 0x8b, 0x05];
+///
+/// // skip matches right after int3 padding
+/// let scanner = ConstrainedScanner::new(Pattern::new("48 8b 05"))
+///     .with_exclusion(Pattern::new("cc"), -1..0);
+///
+/// assert_eq!(scanner.find(None, &binary), Some(5));
+/// ```
+#[derive(Debug)]
+pub struct ConstrainedScanner {
+    scanner: Scanner,
+    exclusions: Vec<(Scanner, RelativeRange)>,
+}
+
+impl ConstrainedScanner {
+    /// Create a new [`ConstrainedScanner`] for `pattern`, without any exclusions
+    pub fn new(pattern: Pattern) -> Self {
+        ConstrainedScanner {
+            scanner: Scanner::from(pattern),
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Reject matches for which `excluded` occurs entirely within `range` of them
+    ///
+    /// The parts of the window before the start or past the end of the haystack
+    /// can't contain the excluded pattern.
+    pub fn with_exclusion(mut self, excluded: Pattern, range: impl Into<RelativeRange>) -> Self {
+        self.exclusions
+            .push((Scanner::from(excluded), range.into()));
+        self
+    }
+
+    /// Find the offset of the first match in `haystack` which none of the exclusions reject
+    pub fn find(&self, preferred_scan_mode: Option<ScanMode>, haystack: &[u8]) -> Option<usize> {
+        self.matches(preferred_scan_mode, haystack).next()
+    }
+
+    /// Find the offsets of all matches in `haystack` which none of the exclusions reject
+    ///
+    /// Overlapping matches are reported separately, in ascending order.
+    pub fn find_all(&self, preferred_scan_mode: Option<ScanMode>, haystack: &[u8]) -> Vec<usize> {
+        self.matches(preferred_scan_mode, haystack).collect()
+    }
+
+    fn matches<'a>(
+        &'a self,
+        preferred_scan_mode: Option<ScanMode>,
+        haystack: &'a [u8],
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut start = 0;
+
+        std::iter::from_fn(move || {
+            let offset = self
+                .scanner
+                .find_in_slice(preferred_scan_mode, haystack, start)?;
+            start = offset + 1;
+
+            Some(offset)
+        })
+        .filter(move |&offset| !self.is_excluded(preferred_scan_mode, haystack, offset))
+    }
+
+    fn is_excluded(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        haystack: &[u8],
+        offset: usize,
+    ) -> bool {
+        let clamp = |relative: isize| {
+            offset
+                .checked_add_signed(relative)
+                .map_or(if relative < 0 { 0 } else { usize::MAX }, |offset| offset)
+                .min(haystack.len())
+        };
+
+        self.exclusions.iter().any(|(excluded, range)| {
+            let window = clamp(range.start)..clamp(range.end);
+
+            window.start < window.end
+                && excluded
+                    .find_in_range(preferred_scan_mode, haystack, window)
+                    .is_some()
+        })
+    }
+}
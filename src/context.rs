@@ -0,0 +1,171 @@
+//! Matches with surrounding context bytes
+
+use crate::{ScanMode, ScanResult, Scanner};
+use std::fmt::Write;
+
+/// Number of bytes to capture around every match, see [`Scanner::find_all_with_context`]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ContextOptions {
+    /// Bytes to capture before the match
+    pub context_before: usize,
+    /// Bytes to capture after the match
+    pub context_after: usize,
+}
+
+/// Match with the bytes surrounding it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ContextMatch<'a> {
+    offset: usize,
+    context: &'a [u8],
+    offset_in_context: usize,
+}
+
+impl<'a> ContextMatch<'a> {
+    /// Offset of the match from the beginning of the haystack
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The match and the bytes around it, clamped at the haystack edges
+    pub fn context(&self) -> &'a [u8] {
+        self.context
+    }
+
+    /// Offset of the match within [`ContextMatch::context`]
+    pub fn offset_in_context(&self) -> usize {
+        self.offset_in_context
+    }
+}
+
+impl Scanner {
+    /// Find all occurrences of the pattern in `binary`, along with the bytes around them
+    ///
+    /// The context spans `options.context_before` bytes before the start of the match
+    /// to `options.context_after` bytes after its end, clamped at the edges of `binary`.
+    /// Overlapping occurrences are reported separately, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::context::ContextOptions;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xcc, 0xcc, 0x48, 0x89, 0x5c, 0x24, 0x08, 0x57];
+    /// let options = ContextOptions {
+    ///     context_before: 1,
+    ///     context_after: 16,
+    /// };
+    ///
+    /// let matches = Scanner::new("48 89 5c 24").find_all_with_context(None, &binary, options);
+    ///
+    /// assert_eq!(matches[0].offset(), 2);
+    /// assert_eq!(matches[0].context(), &binary[1..]);
+    /// assert_eq!(matches[0].offset_in_context(), 1);
+    /// ```
+    pub fn find_all_with_context<'a>(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &'a [u8],
+        options: ContextOptions,
+    ) -> Vec<ContextMatch<'a>> {
+        self.find_all_offsets(preferred_scan_mode, binary)
+            .into_iter()
+            .map(|offset| {
+                let start = offset.saturating_sub(options.context_before);
+                let end = (offset + self.pattern.unpadded_size)
+                    .saturating_add(options.context_after)
+                    .min(binary.len());
+
+                ContextMatch {
+                    offset,
+                    context: &binary[start..end],
+                    offset_in_context: offset - start,
+                }
+            })
+            .collect()
+    }
+}
+
+impl ScanResult {
+    /// Format a hexdump of the match and the bytes around it, the match bracketed
+    ///
+    /// The dump spans `before` bytes before the match to `after` bytes after its end,
+    /// clamped at the edges of the buffer at `base`. Every line shows 16 bytes, prefixed
+    /// with the address of the first one. `match_len` is the length of the matched
+    /// pattern, which the result itself doesn't know.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result doesn't point into the buffer.
+    ///
+    /// # Safety
+    ///
+    /// * `base` - is a valid pointer
+    ///
+    /// * `len` - corresponds to a valid size of the buffer at `base`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xcc, 0xcc, 0x48, 0x89, 0x5c, 0x24, 0x08, 0x57];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    ///
+    /// // SAFETY: `binary` is valid for its whole length
+    /// let dump = unsafe { result.context_dump(binary.as_ptr(), binary.len(), 4, 1, 1) };
+    /// assert!(dump.ends_with(": cc [48 89 5c 24] 08"));
+    /// ```
+    pub unsafe fn context_dump(
+        &self,
+        base: *const u8,
+        len: usize,
+        match_len: usize,
+        before: usize,
+        after: usize,
+    ) -> String {
+        let offset = (self.addr as usize).wrapping_sub(base as usize);
+        assert!(
+            self.is_valid() && offset < len,
+            "scan result at {:p} is outside of the buffer of {} bytes at {:p}",
+            self.addr,
+            len,
+            base
+        );
+
+        // SAFETY: the caller guarantees `base` is valid for `len` bytes
+        let buffer = unsafe { std::slice::from_raw_parts(base, len) };
+
+        let match_end = offset.saturating_add(match_len).min(len);
+        let start = offset.saturating_sub(before);
+        let end = match_end.saturating_add(after).min(len);
+
+        let mut dump = String::new();
+        for line_start in (start..end).step_by(16) {
+            if !dump.is_empty() {
+                dump.push('\n');
+            }
+            write!(dump, "{:#x}:", base as usize + line_start).unwrap();
+
+            let line_end = (line_start + 16).min(end);
+            for (index, byte) in buffer.iter().enumerate().take(line_end).skip(line_start) {
+                let in_match = (offset..match_end).contains(&index);
+                let open = in_match && (index == offset || index == line_start);
+                let close = in_match && (index + 1 == match_end || index + 1 == line_end);
+
+                dump.push(' ');
+                if open {
+                    dump.push('[');
+                }
+                write!(dump, "{:02x}", byte).unwrap();
+                if close {
+                    dump.push(']');
+                }
+            }
+        }
+
+        dump
+    }
+}
@@ -0,0 +1,207 @@
+//! Scans which give up once a deadline has passed
+
+use crate::{ScanMode, Scanner};
+use std::ops::ControlFlow;
+use std::time::Instant;
+
+/// Options for [`Scanner::find_with_options`] and [`Scanner::find_all_with_options`]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ScanOptions {
+    preferred_scan_mode: Option<ScanMode>,
+    deadline: Option<Instant>,
+}
+
+impl ScanOptions {
+    /// Create options without a preferred scan mode or a deadline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefer the given scan mode, see [`Scanner::find`]
+    pub fn with_scan_mode(mut self, preferred_scan_mode: ScanMode) -> Self {
+        self.preferred_scan_mode = Some(preferred_scan_mode);
+        self
+    }
+
+    /// Abandon the scan once `deadline` has passed
+    ///
+    /// The deadline is checked before the scan and after every
+    /// [`Scanner::DEADLINE_CHECK_INTERVAL`] bytes, so a scan can overrun it by the time
+    /// it takes to scan that many bytes.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The preferred scan mode, if any
+    pub fn scan_mode(&self) -> Option<ScanMode> {
+        self.preferred_scan_mode
+    }
+
+    /// The deadline, if any
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn is_past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Outcome of a scan with a deadline
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScanOutcome<T> {
+    /// The scan finished before the deadline
+    Completed(T),
+    /// The deadline passed before the scan finished
+    TimedOut {
+        /// Matches found before the scan was abandoned
+        matches_so_far: T,
+        /// Number of leading bytes every match starting within has been found for
+        bytes_scanned: usize,
+    },
+}
+
+impl<T> ScanOutcome<T> {
+    /// Check if the scan finished before the deadline
+    pub fn is_completed(&self) -> bool {
+        matches!(self, ScanOutcome::Completed(_))
+    }
+
+    /// Matches found by the scan, whether it finished or not
+    pub fn into_matches(self) -> T {
+        match self {
+            ScanOutcome::Completed(matches) => matches,
+            ScanOutcome::TimedOut { matches_so_far, .. } => matches_so_far,
+        }
+    }
+}
+
+impl Scanner {
+    /// Number of bytes scanned between two checks of the deadline of [`ScanOptions`]
+    pub const DEADLINE_CHECK_INTERVAL: usize = 256 * 1024;
+
+    /// Find the offset of the first occurrence of the pattern in `binary`, unless the deadline passes
+    ///
+    /// A scan abandoned because of the deadline is reported as [`ScanOutcome::TimedOut`],
+    /// with no matches so far. The offset is that of the match start, regardless of the
+    /// pattern's [cursor](crate::pattern::Pattern::cursor).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::deadline::{ScanOptions, ScanOutcome};
+    /// use lightningscanner::Scanner;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    /// let options = ScanOptions::new().with_deadline(Instant::now() + Duration::from_millis(50));
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// match scanner.find_with_options(&binary, &options) {
+    ///     ScanOutcome::Completed(offset) => assert_eq!(offset, Some(2)),
+    ///     ScanOutcome::TimedOut { .. } => println!("fall back"),
+    /// }
+    /// ```
+    pub fn find_with_options(
+        &self,
+        binary: &[u8],
+        options: &ScanOptions,
+    ) -> ScanOutcome<Option<usize>> {
+        let timed_out = |bytes_scanned| ScanOutcome::TimedOut {
+            matches_so_far: None,
+            bytes_scanned,
+        };
+
+        if options.is_past_deadline() {
+            return timed_out(0);
+        }
+
+        let mut bytes_scanned = None;
+        let offset = self.find_in_steps(
+            options.preferred_scan_mode,
+            binary,
+            Self::DEADLINE_CHECK_INTERVAL,
+            |scanned| {
+                if scanned < binary.len() && options.is_past_deadline() {
+                    bytes_scanned = Some(scanned);
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+
+        match bytes_scanned {
+            Some(bytes_scanned) => timed_out(bytes_scanned),
+            None => ScanOutcome::Completed(offset),
+        }
+    }
+
+    /// Find the offsets of all occurrences of the pattern in `binary`, unless the deadline passes
+    ///
+    /// A completed scan reports the same offsets as [`Scanner::find_all_offsets`].
+    /// A scan abandoned because of the deadline reports the matches starting within the
+    /// bytes scanned so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::deadline::{ScanOptions, ScanOutcome};
+    /// use lightningscanner::Scanner;
+    /// use std::time::Instant;
+    ///
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    /// let options = ScanOptions::new().with_deadline(Instant::now());
+    ///
+    /// let scanner = Scanner::new("48 89 5c");
+    /// assert_eq!(
+    ///     scanner.find_all_with_options(&binary, &options),
+    ///     ScanOutcome::TimedOut {
+    ///         matches_so_far: vec![],
+    ///         bytes_scanned: 0
+    ///     }
+    /// );
+    /// ```
+    pub fn find_all_with_options(
+        &self,
+        binary: &[u8],
+        options: &ScanOptions,
+    ) -> ScanOutcome<Vec<usize>> {
+        let mut offsets = Vec::new();
+
+        let mut start = 0;
+        loop {
+            if options.is_past_deadline() {
+                return ScanOutcome::TimedOut {
+                    matches_so_far: offsets,
+                    bytes_scanned: start,
+                };
+            }
+
+            let scanned = start
+                .saturating_add(Self::DEADLINE_CHECK_INTERVAL)
+                .min(binary.len());
+            // empty patterns also match at the very end, which belongs to the last step
+            let end = if scanned == binary.len() {
+                scanned + 1
+            } else {
+                scanned
+            };
+
+            offsets.extend(self.find_all_starting_in(
+                options.preferred_scan_mode,
+                binary,
+                start,
+                end,
+            ));
+
+            if scanned == binary.len() {
+                return ScanOutcome::Completed(offsets);
+            }
+
+            start = scanned;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! File scanning
+
+use crate::{ScanMode, Scanner};
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+
+/// Section of an executable image
+///
+/// Used to translate file offsets into relative virtual addresses (RVAs).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Section {
+    /// Offset of the section's raw data in the file
+    pub file_offset: usize,
+    /// Size of the section's raw data in the file
+    pub file_size: usize,
+    /// Address of the section relative to the image base once loaded
+    pub rva: usize,
+}
+
+impl Section {
+    /// Translate a file offset into an RVA if it lies within this section
+    pub fn rva_of(&self, file_offset: usize) -> Option<usize> {
+        let offset = file_offset.checked_sub(self.file_offset)?;
+        (offset < self.file_size).then(|| self.rva + offset)
+    }
+}
+
+impl Scanner {
+    /// Find the file offsets of all occurrences of the pattern in a file
+    ///
+    /// The file is memory-mapped where supported, so it is not read into memory as a whole.
+    /// Files smaller than the pattern produce no matches.
+    ///
+    /// The file must not be modified while it is being scanned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lightningscanner::Scanner;
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    /// let offsets = scanner.find_all_in_file("game.exe", None).unwrap();
+    ///
+    /// println!("{:x?}", offsets);
+    /// ```
+    pub fn find_all_in_file(
+        &self,
+        path: impl AsRef<Path>,
+        preferred_scan_mode: Option<ScanMode>,
+    ) -> io::Result<Vec<usize>> {
+        let file = File::open(path)?;
+        let contents = Mapping::new(&file)?;
+
+        Ok(self.find_all_offsets(preferred_scan_mode, &contents))
+    }
+
+    /// Find the RVAs of all occurrences of the pattern in an executable file
+    ///
+    /// Matches are translated using the section which contains the first byte of the match,
+    /// matches outside of every section are skipped.
+    ///
+    /// See [`Scanner::find_all_in_file`].
+    pub fn find_all_rvas_in_file(
+        &self,
+        path: impl AsRef<Path>,
+        preferred_scan_mode: Option<ScanMode>,
+        sections: &[Section],
+    ) -> io::Result<Vec<usize>> {
+        let offsets = self.find_all_in_file(path, preferred_scan_mode)?;
+
+        Ok(offsets
+            .into_iter()
+            .filter_map(|offset| sections.iter().find_map(|section| section.rva_of(offset)))
+            .collect())
+    }
+}
+
+/// Read-only memory mapping of a whole file
+#[cfg(unix)]
+pub(crate) struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+#[cfg(unix)]
+impl Mapping {
+    pub(crate) fn new(file: &File) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file is too large"))?;
+
+        if len == 0 {
+            // empty mappings are rejected by mmap
+            return Ok(Mapping {
+                ptr: std::ptr::null_mut(),
+                len,
+            });
+        }
+
+        // SAFETY: a fresh private read-only mapping is requested, the file descriptor is valid
+        // for the duration of the call
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Mapping { ptr, len })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        if self.len == 0 {
+            &[]
+        } else {
+            // SAFETY: `ptr` points to a readable mapping of `len` bytes which lives as long as `self`
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            // SAFETY: `ptr` and `len` describe a mapping created by `mmap` which is not used anymore
+            unsafe {
+                libc::munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
+
+/// Contents of a whole file, read into memory on platforms without mapping support
+#[cfg(not(unix))]
+pub(crate) struct Mapping(Vec<u8>);
+
+#[cfg(not(unix))]
+impl Mapping {
+    pub(crate) fn new(mut file: &File) -> io::Result<Self> {
+        use std::io::Read;
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+
+        Ok(Mapping(contents))
+    }
+}
+
+#[cfg(not(unix))]
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
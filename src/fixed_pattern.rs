@@ -0,0 +1,96 @@
+//! Patterns stored inline, without allocating
+//!
+//! A [`FixedPattern`] keeps its bytes in arrays of a fixed capacity, so it can be built in
+//! `const` items and scanned for without touching the heap. Scans use the scalar algorithm
+//! of [`const_find`], a [`Pattern`] converted from it is needed for the SIMD scanners.
+
+use crate::const_parser;
+use crate::const_scan::const_find;
+use crate::pattern::Pattern;
+
+/// Byte array aligned like the buffers of a [`Pattern`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C, align(64))]
+struct AlignedArray<const N: usize>([u8; N]);
+
+/// An IDA-style binary pattern of at most `N` bytes, stored without allocating
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::fixed_pattern::FixedPattern;
+///
+/// const PATTERN: FixedPattern<16> = FixedPattern::new("48 8b ?? c3");
+///
+/// assert_eq!(PATTERN.find(&[0xcc, 0x48, 0x8b, 0x05, 0xc3]), Some(1));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FixedPattern<const N: usize> {
+    data: AlignedArray<N>,
+    mask: AlignedArray<N>,
+    len: usize,
+    cursor: usize,
+}
+
+impl<const N: usize> FixedPattern<N> {
+    /// Create a new [`FixedPattern`] from an IDA-style pattern string
+    ///
+    /// The syntax is the same as for [`Pattern::new`], except for label markers,
+    /// which aren't supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is malformed, or longer than `N` bytes.
+    /// In a `const` item, this is a compile error instead.
+    pub const fn new(pattern: &str) -> Self {
+        let parsed = const_parser::parse_pattern::<N>(pattern);
+
+        if parsed.len > N {
+            panic!("Pattern is longer than the capacity of the fixed pattern");
+        }
+
+        FixedPattern {
+            data: AlignedArray(parsed.data),
+            mask: AlignedArray(parsed.mask),
+            len: parsed.len,
+            cursor: parsed.cursor,
+        }
+    }
+
+    /// Length of the pattern in bytes
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the pattern has no bytes
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Offset of the byte marked with `^` from the start of the pattern, see [`Pattern::cursor`]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Find the offset of the first occurrence of the pattern in `haystack`
+    ///
+    /// This doesn't allocate and can be used in `const` contexts. The offset is that of
+    /// the match start, regardless of the cursor.
+    pub const fn find(&self, haystack: &[u8]) -> Option<usize> {
+        const_find(
+            haystack,
+            self.data.0.split_at(self.len).0,
+            self.mask.0.split_at(self.len).0,
+        )
+    }
+}
+
+impl<const N: usize> From<&FixedPattern<N>> for Pattern {
+    fn from(value: &FixedPattern<N>) -> Self {
+        Pattern::from_unpadded(
+            value.data.0[..value.len].to_vec(),
+            value.mask.0[..value.len].to_vec(),
+        )
+        .with_cursor(value.cursor)
+    }
+}
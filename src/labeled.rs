@@ -0,0 +1,65 @@
+//! Matches with named offsets
+
+use crate::Scanner;
+
+/// Match of a pattern with `@name` label markers, see [`Scanner::find_labeled`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LabeledMatch<'a> {
+    offset: usize,
+    labels: &'a [(String, usize)],
+}
+
+impl<'a> LabeledMatch<'a> {
+    /// Offset of the match from the beginning of the haystack
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Offset of the byte labeled `name` from the beginning of the haystack
+    ///
+    /// Returns `None` if the pattern has no such label.
+    pub fn label(&self, name: &str) -> Option<usize> {
+        self.labels
+            .iter()
+            .find(|(label, _)| label == name)
+            .map(|(_, offset)| self.offset + offset)
+    }
+
+    /// Every label of the pattern with its offset from the beginning of the haystack
+    pub fn labels(&self) -> impl Iterator<Item = (&'a str, usize)> + '_ {
+        self.labels
+            .iter()
+            .map(|(label, offset)| (label.as_str(), self.offset + offset))
+    }
+}
+
+impl Scanner {
+    /// Find the first occurrence of the pattern in `haystack` and resolve its labels
+    ///
+    /// Every `@name` marker of the pattern can be looked up in the result,
+    /// which avoids keeping the offsets of the interesting bytes as separate constants.
+    /// A label at the very end of the pattern points right past the match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0xc3];
+    ///
+    /// let scanner = Scanner::new("48 8B 05 @disp ?? ?? ?? ?? @next C3");
+    /// let result = scanner.find_labeled(&haystack).unwrap();
+    ///
+    /// assert_eq!(result.offset(), 1);
+    /// assert_eq!(result.label("disp"), Some(4));
+    /// assert_eq!(result.label("next"), Some(8));
+    /// assert_eq!(result.label("call"), None);
+    /// ```
+    pub fn find_labeled(&self, haystack: &[u8]) -> Option<LabeledMatch<'_>> {
+        self.find_in_slice(None, haystack, 0)
+            .map(|offset| LabeledMatch {
+                offset,
+                labels: &self.pattern.labels,
+            })
+    }
+}
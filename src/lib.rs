@@ -14,22 +14,184 @@
 //!
 //! println!("{:?}", result);
 //! ```
+#![cfg_attr(
+    all(feature = "portable-simd", not(feature = "no-simd")),
+    feature(portable_simd)
+)]
 #![deny(unsafe_op_in_unsafe_fn, clippy::undocumented_unsafe_blocks)]
 
 use crate::pattern::Pattern;
+use memchr::memmem;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::{fmt, io, mem, ptr, slice};
 
+pub mod address;
 pub mod aligned_bytes;
 mod backends;
+mod benchmark;
+#[cfg(feature = "byte-filter")]
+mod byte_filter;
+pub mod cache;
+mod chunks;
+#[cfg(feature = "patch")]
+mod code_patch;
+#[doc(hidden)]
+pub mod const_parser;
+pub mod const_scan;
+pub mod constrained;
+pub mod context;
+pub mod deadline;
+pub mod file;
+pub mod fixed_pattern;
+pub mod labeled;
+mod masked;
+pub mod match_list;
+#[cfg(feature = "minidump")]
+pub mod minidump;
+pub mod multi;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+mod partial;
+pub mod patch;
 pub mod pattern;
+pub mod pattern_set;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod process;
+mod progress;
+pub mod proximity;
+pub mod range_pattern;
+pub mod selectivity;
+mod self_check;
+pub mod serialize;
+pub mod sigfile;
 
 #[macro_use]
 mod macros;
 
+pub use benchmark::ScanBench;
+pub use masked::{masked_eq, masked_eq_at};
+pub use self_check::{self_check, BackendMismatch};
+
+/// Number of times the CPU features were detected, at most once per process
+///
+/// The features are detected on first use and cached for every later scan.
+#[cfg(feature = "test-hooks")]
+#[doc(hidden)]
+pub fn feature_detections() -> usize {
+    backends::feature_detections()
+}
+
+/// Allow or forbid verifying long sparse patterns with a gather in the AVX2 backend
+///
+/// Gathered verification is enabled by default where it pays off, this switch only exists
+/// to benchmark it against verifying the patterns unit by unit.
+#[cfg(feature = "test-hooks")]
+#[doc(hidden)]
+pub fn set_gather_verification(enabled: bool) {
+    backends::set_gather_verification(enabled);
+}
+
+/// Advance the vector backends by `step` bytes after every candidate offset, or by one if `None`
+///
+/// **Not for production use.** The offsets stepped over are never checked, so matches are
+/// missed. This only exists for research and benchmarking, to find out whether a scan is
+/// bound by memory or by compute on a given haystack.
+#[cfg(feature = "test-hooks")]
+#[doc(hidden)]
+pub fn set_step_override(step: Option<usize>) {
+    backends::set_step_override(step);
+}
+
 /// Single result IDA-style pattern scanner
 ///
 /// A pattern scanner that searches for an IDA-style pattern
 /// and returns the pointer to the first occurrence in the binary.
-pub struct Scanner(Pattern);
+///
+/// Patterns without any wildcards, and one or two byte patterns with a single concrete byte,
+/// are searched for with dedicated substring and byte searches when no scan mode is preferred.
+///
+/// A scanner is immutable after construction and is both [`Send`] and [`Sync`],
+/// so a single instance can be shared by any number of threads scanning in parallel.
+///
+/// No scan mode reads memory outside of the scanned binary. The SIMD backends leave
+/// the positions at which a full vector load would reach past its end to the scalar backend,
+/// so a region directly followed by unmapped memory can be scanned safely.
+///
+/// The padding of the pattern buffers is made of wildcard bytes and never takes part in
+/// a match, so results only depend on the pattern's bytes and a match may end at the very
+/// last byte of the binary.
+pub struct Scanner {
+    pattern: Pattern,
+    fast_path: Option<FastPath>,
+}
+
+/// Specialized search used instead of the backends when no scan mode is preferred
+enum FastPath {
+    /// Substring search for patterns without wildcards
+    Substring(Box<memmem::Finder<'static>>),
+    /// Byte search for one and two byte patterns with a single concrete byte
+    Byte {
+        /// The concrete byte
+        byte: u8,
+        /// Offset of the concrete byte in the pattern
+        offset: usize,
+        /// Size of the pattern
+        size: usize,
+    },
+}
+
+impl FastPath {
+    fn new(pattern: &Pattern) -> Option<Self> {
+        let data = &pattern.data[..pattern.unpadded_size];
+        let mask = &pattern.mask[..pattern.unpadded_size];
+
+        if mask.iter().all(|&mask| mask == 0xff) {
+            return Some(FastPath::Substring(Box::new(
+                memmem::Finder::new(data).into_owned(),
+            )));
+        }
+
+        match mask {
+            [0xff, 0x00] | [0x00, 0xff] => {
+                let offset = mask.iter().position(|&mask| mask == 0xff)?;
+
+                Some(FastPath::Byte {
+                    byte: data[offset],
+                    offset,
+                    size: mask.len(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Find the offset of the first match in `binary`
+    fn find(&self, binary: &[u8]) -> Option<usize> {
+        match *self {
+            FastPath::Substring(ref finder) => finder.find(binary),
+            FastPath::Byte { byte, offset, size } => {
+                // the concrete byte can only be at positions which leave room for the wildcard
+                let end = binary.len().checked_sub(size - offset - 1)?;
+                memchr::memchr(byte, binary.get(offset..end)?)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Scanner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fast_path = self.fast_path.as_ref().map(|fast_path| match fast_path {
+            FastPath::Substring(_) => "substring",
+            FastPath::Byte { .. } => "byte",
+        });
+
+        f.debug_struct("Scanner")
+            .field("pattern", &self.pattern)
+            .field("fast_path", &fast_path)
+            .finish()
+    }
+}
 
 impl Scanner {
     /// Create a new [`Scanner`] instance
@@ -39,26 +201,905 @@ impl Scanner {
     /// ```
     /// use lightningscanner::Scanner;
     ///
-    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    /// ```
+    pub fn new(pattern: &str) -> Self {
+        Scanner::from(Pattern::new(pattern))
+    }
+
+    /// Create a new [`Scanner`] instance, using a string literal pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let scanner = Scanner::new_from_str("LocalPlayer");
+    /// ```
+    pub fn new_from_str(pattern: &str) -> Self {
+        Scanner::from(Pattern::new_string(pattern))
+    }
+
+    /// Find the first occurrence of the pattern in the binary
+    ///
+    /// The result points at the match start, or at the byte marked by the pattern's
+    /// [cursor](Pattern::cursor) if it has one.
+    /// A null `binary_ptr`, or a binary wrapping around the end of the address space, is
+    /// reported as no match, see [`Scanner::find_checked`] for a version that reports invalid
+    /// arguments as errors.
+    ///
+    /// # Params
+    ///
+    /// * `preferred_scan_mode` - preferred scan mode to use (Avx2, Sse42, Scalar)
+    ///   if the preferred mode is not available, will choose the fastest out of the availble ones
+    ///
+    /// * `binary_ptr` - pointer to the first element of the binary to search the pattern in
+    ///
+    /// * `binary_size` - binary size
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x6c];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    /// let result = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    ///
+    /// println!("{:?}", result);
+    /// ```
+    pub unsafe fn find(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+    ) -> ScanResult {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        }
+
+        let result = if let (None, Some(fast_path)) =
+            (ScanMode::preference(preferred_scan_mode), &self.fast_path)
+        {
+            // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+            let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+            ScanResult {
+                addr: fast_path
+                    .find(binary)
+                    .map_or(ptr::null(), |offset| binary[offset..].as_ptr()),
+                writable: false,
+            }
+        } else {
+            // SAFETY: safe to call as long as the safety conditions were met for this function
+            unsafe { backends::find(&self.pattern, preferred_scan_mode, binary_ptr, binary_size) }
+        };
+
+        if !result.is_valid() {
+            return result;
+        }
+
+        ScanResult {
+            // SAFETY: the cursor is within the pattern, so it's at most one past the end of the match
+            addr: unsafe { result.addr.add(self.pattern.cursor) },
+            writable: false,
+        }
+    }
+
+    /// Find the first occurrence of the pattern in the binary, validating the pointer and size first
+    ///
+    /// Unlike [`Scanner::find`], a null pointer, a binary which is empty or smaller than
+    /// the pattern, and a range wrapping around the address space are reported as errors
+    /// before any memory is read. Not finding the pattern is not an error.
+    ///
+    /// # Params
+    ///
+    /// * `preferred_scan_mode` - preferred scan mode to use (Avx2, Sse42, Scalar)
+    ///   if the preferred mode is not available, will choose the fastest out of the availble ones
+    ///
+    /// * `binary_ptr` - pointer to the first element of the binary to search the pattern in
+    ///
+    /// * `binary_size` - binary size
+    ///
+    /// # Safety
+    ///
+    /// The checks can't tell if memory is actually readable, so the same conditions
+    /// as for [`Scanner::find`] apply:
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::{ScanError, Scanner};
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe { scanner.find_checked(None, std::ptr::null(), 16) };
+    ///
+    /// assert!(matches!(result, Err(ScanError::NullPointer)));
+    /// ```
+    pub unsafe fn find_checked(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+    ) -> Result<ScanResult, ScanError> {
+        if binary_ptr.is_null() {
+            return Err(ScanError::NullPointer);
+        }
+
+        let pattern_size = self.pattern.unpadded_size;
+        if binary_size == 0 || binary_size < pattern_size {
+            return Err(ScanError::BinaryTooSmall {
+                binary_size,
+                pattern_size,
+            });
+        }
+
+        if !is_valid_range(binary_ptr, binary_size) {
+            return Err(ScanError::AddressOverflow);
+        }
+
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        Ok(unsafe { self.find(preferred_scan_mode, binary_ptr, binary_size) })
+    }
+
+    /// Find the first occurrence of the pattern in the binary using exactly the given scan mode
+    ///
+    /// Unlike [`Scanner::find`], a mode the running CPU doesn't support is reported as an
+    /// error instead of falling back to another one. [`ScanMode::Auto`] is always accepted,
+    /// and keeps picking the best available implementation.
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::{ScanMode, Scanner};
+    ///
+    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// match unsafe { scanner.try_find(ScanMode::Avx2, binary.as_ptr(), binary.len()) } {
+    ///     Ok(result) => assert_eq!(result.get_addr(), binary[2..].as_ptr()),
+    ///     Err(err) => println!("{}", err),
+    /// }
+    /// ```
+    pub unsafe fn try_find(
+        &self,
+        scan_mode: ScanMode,
+        binary_ptr: *const u8,
+        binary_size: usize,
+    ) -> Result<ScanResult, UnsupportedMode> {
+        if !scan_mode.is_supported() {
+            return Err(UnsupportedMode { mode: scan_mode });
+        }
+
+        // SAFETY: safe to call as long as the safety conditions were met for this function
+        Ok(unsafe { self.find(Some(scan_mode), binary_ptr, binary_size) })
+    }
+
+    /// Find all occurrences of the pattern in the binary
+    ///
+    /// Overlapping occurrences are reported separately, in strictly ascending address order,
+    /// so the order is the same on every run and no result appears twice. Like with
+    /// [`Scanner::find`], the results point at the pattern's [cursor](Pattern::cursor).
+    ///
+    /// # Params
+    ///
+    /// * `preferred_scan_mode` - preferred scan mode to use (Avx2, Sse42, Scalar)
+    ///   if the preferred mode is not available, will choose the fastest out of the availble ones
+    ///
+    /// * `binary_ptr` - pointer to the first element of the binary to search the pattern in
+    ///
+    /// * `binary_size` - binary size
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    ///
+    /// let scanner = Scanner::new("48 89 5c");
+    /// let results = unsafe { scanner.find_all(None, binary.as_ptr(), binary.len()) };
+    ///
+    /// assert_eq!(results.len(), 2);
+    /// ```
+    pub unsafe fn find_all(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+    ) -> Vec<ScanResult> {
+        // SAFETY: the caller upholds the safety contract of `find_all`
+        unsafe { self.find_all_limited(preferred_scan_mode, binary_ptr, binary_size, usize::MAX) }
+    }
+
+    /// Find the first `max` occurrences of the pattern in the binary
+    ///
+    /// Like [`Scanner::find_all`], but the scan stops once `max` results were collected,
+    /// which bounds the memory taken by the results when scanning untrusted or highly
+    /// repetitive data. Check if the length of the results is `max` to tell if the scan
+    /// was cut short.
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x90; 4096];
+    ///
+    /// let scanner = Scanner::new("90 90");
+    /// let results = unsafe { scanner.find_all_limited(None, binary.as_ptr(), binary.len(), 16) };
+    ///
+    /// assert_eq!(results.len(), 16);
+    /// assert_eq!(results[15].get_addr(), binary[15..].as_ptr());
+    /// ```
+    pub unsafe fn find_all_limited(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        max: usize,
+    ) -> Vec<ScanResult> {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return Vec::new();
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        self.find_offsets_limited(preferred_scan_mode, binary, max)
+            .into_iter()
+            .map(|offset| ScanResult {
+                // SAFETY: every match is within the binary, and the cursor is at most
+                // one past the end of the match
+                addr: unsafe { binary_ptr.add(offset + self.pattern.cursor) },
+                writable: false,
+            })
+            .collect()
+    }
+
+    /// Find all occurrences of the pattern in the binary, grouped by the page they're in
+    ///
+    /// Maps the base address of every page containing a result to the offsets of its results
+    /// from that base, in ascending order. Like with [`Scanner::find_all`], results point at
+    /// the pattern's [cursor](Pattern::cursor), which decides the page of a match extending
+    /// into the next one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` isn't a power of two.
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::{ScanResult, Scanner};
+    ///
+    /// let binary = vec![0xcc; 0x3000];
+    ///
+    /// let scanner = Scanner::new("cc");
+    /// let pages = unsafe {
+    ///     scanner.find_all_by_page(None, binary.as_ptr(), binary.len(), ScanResult::DEFAULT_PAGE_SIZE)
+    /// };
+    ///
+    /// assert!(pages.values().all(|offsets| offsets.len() <= 0x1000));
+    /// assert_eq!(pages.values().map(Vec::len).sum::<usize>(), 0x3000);
+    /// ```
+    pub unsafe fn find_all_by_page(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        page_size: usize,
+    ) -> BTreeMap<usize, Vec<usize>> {
+        assert!(
+            page_size.is_power_of_two(),
+            "page size of {} bytes is not a power of two",
+            page_size
+        );
+
+        if !is_valid_range(binary_ptr, binary_size) {
+            return BTreeMap::new();
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        let mut pages = BTreeMap::<usize, Vec<usize>>::new();
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) {
+            let addr = binary_ptr as usize + offset + self.pattern.cursor;
+            let base = addr & !(page_size - 1);
+
+            pages.entry(base).or_default().push(addr - base);
+            start = offset + 1;
+        }
+
+        pages
+    }
+
+    /// Find the offsets of all occurrences of the pattern in the binary
+    ///
+    /// Overlapping occurrences are reported separately, in strictly ascending order
+    /// without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    ///
+    /// let scanner = Scanner::new("48 89 5c");
+    /// assert_eq!(scanner.find_all_offsets(None, &binary), [0, 5]);
+    /// ```
+    pub fn find_all_offsets(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+    ) -> Vec<usize> {
+        self.find_offsets_limited(preferred_scan_mode, binary, usize::MAX)
+    }
+
+    /// Find the offsets of the first `max` occurrences of the pattern in the binary
+    fn find_offsets_limited(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        max: usize,
+    ) -> Vec<usize> {
+        let mut offsets = Vec::new();
+
+        let mut start = 0;
+        while offsets.len() < max {
+            let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) else {
+                break;
+            };
+
+            offsets.push(offset);
+            start = offset + 1;
+        }
+
+        offsets
+    }
+
+    /// Count the occurrences of the pattern in the binary, stopping once `cap` were found
+    ///
+    /// Returns `cap` if there are at least `cap` occurrences, without scanning the rest of
+    /// the binary. Overlapping occurrences are counted separately, as with
+    /// [`Scanner::find_all_offsets`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x90; 64];
+    ///
+    /// let scanner = Scanner::new("90 90");
+    /// assert_eq!(scanner.count_up_to(None, &binary, 5), 5);
+    /// assert_eq!(scanner.count_up_to(None, &binary, 100), 63);
+    /// ```
+    pub fn count_up_to(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        cap: usize,
+    ) -> usize {
+        let mut count = 0;
+
+        let mut start = 0;
+        while count < cap {
+            let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) else {
+                break;
+            };
+
+            count += 1;
+            start = offset + 1;
+        }
+
+        count
+    }
+
+    /// Find the first occurrence of the pattern, looking near a previously known offset first
+    ///
+    /// Matches starting within `window` bytes of `hint_offset` in either direction are
+    /// searched for first. If there are none, the whole haystack is scanned.
+    /// The returned offset is always relative to the beginning of `haystack`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    ///
+    /// let scanner = Scanner::new("48 89 5c");
+    /// assert_eq!(scanner.find_with_hint(&haystack, 6, 2), Some(5));
+    /// assert_eq!(scanner.find_with_hint(&haystack, 6, 0), Some(0));
+    /// ```
+    pub fn find_with_hint(
+        &self,
+        haystack: &[u8],
+        hint_offset: usize,
+        window: usize,
+    ) -> Option<usize> {
+        let start = hint_offset.saturating_sub(window).min(haystack.len());
+        let end = hint_offset
+            .saturating_add(window)
+            .saturating_add(self.pattern.unpadded_size)
+            .min(haystack.len());
+
+        self.find_in_range(None, haystack, start..end.max(start))
+            .or_else(|| self.find_in_slice(None, haystack, 0))
+    }
+
+    /// Find the offset of the occurrence of the pattern starting closest to `target`
+    ///
+    /// The haystack is scanned forward from `target` for the first match after it, then in
+    /// windows of growing size backward from it, but no further back than that match.
+    /// Matches at the same distance on both sides resolve to the lower offset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0xc3, 0xcc, 0xcc, 0xcc, 0xcc, 0xc3, 0xcc, 0xc3];
+    ///
+    /// let scanner = Scanner::new("c3");
+    /// assert_eq!(scanner.find_closest_to(&haystack, 3), Some(5));
+    /// assert_eq!(scanner.find_closest_to(&haystack, 1), Some(0));
+    /// // equally close to both
+    /// assert_eq!(scanner.find_closest_to(&haystack, 6), Some(5));
+    /// ```
+    pub fn find_closest_to(&self, haystack: &[u8], target: usize) -> Option<usize> {
+        const WINDOW: usize = 4096;
+
+        let size = self.pattern.unpadded_size;
+        let target = target.min(haystack.len());
+
+        let after = self.find_in_slice(None, haystack, target);
+        // a match before `target` is closer, or as close, if it starts at `lowest` or later
+        let lowest = after.map_or(0, |after| target - (after - target).min(target));
+
+        let mut window = WINDOW;
+        let mut high = target;
+        while high > lowest {
+            let low = high.saturating_sub(window).max(lowest);
+            // matches starting before `high` may extend up to `size - 1` bytes past it
+            let end = high
+                .saturating_add(size.saturating_sub(1))
+                .min(haystack.len());
+
+            let mut before = None;
+            let mut start = low;
+            while let Some(offset) = self.find_in_range(None, haystack, start..end) {
+                before = Some(offset);
+                start = offset + 1;
+            }
+            if before.is_some() {
+                return before;
+            }
+
+            high = low;
+            window = window.saturating_mul(2);
+        }
+
+        after
+    }
+
+    /// Find the first occurrence of the pattern in a ring buffer whose oldest byte is at `head`
+    ///
+    /// The logical contents of the buffer are `ring[head..]` followed by `ring[..head]`, so
+    /// matches may wrap around from its end to its start. The returned offset is relative
+    /// to `head` in that logical order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `head` is larger than the size of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// // oldest byte at 6, the match wraps around the end
+    /// let ring = [0x5c, 0x24, 0x00, 0x00, 0x00, 0x00, 0xee, 0x48, 0x89];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// assert_eq!(scanner.find_ring(&ring, 6), Some(1));
+    /// ```
+    pub fn find_ring(&self, ring: &[u8], head: usize) -> Option<usize> {
+        assert!(
+            head <= ring.len(),
+            "head {} is outside of a ring buffer of {} bytes",
+            head,
+            ring.len()
+        );
+
+        let (older, newer) = (&ring[head..], &ring[..head]);
+        if let Some(offset) = self.find_in_slice(None, older, 0) {
+            return Some(offset);
+        }
+
+        // matches wrapping around start within the last `size - 1` bytes of the older part
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+        let seam_start = older.len().saturating_sub(overlap);
+        let seam = [&older[seam_start..], &newer[..overlap.min(newer.len())]].concat();
+        if let Some(offset) = self.find_in_slice(None, &seam, 0) {
+            return Some(seam_start + offset);
+        }
+
+        self.find_in_slice(None, newer, 0)
+            .map(|offset| older.len() + offset)
+    }
+
+    /// Find the first offset at which the pattern occurs in both `a` and `b`
+    ///
+    /// `a` is scanned, each of its matches is checked against `b` at the same offset.
+    /// This tells whether a signature is stable across two builds of a binary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let a = [0x48, 0x8b, 0x05, 0x10, 0x90, 0x48, 0x8b, 0x05, 0x20];
+    /// let b = [0x48, 0x8b, 0x0d, 0x10, 0x90, 0x48, 0x8b, 0x05, 0x30];
+    ///
+    /// let scanner = Scanner::new("48 8b 05 ??");
+    /// assert_eq!(scanner.find_common(&a, &b), Some(5));
+    /// ```
+    pub fn find_common(&self, a: &[u8], b: &[u8]) -> Option<usize> {
+        // matches extending past the end of the shorter buffer can't be common
+        let common = &a[..a.len().min(b.len())];
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(None, common, start) {
+            if backends::matches_at(&self.pattern, b, offset) {
+                return Some(offset);
+            }
+
+            start = offset + 1;
+        }
+
+        None
+    }
+
+    /// Find the first occurrence of the pattern whose matched bytes hash to `expected`
+    ///
+    /// `hash` is given the bytes of every candidate match, wildcards included, which guards
+    /// against a loose pattern coincidentally matching somewhere else.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0xe8, 0x10, 0x00, 0x00, 0x00, 0xe8, 0x20, 0x00, 0x00, 0x00];
+    /// let sum = |bytes: &[u8]| bytes.iter().map(|&byte| u64::from(byte)).sum::<u64>();
+    ///
+    /// let scanner = Scanner::new("e8 ?? ?? ?? ??");
+    /// assert_eq!(scanner.find_with_checksum(&haystack, 0x108, sum), Some(5));
+    /// assert_eq!(scanner.find_with_checksum(&haystack, 0x100, sum), None);
+    /// ```
+    pub fn find_with_checksum(
+        &self,
+        haystack: &[u8],
+        expected: u64,
+        hash: impl Fn(&[u8]) -> u64,
+    ) -> Option<usize> {
+        let size = self.pattern.unpadded_size;
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(None, haystack, start) {
+            if hash(&haystack[offset..offset + size]) == expected {
+                return Some(offset);
+            }
+
+            start = offset + 1;
+        }
+
+        None
+    }
+
+    /// Find the first occurrence of the pattern in two passes, collecting candidates first
+    ///
+    /// The haystack is processed in blocks. The first pass over a block records every
+    /// position of the pattern's rarest concrete byte, see [`Pattern::selectivity`], the
+    /// second pass verifies the whole pattern at each of them. Patterns without a concrete
+    /// byte are scanned in a single pass. The result is the same as that of a regular scan.
+    ///
+    /// Prefer this for large haystacks in which the rarest byte of the pattern is rare as well.
+    /// The `two_pass` benchmark shows it about four times ahead of [`Scanner::find`] with one
+    /// anchor byte every 256 bytes and over ten times ahead with one every 4 KiB, while it falls
+    /// behind once the anchor byte occurs every few bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let haystack = [0x48, 0x8b, 0x05, 0x00, 0x48, 0x8b, 0x05, 0xc3];
+    ///
+    /// let scanner = Scanner::new("48 8b ?? c3");
+    /// assert_eq!(scanner.find_two_pass(&haystack), Some(4));
+    /// ```
+    pub fn find_two_pass(&self, haystack: &[u8]) -> Option<usize> {
+        const BLOCK_SIZE: usize = 64 * 1024;
+
+        let Some(anchor) = self.pattern.selectivity().anchor else {
+            return self.find_in_slice(None, haystack, 0);
+        };
+
+        let mut candidates = Vec::new();
+        for block_start in (0..haystack.len()).step_by(BLOCK_SIZE) {
+            let block = &haystack[block_start..(block_start + BLOCK_SIZE).min(haystack.len())];
+
+            candidates.clear();
+            candidates.extend(memchr::memchr_iter(anchor.byte, block));
+
+            let found = candidates.iter().find_map(|&position| {
+                let start = (block_start + position).checked_sub(anchor.offset)?;
+                backends::matches_at(&self.pattern, haystack, start).then_some(start)
+            });
+            if found.is_some() {
+                return found;
+            }
+        }
+
+        None
+    }
+
+    /// Find the index of the first element of `slice` at which the pattern occurs
+    ///
+    /// The bytes of the slice are scanned as one buffer, and only matches which start at
+    /// the first byte of an element are reported, as that element's index. Matches starting
+    /// within an element are skipped. A match may extend over any number of elements.
+    /// Slices of zero-sized elements never match.
+    ///
+    /// # Safety
+    ///
+    /// * `T` - has no padding or other uninitialized bytes, as every byte of the slice is read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let records: [[u16; 2]; 3] = [[0x0001, 0x0203], [0x0a0b, 0x0c0d], [0x1111, 0x2222]];
+    ///
+    /// // little-endian bytes of the second record's first field
+    /// let scanner = Scanner::new("0b 0a");
+    /// // SAFETY: arrays of integers have no padding
+    /// assert_eq!(unsafe { scanner.find_element(&records) }, Some(1));
+    ///
+    /// // the bytes of the second field never start a record
+    /// let scanner = Scanner::new("0d 0c");
+    /// assert_eq!(unsafe { scanner.find_element(&records) }, None);
     /// ```
-    pub fn new(pattern: &str) -> Self {
-        Scanner(Pattern::new(pattern))
+    pub unsafe fn find_element<T>(&self, slice: &[T]) -> Option<usize> {
+        let element_size = mem::size_of::<T>();
+        if element_size == 0 {
+            return None;
+        }
+
+        // SAFETY: the slice is valid for reads of its whole size,
+        // and the caller guarantees every byte of it is initialized
+        let bytes =
+            unsafe { slice::from_raw_parts(slice.as_ptr().cast::<u8>(), mem::size_of_val(slice)) };
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(None, bytes, start) {
+            if offset % element_size == 0 {
+                return Some(offset / element_size);
+            }
+
+            // the next match can only be reported if it starts at the next element
+            start = offset.next_multiple_of(element_size);
+        }
+
+        None
     }
 
-    /// Create a new [`Scanner`] instance, using a string literal pattern.
+    /// Find the first occurrence of the pattern that looks like a function start
+    ///
+    /// A match is only reported if it is directly preceded by a run of padding bytes
+    /// described by `filter`, which filters out coincidental matches in the middle of a function.
+    /// A match closer to the beginning of the binary than `filter.min_padding` is accepted
+    /// if every byte before it is padding.
+    ///
+    /// # Params
+    ///
+    /// * `preferred_scan_mode` - preferred scan mode to use (Avx2, Sse42, Scalar)
+    ///   if the preferred mode is not available, will choose the fastest out of the availble ones
+    ///
+    /// * `binary_ptr` - pointer to the first element of the binary to search the pattern in
+    ///
+    /// * `binary_size` - binary size
+    ///
+    /// * `filter` - padding that has to precede the match
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::{PrologueFilter, Scanner};
+    ///
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0xcc, 0xcc, 0x48, 0x89, 0x5c, 0x24];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe {
+    ///     scanner.find_prologue(None, binary[1..].as_ptr(), binary.len() - 1, PrologueFilter::default())
+    /// };
+    ///
+    /// assert_eq!(result.get_addr(), binary[6..].as_ptr());
+    /// ```
+    pub unsafe fn find_prologue(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        filter: PrologueFilter,
+    ) -> ScanResult {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) {
+            let padding_start = offset.saturating_sub(filter.min_padding);
+
+            if binary[padding_start..offset]
+                .iter()
+                .all(|&byte| filter.is_padding(byte))
+            {
+                return ScanResult {
+                    addr: binary[offset + self.pattern.cursor..].as_ptr(),
+                    writable: false,
+                };
+            }
+
+            start = offset + 1;
+        }
+
+        ScanResult {
+            addr: ptr::null(),
+            writable: false,
+        }
+    }
+}
+
+impl Scanner {
+    /// Find the first occurrence of the pattern at which `negative` does not match
+    ///
+    /// Both patterns are anchored at the same position, so this picks between code variants
+    /// that share a prefix. A `negative` pattern that would extend past the end of the binary
+    /// is treated as not matching.
+    ///
+    /// # Params
+    ///
+    /// * `negative` - pattern that must not match at the same position
+    ///
+    /// * `preferred_scan_mode` - preferred scan mode to use (Avx2, Sse42, Scalar)
+    ///   if the preferred mode is not available, will choose the fastest out of the availble ones
+    ///
+    /// * `binary_ptr` - pointer to the first element of the binary to search the pattern in
+    ///
+    /// * `binary_size` - binary size
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
     ///
     /// # Example
     ///
     /// ```
+    /// use lightningscanner::pattern::Pattern;
     /// use lightningscanner::Scanner;
     ///
-    /// let scanner = Scanner::new_from_str("LocalPlayer");
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0x08, 0x48, 0x89, 0x5c, 0x24, 0x10];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let negative = Pattern::new("?? ?? ?? ?? 08");
+    /// let result = unsafe { scanner.find_excluding(&negative, None, binary.as_ptr(), binary.len()) };
+    ///
+    /// assert_eq!(result.get_addr(), binary[5..].as_ptr());
     /// ```
-    pub fn new_from_str(pattern: &str) -> Self {
-        Scanner(Pattern::new_string(pattern))
+    pub unsafe fn find_excluding(
+        &self,
+        negative: &Pattern,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+    ) -> ScanResult {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) {
+            if !backends::matches_at(negative, binary, offset) {
+                return ScanResult {
+                    addr: binary[offset + self.pattern.cursor..].as_ptr(),
+                    writable: false,
+                };
+            }
+
+            start = offset + 1;
+        }
+
+        ScanResult {
+            addr: ptr::null(),
+            writable: false,
+        }
     }
+}
 
-    /// Find the first occurence of the pattern in the binary
+impl Scanner {
+    /// Find the first occurrence of the pattern which starts at an instruction boundary
+    ///
+    /// The beginning of the binary is taken as a known instruction boundary. Instructions are
+    /// decoded forward from it with `decoder`, which returns the length of the instruction
+    /// at the given address, and matches that start in the middle of an instruction are skipped.
+    /// A decoded length of zero is treated as a single byte.
     ///
     /// # Params
     ///
@@ -69,6 +1110,8 @@ impl Scanner {
     ///
     /// * `binary_size` - binary size
     ///
+    /// * `decoder` - instruction length decoder, e.g. built on a disassembler crate
+    ///
     /// # Safety
     ///
     /// * `binary_ptr` - is a valid pointer
@@ -80,45 +1123,380 @@ impl Scanner {
     /// ```
     /// use lightningscanner::Scanner;
     ///
-    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x6c];
+    /// // mov eax, 0xc3c3c3c3; ret
+    /// let binary = [0xb8, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3];
+    /// let decoder = |addr: *const u8| match unsafe { *addr } {
+    ///     0xb8 => 5,
+    ///     _ => 1,
+    /// };
     ///
-    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
-    /// let result = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    /// let scanner = Scanner::new("c3");
+    /// let result = unsafe {
+    ///     scanner.find_aligned_to_instruction(None, binary.as_ptr(), binary.len(), decoder)
+    /// };
     ///
-    /// println!("{:?}", result);
+    /// assert_eq!(result.get_addr(), binary[5..].as_ptr());
     /// ```
-    pub unsafe fn find(
+    pub unsafe fn find_aligned_to_instruction(
         &self,
         preferred_scan_mode: Option<ScanMode>,
         binary_ptr: *const u8,
         binary_size: usize,
+        decoder: impl Fn(*const u8) -> usize,
     ) -> ScanResult {
-        // SAFETY: safe to call as long as the safety conditions were met for this function
-        unsafe { backends::find(&self.0, preferred_scan_mode, binary_ptr, binary_size) }
+        if !is_valid_range(binary_ptr, binary_size) {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        let mut boundary = 0;
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(preferred_scan_mode, binary, start) {
+            while boundary < offset {
+                boundary += decoder(binary[boundary..].as_ptr()).max(1);
+            }
+
+            if boundary == offset {
+                return ScanResult {
+                    addr: binary[offset + self.pattern.cursor..].as_ptr(),
+                    writable: false,
+                };
+            }
+
+            start = offset + 1;
+        }
+
+        ScanResult {
+            addr: ptr::null(),
+            writable: false,
+        }
+    }
+
+    /// Find the offset of the first match of the pattern which starts at an instruction
+    /// boundary, only trying the boundaries
+    ///
+    /// Finds the same match as [`Scanner::find_aligned_to_instruction`], but walks the
+    /// instructions instead of scanning for candidates: the pattern is only compared at the
+    /// start of every instruction, advancing by its decoded length on a mismatch. `decode_len`
+    /// is given the address of the instruction and the number of bytes left in the binary.
+    /// A decoded length of zero is treated as a single byte.
+    ///
+    /// Every instruction up to the match is decoded and checked for the anchor byte of the
+    /// pattern, so this only wins when the decoder is cheap and the SIMD scan would find many
+    /// misaligned candidates. In the `instruction_walk` benchmark, walking is about 1.2 times
+    /// faster for a pattern occurring all over the operands, but about 18 times slower for
+    /// a pattern which doesn't occur at all, where the SIMD scan skips the decoding entirely.
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// // mov eax, 0xc3c3c3c3; ret
+    /// let binary = [0xb8, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3];
+    /// let decode_len = |addr: *const u8, _: usize| match unsafe { *addr } {
+    ///     0xb8 => 5,
+    ///     _ => 1,
+    /// };
+    ///
+    /// let scanner = Scanner::new("c3");
+    /// let offset =
+    ///     unsafe { scanner.find_instruction_aligned(binary.as_ptr(), binary.len(), decode_len) };
+    ///
+    /// assert_eq!(offset, Some(5));
+    /// ```
+    pub unsafe fn find_instruction_aligned(
+        &self,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        decode_len: impl Fn(*const u8, usize) -> usize,
+    ) -> Option<usize> {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return None;
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+        let last_start = binary_size.checked_sub(self.pattern.unpadded_size)?;
+        let anchor = self.pattern.selectivity().anchor;
+
+        let mut boundary = 0;
+        while boundary <= last_start {
+            // rejecting most boundaries by the anchor alone is cheaper than the full compare
+            let anchored =
+                anchor.is_none_or(|anchor| binary[boundary + anchor.offset] == anchor.byte);
+            if anchored && backends::matches_at(&self.pattern, binary, boundary) {
+                return Some(boundary);
+            }
+
+            let rest = &binary[boundary..];
+            boundary += decode_len(rest.as_ptr(), rest.len()).max(1);
+        }
+
+        None
+    }
+}
+
+impl Scanner {
+    /// Find the pattern followed by `second`, separated by a gap of bounded size
+    ///
+    /// For every occurrence of the pattern, `second` is searched for starting between
+    /// `min_gap` and `max_gap` bytes (inclusive) after the end of the occurrence.
+    /// Returns the offsets of the first pair found, which is useful for signatures
+    /// where the distance between two anchors varies between builds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xe8, 0x10, 0x20, 0x30, 0x40, 0x90, 0x90, 0x84, 0xc0];
+    ///
+    /// let scanner = Scanner::new("e8 ?? ?? ?? ??");
+    /// let test = Pattern::new("84 c0");
+    ///
+    /// assert_eq!(scanner.find_bounded_gap(&test, 0, 4, &binary), Some((0, 7)));
+    /// assert_eq!(scanner.find_bounded_gap(&test, 0, 1, &binary), None);
+    /// ```
+    pub fn find_bounded_gap(
+        &self,
+        second: &Pattern,
+        min_gap: usize,
+        max_gap: usize,
+        binary: &[u8],
+    ) -> Option<(usize, usize)> {
+        let mut start = 0;
+        while let Some(first) = self.find_in_slice(None, binary, start) {
+            let first_end = first + self.pattern.unpadded_size;
+
+            let range_start = first_end.saturating_add(min_gap).min(binary.len());
+            let range_end = first_end
+                .saturating_add(max_gap)
+                .saturating_add(second.unpadded_size)
+                .min(binary.len());
+
+            if let Some(found) = backends::find_in_range(
+                second,
+                None,
+                binary,
+                range_start..range_end.max(range_start),
+            ) {
+                return Some((first, found));
+            }
+
+            start = first + 1;
+        }
+
+        None
+    }
+}
+
+/// Padding expected in front of a function start
+///
+/// Used by [`Scanner::find_prologue`]. The default accepts at least two `0xCC` (int3) bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PrologueFilter {
+    /// Minimum number of padding bytes directly preceding the match
+    pub min_padding: usize,
+    /// Accept `0xCC` (int3) as a padding byte
+    pub int3: bool,
+    /// Accept `0x00` as a padding byte
+    pub zero: bool,
+}
+
+impl PrologueFilter {
+    fn is_padding(&self, byte: u8) -> bool {
+        (self.int3 && byte == 0xcc) || (self.zero && byte == 0x00)
+    }
+}
+
+impl Default for PrologueFilter {
+    fn default() -> Self {
+        PrologueFilter {
+            min_padding: 2,
+            int3: true,
+            zero: false,
+        }
+    }
+}
+
+impl Scanner {
+    /// Find the first occurrence of the pattern in `binary`, starting at `start`
+    ///
+    /// Returns the offset of the match from the beginning of `binary`
+    pub(crate) fn find_in_slice(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        start: usize,
+    ) -> Option<usize> {
+        self.find_in_range(preferred_scan_mode, binary, start..binary.len())
+    }
+
+    /// Find the offsets of all occurrences of the pattern starting within `start..end` of `binary`
+    ///
+    /// Matches may extend past `end`, up to the end of `binary`.
+    pub(crate) fn find_all_starting_in(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        mut start: usize,
+        end: usize,
+    ) -> Vec<usize> {
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+        let range_end = end.saturating_add(overlap).min(binary.len());
+
+        let mut offsets = Vec::new();
+        while let Some(offset) = self.find_in_range(preferred_scan_mode, binary, start..range_end) {
+            if offset >= end {
+                break;
+            }
+
+            offsets.push(offset);
+            start = offset + 1;
+        }
+
+        offsets
+    }
+
+    /// Find the first occurrence of the pattern which lies entirely within `range` of `binary`
+    ///
+    /// Returns the offset of the match from the beginning of `binary`
+    pub(crate) fn find_in_range(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        range: Range<usize>,
+    ) -> Option<usize> {
+        if let (None, Some(fast_path)) =
+            (ScanMode::preference(preferred_scan_mode), &self.fast_path)
+        {
+            let start = range.start;
+            return fast_path
+                .find(binary.get(range)?)
+                .map(|offset| start + offset);
+        }
+
+        backends::find_in_range(&self.pattern, preferred_scan_mode, binary, range)
     }
 }
 
+/// Check if `binary_ptr` and `binary_size` can describe a slice at all
+///
+/// Null pointers, binaries wrapping around the end of the address space and sizes above
+/// `isize::MAX`, which [`slice::from_raw_parts`] doesn't allow, are rejected. Raw pointer
+/// scans check this before reading any memory.
+pub(crate) fn is_valid_range(binary_ptr: *const u8, binary_size: usize) -> bool {
+    !binary_ptr.is_null()
+        && (binary_ptr as usize).checked_add(binary_size).is_some()
+        && binary_size <= isize::MAX as usize
+}
+
 impl From<Pattern> for Scanner {
     fn from(value: Pattern) -> Self {
-        Scanner(value)
+        Scanner {
+            fast_path: FastPath::new(&value),
+            pattern: value,
+        }
     }
 }
 
 /// Scan mode
+///
+/// Every scan mode reports exactly the same matches, they only differ in speed.
+/// Passing `None` as the preferred scan mode is equivalent to [`ScanMode::Auto`].
+/// A specific mode which isn't supported by the running CPU falls back to the fastest
+/// available one, [`Scanner::try_find`] reports it as an [`UnsupportedMode`] error instead.
+/// [`ScanMode::is_supported`] checks for support up front. With the `no-simd` feature,
+/// only the scalar implementation is compiled in and every other mode is unsupported.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ScanMode {
+    /// Pick the fastest implementation for the pattern and the haystack
+    ///
+    /// This uses a substring search for patterns without wildcards, the scalar backend
+    /// for tiny haystacks, and otherwise the widest SIMD backend supported by the CPU.
+    Auto,
     /// Scalar scan mode
     Scalar,
     /// Scan mode that uses SSE4.2 SIMD instructions
     Sse42,
     /// Scan mode that uses AVX2 SIMD instructions
     Avx2,
+    /// Scan mode that uses portable `std::simd` instructions, requires a nightly compiler
+    #[cfg(feature = "portable-simd")]
+    Portable,
+}
+
+impl ScanMode {
+    /// Check if the running CPU supports the scan mode
+    ///
+    /// [`ScanMode::Auto`] and [`ScanMode::Scalar`] are always supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::ScanMode;
+    ///
+    /// assert!(ScanMode::Scalar.is_supported());
+    /// println!("AVX2 supported: {}", ScanMode::Avx2.is_supported());
+    /// ```
+    pub fn is_supported(self) -> bool {
+        self == ScanMode::Auto || backends::supported_modes().contains(&self)
+    }
+
+    /// Normalize a preferred scan mode, [`ScanMode::Auto`] being the same as no preference
+    pub(crate) fn preference(mode: Option<ScanMode>) -> Option<ScanMode> {
+        mode.filter(|&mode| mode != ScanMode::Auto)
+    }
 }
 
 /// Scan result
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///
+/// Results are ordered by address, invalid results sorting before all valid ones.
+/// Results at the same address compare equal, whether they're writable or not.
+#[derive(Debug, Copy, Clone)]
 pub struct ScanResult {
     addr: *const u8,
+    /// The address was derived from a mutable reference, see [`Scanner::find_in_mut`]
+    writable: bool,
+}
+
+impl PartialEq for ScanResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr == other.addr
+    }
+}
+
+impl Eq for ScanResult {}
+
+impl PartialOrd for ScanResult {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScanResult {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.addr.cmp(&other.addr)
+    }
+}
+
+impl std::hash::Hash for ScanResult {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.addr.hash(state);
+    }
 }
 
 impl ScanResult {
@@ -174,9 +1552,156 @@ impl ScanResult {
     ///
     /// * The scan result was produced from an immutable reference
     ///
+    /// Results of [`Scanner::find_in_mut`] provide a pointer which may be written through
+    /// with [`ScanResult::as_mut_ptr`].
+    ///
     /// [allocated object]: crate::ptr#allocated-object
     pub unsafe fn get_mut_ptr<T>(&self, offset: isize) -> *mut T {
         // SAFETY: the caller must uphold the safety contract for `get_mut_ptr`.
         unsafe { self.addr.offset(offset) as *mut _ }
     }
+
+    /// Get the address of this scan result as a pointer which may be written through
+    ///
+    /// Only results of [`Scanner::find_in_mut`] have one, their address was derived from
+    /// a mutable reference to the scanned buffer. The pointer may be written through for
+    /// as long as that buffer isn't accessed in any other way. Returns `None` for invalid
+    /// results and for results of scans over immutable memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let mut data = [0xcc, 0x74, 0x05, 0x90];
+    /// let scanner = Scanner::new("74 ??");
+    ///
+    /// let result = scanner.find_in_mut(None, &mut data);
+    /// let ptr = result.as_mut_ptr().unwrap();
+    /// // SAFETY: the pointer came from `data`, which isn't accessed otherwise in the meantime
+    /// unsafe { ptr.write(0xeb) };
+    /// assert_eq!(data, [0xcc, 0xeb, 0x05, 0x90]);
+    ///
+    /// // results of scans over immutable memory can't be written through
+    /// let result = unsafe { scanner.find(None, data.as_ptr(), data.len()) };
+    /// assert_eq!(result.as_mut_ptr(), None);
+    /// ```
+    pub fn as_mut_ptr(&self) -> Option<*mut u8> {
+        (self.writable && self.is_valid()).then_some(self.addr.cast_mut())
+    }
+
+    /// Page size assumed by [`ScanResult::default_page_base`]
+    pub const DEFAULT_PAGE_SIZE: usize = 4096;
+
+    /// Get the base address of the page containing this scan result, `None` if it's invalid
+    ///
+    /// This is the address to pass to `VirtualProtect` or `mprotect` to change the
+    /// protection of the matched bytes. Note that the matched bytes may extend into
+    /// the following page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` isn't a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    ///
+    /// let page_base = result.page_base(4096).unwrap();
+    /// assert_eq!(page_base, result.get_addr() as usize & !0xfff);
+    /// ```
+    pub fn page_base(&self, page_size: usize) -> Option<usize> {
+        assert!(
+            page_size.is_power_of_two(),
+            "page size of {} bytes is not a power of two",
+            page_size
+        );
+
+        self.is_valid()
+            .then(|| self.addr as usize & !(page_size - 1))
+    }
+
+    /// Get the base address of the page containing this scan result, assuming 4 KiB pages
+    ///
+    /// See [`ScanResult::page_base`].
+    pub fn default_page_base(&self) -> Option<usize> {
+        self.page_base(Self::DEFAULT_PAGE_SIZE)
+    }
+}
+
+/// Error for a scan mode which isn't supported by the running CPU, see [`Scanner::try_find`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct UnsupportedMode {
+    /// The requested scan mode
+    pub mode: ScanMode,
+}
+
+impl fmt::Display for UnsupportedMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} scan mode is not supported by this CPU", self.mode)
+    }
+}
+
+impl std::error::Error for UnsupportedMode {}
+
+/// Scan error
+#[derive(Debug)]
+pub enum ScanError {
+    /// Reading the memory to scan failed
+    Io(io::Error),
+    /// The scan took longer than the configured timeout
+    TimedOut,
+    /// The pointer to the binary is null
+    NullPointer,
+    /// The binary is empty or smaller than the pattern
+    BinaryTooSmall {
+        /// Size of the binary in bytes
+        binary_size: usize,
+        /// Size of the pattern in bytes
+        pattern_size: usize,
+    },
+    /// The binary extends past the end of the address space
+    AddressOverflow,
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Io(err) => write!(f, "unable to read memory: {}", err),
+            ScanError::TimedOut => write!(f, "scan timed out"),
+            ScanError::NullPointer => write!(f, "binary pointer is null"),
+            ScanError::BinaryTooSmall {
+                binary_size,
+                pattern_size,
+            } => write!(
+                f,
+                "binary of {} bytes is too small for a pattern of {} bytes",
+                binary_size, pattern_size
+            ),
+            ScanError::AddressOverflow => {
+                write!(f, "binary extends past the end of the address space")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScanError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScanError {
+    fn from(value: io::Error) -> Self {
+        ScanError::Io(value)
+    }
 }
@@ -14,111 +14,62 @@
 /// // You can then create a scanner from it.
 /// let scanner = Scanner::from(pattern);
 /// ```
+///
+/// Like with [`Pattern::new`](crate::pattern::Pattern::new), there's no limit to the length of the pattern.
+///
+/// Malformed patterns and patterns made of wildcards only fail to compile:
+///
+/// ```compile_fail
+/// let pattern = lightningscanner::create_pattern!("?? ?? ??");
+/// ```
 #[macro_export]
 macro_rules! create_pattern {
     ($pattern:expr) => {{
-        // This module contains the compile-time parser for IDA-style patterns.
-        // It's defined inside the macro to not pollute the module namespace.
-        // All functions inside are `const` and will be evaluated at compile time.
-        mod const_parser {
-            // A struct to hold the result of the compile-time parsing.
-            // The arrays have a fixed size, and `len` holds the actual pattern length.
-            // This is a common pattern for `const` contexts where dynamic allocation is not possible.
-            pub struct ParsedPattern {
-                pub data: [u8; 256],
-                pub mask: [u8; 256],
-                pub len: usize,
-            }
-
-            // A `const` version of `char_to_byte` from the original `Pattern::new`.
-            const fn char_to_byte(c: u8) -> u8 {
-                if c >= b'a' && c <= b'z' {
-                    c - b'a' + 0xA
-                } else if c >= b'A' && c <= b'Z' {
-                    c - b'A' + 0xA
-                } else if c >= b'0' && c <= b'9' {
-                    c - b'0'
-                } else {
-                    0
-                }
-            }
-
-            // The main `const` function to parse the pattern string.
-            pub const fn parse_pattern(pattern: &str) -> ParsedPattern {
-                let pattern = pattern.as_bytes();
-                let mut data = [0u8; 256];
-                let mut mask = [0u8; 256];
-                let mut len = 0;
-                let mut i = 0;
-
-                while i < pattern.len() {
-                    // We use a fixed-size array, so we must check for overflow.
-                    if len >= 256 {
-                        panic!("Pattern is too long for compile-time parsing (max 256 bytes)");
-                    }
+        // The pattern string is parsed at compile time here,
+        // into buffers sized to fit the whole pattern.
+        const LEN: usize = $crate::const_parser::pattern_len($pattern);
+        const PARSED: $crate::const_parser::ParsedPattern<LEN> =
+            $crate::const_parser::parse_pattern::<LEN>($pattern);
 
-                    let symbol = pattern[i];
-                    let next_symbol = if i + 1 < pattern.len() {
-                        pattern[i + 1]
-                    } else {
-                        b'\0'
-                    };
-
-                    i += 1;
-
-                    match symbol {
-                        b' ' => continue,
-                        b'?' => {
-                            data[len] = 0x00;
-                            mask[len] = 0x00;
-                            len += 1;
-
-                            if next_symbol == b'?' {
-                                i += 1;
-                            }
-                            continue;
-                        }
-                        _ => {
-                            let byte = (char_to_byte(symbol) << 4) | char_to_byte(next_symbol);
-                            data[len] = byte;
-                            mask[len] = 0xff;
-                            len += 1;
-                            i += 1;
-                        }
-                    }
-                }
-
-                ParsedPattern { data, mask, len }
-            }
-        }
-
-        // The pattern string is parsed at compile time here.
-        const PARSED: const_parser::ParsedPattern = const_parser::parse_pattern($pattern);
-
-        // The rest of the code constructs the `Pattern` at runtime,
+        // The `Pattern` itself is constructed at runtime, as it uses heap allocation,
         // but from the data that was prepared at compile time.
-        // This is necessary because `Pattern` uses heap allocation.
-
-        let unpadded_size = PARSED.len;
-
-        let mut data_vec = PARSED.data[..unpadded_size].to_vec();
-        let mut mask_vec = PARSED.mask[..unpadded_size].to_vec();
+        $crate::pattern::Pattern::from_masked_bytes(
+            &PARSED.data[..PARSED.len],
+            &PARSED.mask[..PARSED.len],
+        )
+        .with_cursor(PARSED.cursor)
+    }};
+}
 
-        // The padding logic from the original `Pattern::new`.
-        // We use integer arithmetic to be compatible with `const` contexts if needed,
-        // although here it runs at runtime.
-        const ALIGNMENT: usize = 32;
-        let count = (unpadded_size + ALIGNMENT - 1) / ALIGNMENT;
-        let padding_size = count * ALIGNMENT - unpadded_size;
+/// Find the offset of the first occurrence of a pattern in a byte string at compile time.
+///
+/// The haystack has to be a constant expression, such as the result of `include_bytes!`.
+/// The macro evaluates to an `Option<usize>` constant, so it can be used in `const` items.
+/// Matches follow the same rules as the runtime scanners.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::find_pattern_const;
+///
+/// const FIRMWARE: &[u8] = &[0x00, 0x11, 0xde, 0xad, 0xbe, 0xef, 0x22];
+/// const MAGIC_OFFSET: Option<usize> = find_pattern_const!(FIRMWARE, "de ad ?? ef");
+///
+/// assert_eq!(MAGIC_OFFSET, Some(2));
+/// ```
+#[macro_export]
+macro_rules! find_pattern_const {
+    ($haystack:expr, $pattern:expr) => {{
+        const LEN: usize = $crate::const_parser::pattern_len($pattern);
+        const PARSED: $crate::const_parser::ParsedPattern<LEN> =
+            $crate::const_parser::parse_pattern::<LEN>($pattern);
 
-        data_vec.resize(unpadded_size + padding_size, 0);
-        mask_vec.resize(unpadded_size + padding_size, 0);
+        const RESULT: ::core::option::Option<usize> = $crate::const_scan::const_find(
+            $haystack,
+            PARSED.data.split_at(PARSED.len).0,
+            PARSED.mask.split_at(PARSED.len).0,
+        );
 
-        // Finally, create the `Pattern` instance using the public constructor.
-        $crate::pattern::Pattern::from_parts(
-            $crate::aligned_bytes::AlignedBytes::new(&data_vec),
-            $crate::aligned_bytes::AlignedBytes::new(&mask_vec),
-            unpadded_size,
-        )
+        RESULT
     }};
 }
@@ -0,0 +1,47 @@
+//! Masked comparisons outside of scans
+
+use crate::backends;
+use crate::pattern::Pattern;
+
+/// Check if `haystack` equals `data` in every bit set in `mask`
+///
+/// This is the comparison the scanners verify candidate matches with, comparing a machine
+/// word at a time. A `mask` byte of `0xff` requires the byte to be equal, `0x00` accepts any
+/// byte, and other values only compare the set bits. Slices of different lengths are never
+/// equal.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::masked_eq;
+///
+/// let patched = [0xeb, 0x05, 0x90];
+///
+/// assert!(masked_eq(&patched, &[0xeb, 0x00, 0x90], &[0xff, 0x00, 0xff]));
+/// assert!(!masked_eq(&patched, &[0x74, 0x00, 0x90], &[0xff, 0x00, 0xff]));
+/// assert!(!masked_eq(&patched, &[0xeb], &[0xff]));
+/// ```
+pub fn masked_eq(haystack: &[u8], data: &[u8], mask: &[u8]) -> bool {
+    backends::masked_eq(haystack, data, mask)
+}
+
+/// Check if `pattern` matches `haystack` at `pos`
+///
+/// A match which would extend past the end of `haystack` is rejected.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::masked_eq_at;
+/// use lightningscanner::pattern::Pattern;
+///
+/// let haystack = [0xcc, 0x48, 0x8b, 0x05];
+/// let pattern = Pattern::new("48 ?? 05");
+///
+/// assert!(masked_eq_at(&haystack, 1, &pattern));
+/// assert!(!masked_eq_at(&haystack, 2, &pattern));
+/// assert!(!masked_eq_at(&haystack, 5, &pattern));
+/// ```
+pub fn masked_eq_at(haystack: &[u8], pos: usize, pattern: &Pattern) -> bool {
+    backends::matches_at(pattern, haystack, pos)
+}
@@ -0,0 +1,193 @@
+//! Combining the matches of several scans
+
+use crate::ScanResult;
+
+/// Sorted set of match offsets or addresses
+///
+/// Lists are kept in ascending order without duplicates, whatever they're built from.
+/// Matches of several regions are combined by translating them to absolute addresses
+/// with [`MatchList::from_region`] and merging the lists.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::match_list::MatchList;
+/// use lightningscanner::Scanner;
+///
+/// let binary = [0x48, 0x89, 0x5c, 0x24, 0x48, 0x89, 0x6c, 0x24];
+///
+/// let a = MatchList::from(Scanner::new("48 89").find_all_offsets(None, &binary));
+/// let b = MatchList::from(Scanner::new("48 89 6c").find_all_offsets(None, &binary));
+///
+/// // offsets matching `48 89` but not `48 89 6c`
+/// assert_eq!(a.difference(&b).as_slice(), [0]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MatchList {
+    matches: Vec<usize>,
+}
+
+impl MatchList {
+    /// Create a new empty list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a list of the absolute addresses of matches found in a region at `base`
+    ///
+    /// # Panics
+    ///
+    /// Panics if an address overflows.
+    pub fn from_region(base: usize, offsets: impl IntoIterator<Item = usize>) -> Self {
+        offsets
+            .into_iter()
+            .map(|offset| {
+                base.checked_add(offset)
+                    .expect("match address overflows the address space")
+            })
+            .collect()
+    }
+
+    /// The matches in ascending order
+    pub fn as_slice(&self) -> &[usize] {
+        &self.matches
+    }
+
+    /// Convert into a vector of the matches in ascending order
+    pub fn into_vec(self) -> Vec<usize> {
+        self.matches
+    }
+
+    /// Number of matches
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Check if the list has no matches
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    /// Check if the list contains `offset`
+    pub fn contains(&self, offset: usize) -> bool {
+        self.matches.binary_search(&offset).is_ok()
+    }
+
+    /// Iterate over the matches in ascending order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator + '_ {
+        self.matches.iter().copied()
+    }
+
+    /// Create a list of the matches in either list
+    pub fn merge(&self, other: &MatchList) -> MatchList {
+        let mut matches = Vec::with_capacity(self.len() + other.len());
+
+        let (mut a, mut b) = (self.matches.as_slice(), other.matches.as_slice());
+        while let (Some(&first), Some(&second)) = (a.first(), b.first()) {
+            matches.push(first.min(second));
+
+            if first <= second {
+                a = &a[1..];
+            }
+            if second <= first {
+                b = &b[1..];
+            }
+        }
+        matches.extend_from_slice(a);
+        matches.extend_from_slice(b);
+
+        MatchList { matches }
+    }
+
+    /// Create a list of the matches in both lists
+    pub fn intersect(&self, other: &MatchList) -> MatchList {
+        self.iter()
+            .filter(|&offset| other.contains(offset))
+            .collect()
+    }
+
+    /// Create a list of the matches in this list but not in `other`
+    pub fn difference(&self, other: &MatchList) -> MatchList {
+        self.iter()
+            .filter(|&offset| !other.contains(offset))
+            .collect()
+    }
+
+    /// Collapse clusters of nearby matches into their earliest match
+    ///
+    /// Going through the list in ascending order, a match is removed if it's at most
+    /// `distance` bytes after the previous match that was kept. Every match which is kept
+    /// is therefore more than `distance` bytes after the previous one, and every removed
+    /// match is within `distance` bytes after a kept one. A distance of 0 keeps everything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::match_list::MatchList;
+    ///
+    /// let mut list = MatchList::from(vec![0, 3, 6, 9, 20]);
+    /// list.dedup_within(4);
+    ///
+    /// // 3 is within 4 bytes of 0, 9 within 4 bytes of 6
+    /// assert_eq!(list.as_slice(), [0, 6, 20]);
+    /// ```
+    pub fn dedup_within(&mut self, distance: usize) {
+        let mut kept: Option<usize> = None;
+
+        self.matches.retain(|&offset| {
+            if kept.is_some_and(|kept| offset - kept <= distance) {
+                return false;
+            }
+
+            kept = Some(offset);
+            true
+        });
+    }
+
+    /// Keep only matches at a multiple of `align`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is zero.
+    pub fn retain_aligned(&mut self, align: usize) {
+        assert!(align != 0, "alignment must not be zero");
+
+        self.matches.retain(|&offset| offset % align == 0);
+    }
+}
+
+impl FromIterator<usize> for MatchList {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl From<Vec<usize>> for MatchList {
+    /// Create a list from offsets or addresses in any order, e.g. those of [`crate::Scanner::find_all_offsets`]
+    fn from(mut matches: Vec<usize>) -> Self {
+        matches.sort_unstable();
+        matches.dedup();
+
+        MatchList { matches }
+    }
+}
+
+impl From<Vec<ScanResult>> for MatchList {
+    /// Create a list of the addresses of the valid results, e.g. those of [`crate::Scanner::find_all`]
+    fn from(results: Vec<ScanResult>) -> Self {
+        results
+            .into_iter()
+            .filter(ScanResult::is_valid)
+            .map(|result| result.get_addr() as usize)
+            .collect()
+    }
+}
+
+impl IntoIterator for MatchList {
+    type Item = usize;
+    type IntoIter = std::vec::IntoIter<usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.matches.into_iter()
+    }
+}
@@ -0,0 +1,311 @@
+//! Scanning Windows minidump files
+//!
+//! Only available with the `minidump` feature. The memory of the dumped process is read
+//! from the `Memory64ListStream` of full dumps and the `MemoryListStream` of smaller ones,
+//! the `ModuleListStream` is used to attribute matches to modules.
+
+use crate::file::Mapping;
+use crate::Scanner;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// `MDMP` in little endian
+const SIGNATURE: u32 = 0x504d_444d;
+
+const MODULE_LIST_STREAM: u32 = 4;
+const MEMORY_LIST_STREAM: u32 = 5;
+const MEMORY64_LIST_STREAM: u32 = 9;
+
+/// Size of a `MINIDUMP_MODULE`, which is packed to 4 bytes
+const MODULE_SIZE: u64 = 108;
+
+/// Match in the memory of a dumped process
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DumpMatch {
+    /// Virtual address of the match start in the dumped process
+    pub address: u64,
+    /// Offset of the match start in the dump file
+    pub file_offset: u64,
+    /// Name of the module containing the match as stored in the dump, usually a full path
+    pub module: Option<String>,
+}
+
+/// Error reading a minidump
+#[derive(Debug)]
+pub enum DumpError {
+    /// The dump file couldn't be read
+    Io(io::Error),
+    /// The file doesn't start with the minidump signature
+    InvalidSignature,
+    /// A structure of the dump extends past the end of the file
+    Truncated {
+        /// File offset of the structure
+        offset: u64,
+    },
+    /// The dump contains no memory list
+    NoMemoryList,
+}
+
+impl fmt::Display for DumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpError::Io(err) => write!(f, "failed to read dump: {}", err),
+            DumpError::InvalidSignature => write!(f, "not a minidump file"),
+            DumpError::Truncated { offset } => {
+                write!(f, "dump is truncated at offset {:#x}", offset)
+            }
+            DumpError::NoMemoryList => write!(f, "dump contains no memory list"),
+        }
+    }
+}
+
+impl std::error::Error for DumpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DumpError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DumpError {
+    fn from(value: io::Error) -> Self {
+        DumpError::Io(value)
+    }
+}
+
+impl Scanner {
+    /// Find all occurrences of the pattern in the memory stored in a minidump
+    ///
+    /// Memory ranges which are contiguous both in the dumped process and in the file
+    /// are scanned as one, so matches can span them. Ranges extending past the end of
+    /// the file are clamped to the bytes it contains. Matches are reported in ascending
+    /// address order.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lightningscanner::Scanner;
+    ///
+    /// let dump = std::fs::read("crash.dmp").unwrap();
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    ///
+    /// for found in scanner.find_in_minidump(&dump).unwrap() {
+    ///     println!("{:#x} in {:?}", found.address, found.module);
+    /// }
+    /// ```
+    pub fn find_in_minidump(&self, dump: &[u8]) -> Result<Vec<DumpMatch>, DumpError> {
+        let reader = Reader(dump);
+
+        if reader.u32(0)? != SIGNATURE {
+            return Err(DumpError::InvalidSignature);
+        }
+
+        let stream_count = reader.u32(8)?;
+        let directory = u64::from(reader.u32(12)?);
+
+        let mut ranges = Vec::new();
+        let mut has_memory_list = false;
+        let mut modules = Vec::new();
+
+        for stream in 0..u64::from(stream_count) {
+            let entry = directory + stream * 12;
+            let location = u64::from(reader.u32(entry + 8)?);
+
+            match reader.u32(entry)? {
+                MEMORY64_LIST_STREAM => {
+                    has_memory_list = true;
+                    reader.memory64_list(location, &mut ranges)?;
+                }
+                MEMORY_LIST_STREAM => {
+                    has_memory_list = true;
+                    reader.memory_list(location, &mut ranges)?;
+                }
+                MODULE_LIST_STREAM => reader.module_list(location, &mut modules)?,
+                _ => {}
+            }
+        }
+
+        if !has_memory_list {
+            return Err(DumpError::NoMemoryList);
+        }
+
+        for range in &mut ranges {
+            // truncated dumps may be missing the end of the memory
+            let available = (dump.len() as u64).saturating_sub(range.file_offset);
+            range.size = range.size.min(available).min(u64::MAX - range.address);
+        }
+        ranges.retain(|range| range.size != 0);
+        ranges.sort_unstable_by_key(|range| range.address);
+
+        let mut matches = Vec::new();
+        for range in merge_contiguous(&ranges) {
+            // the range was clamped to the dump, so it fits into its length
+            let start = range.file_offset as usize;
+            let memory = &dump[start..start + range.size as usize];
+
+            for offset in self.find_all_offsets(None, memory) {
+                let address = range.address + offset as u64;
+                let module = modules
+                    .iter()
+                    .find(|module| module.contains(address))
+                    .map(|module| module.name.clone());
+
+                matches.push(DumpMatch {
+                    address,
+                    file_offset: range.file_offset + offset as u64,
+                    module,
+                });
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Find all occurrences of the pattern in the memory stored in a minidump file
+    ///
+    /// The file is memory-mapped where supported and must not be modified while it is
+    /// being scanned. See [`Scanner::find_in_minidump`].
+    pub fn find_in_minidump_file(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<DumpMatch>, DumpError> {
+        let file = File::open(path)?;
+        let contents = Mapping::new(&file)?;
+
+        self.find_in_minidump(&contents)
+    }
+}
+
+/// Range of the dumped process' memory stored in the dump
+#[derive(Debug, Copy, Clone)]
+struct MemoryRange {
+    address: u64,
+    file_offset: u64,
+    size: u64,
+}
+
+/// Module loaded in the dumped process
+struct Module {
+    base: u64,
+    size: u64,
+    name: String,
+}
+
+impl Module {
+    fn contains(&self, address: u64) -> bool {
+        address
+            .checked_sub(self.base)
+            .is_some_and(|offset| offset < self.size)
+    }
+}
+
+/// Merge ranges sorted by address which are contiguous in memory and in the file
+fn merge_contiguous(ranges: &[MemoryRange]) -> Vec<MemoryRange> {
+    let mut merged: Vec<MemoryRange> = Vec::new();
+
+    for &range in ranges {
+        match merged.last_mut() {
+            Some(last)
+                if last.address.checked_add(last.size) == Some(range.address)
+                    && last.file_offset + last.size == range.file_offset =>
+            {
+                last.size += range.size;
+            }
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Bounds checked little endian reads from a dump
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes(&self, offset: u64, len: u64) -> Result<&[u8], DumpError> {
+        usize::try_from(offset)
+            .ok()
+            .zip(usize::try_from(len).ok())
+            .and_then(|(start, len)| self.0.get(start..start.checked_add(len)?))
+            .ok_or(DumpError::Truncated { offset })
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32, DumpError> {
+        let bytes = self.bytes(offset, 4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn u64(&self, offset: u64) -> Result<u64, DumpError> {
+        let bytes = self.bytes(offset, 8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Read a `MINIDUMP_MEMORY64_LIST`, whose ranges are stored back to back
+    fn memory64_list(&self, offset: u64, ranges: &mut Vec<MemoryRange>) -> Result<(), DumpError> {
+        let count = self.u64(offset)?;
+        let mut file_offset = self.u64(offset + 8)?;
+
+        for index in 0..count {
+            let descriptor = index
+                .checked_mul(16)
+                .and_then(|descriptor| descriptor.checked_add(offset + 16))
+                .ok_or(DumpError::Truncated { offset })?;
+
+            let range = MemoryRange {
+                address: self.u64(descriptor)?,
+                file_offset,
+                size: self.u64(descriptor + 8)?,
+            };
+            ranges.push(range);
+
+            file_offset = file_offset.saturating_add(range.size);
+        }
+
+        Ok(())
+    }
+
+    /// Read a `MINIDUMP_MEMORY_LIST`, whose ranges are located individually
+    fn memory_list(&self, offset: u64, ranges: &mut Vec<MemoryRange>) -> Result<(), DumpError> {
+        let count = u64::from(self.u32(offset)?);
+
+        for index in 0..count {
+            let descriptor = offset + 4 + index * 16;
+
+            ranges.push(MemoryRange {
+                address: self.u64(descriptor)?,
+                size: u64::from(self.u32(descriptor + 8)?),
+                file_offset: u64::from(self.u32(descriptor + 12)?),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Read a `MINIDUMP_MODULE_LIST` along with the module names
+    fn module_list(&self, offset: u64, modules: &mut Vec<Module>) -> Result<(), DumpError> {
+        let count = u64::from(self.u32(offset)?);
+
+        for index in 0..count {
+            let module = offset + 4 + index * MODULE_SIZE;
+
+            let name_offset = u64::from(self.u32(module + 20)?);
+            let name_len = u64::from(self.u32(name_offset)?);
+            let name = self
+                .bytes(name_offset + 4, name_len)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect::<Vec<_>>();
+
+            modules.push(Module {
+                base: self.u64(module)?,
+                size: u64::from(self.u32(module + 8)?),
+                name: String::from_utf16_lossy(&name),
+            });
+        }
+
+        Ok(())
+    }
+}
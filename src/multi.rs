@@ -0,0 +1,207 @@
+//! Scanning for many patterns at once
+
+use crate::backends;
+use crate::pattern::Pattern;
+use aho_corasick::AhoCorasick;
+use std::collections::HashMap;
+
+/// Scanner for a large number of patterns, walking the haystack once
+///
+/// Every pattern is anchored on its longest run of concrete bytes, the runs of all
+/// patterns are combined into a single Aho-Corasick automaton. Each hit of the automaton
+/// is then verified against the full pattern, including its wildcards. Patterns without
+/// a single concrete byte are verified at every offset instead.
+/// Building the scanner is comparatively expensive, matching is linear in the size of the
+/// haystack plus the number of anchor hits.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::multi::MultiScanner;
+/// use lightningscanner::pattern::Pattern;
+///
+/// let scanner = MultiScanner::new([Pattern::new("48 89 ?? 24"), Pattern::new("c3 cc")]);
+/// let binary = [0xc3, 0xcc, 0x48, 0x89, 0x5c, 0x24];
+///
+/// // (pattern id, offset) pairs
+/// assert_eq!(scanner.find_all(&binary), [(1, 0), (0, 2)]);
+/// ```
+pub struct MultiScanner {
+    patterns: Vec<Pattern>,
+    automaton: AhoCorasick,
+    /// Patterns anchored on every needle of the automaton
+    anchors: Vec<Vec<Anchor>>,
+    /// Patterns without a single concrete byte, verified at every offset they fit at
+    unanchored: Vec<usize>,
+    /// Largest distance from the start of a pattern to the end of its needle
+    max_needle_end: usize,
+}
+
+/// Pattern anchored on a needle of the automaton
+#[derive(Debug, Copy, Clone)]
+struct Anchor {
+    pattern_id: usize,
+    /// Offset of the needle within the pattern
+    offset: usize,
+}
+
+impl MultiScanner {
+    /// Create a new [`MultiScanner`], patterns are identified by their index in `patterns`
+    pub fn new(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        let patterns = patterns.into_iter().collect::<Vec<_>>();
+
+        let mut needles: Vec<&[u8]> = Vec::new();
+        let mut needle_ids = HashMap::new();
+        let mut anchors: Vec<Vec<Anchor>> = Vec::new();
+        let mut unanchored = Vec::new();
+        let mut max_needle_end = 0;
+
+        for (pattern_id, pattern) in patterns.iter().enumerate() {
+            let Some((offset, len)) = longest_concrete_run(pattern) else {
+                unanchored.push(pattern_id);
+                continue;
+            };
+
+            let needle = &pattern.data[offset..offset + len];
+            max_needle_end = max_needle_end.max(offset + len);
+            let anchor = Anchor { pattern_id, offset };
+
+            // identical needles share a single automaton entry
+            let needle_id = *needle_ids.entry(needle).or_insert_with(|| {
+                needles.push(needle);
+                anchors.push(Vec::new());
+                needles.len() - 1
+            });
+            anchors[needle_id].push(anchor);
+        }
+
+        let automaton = AhoCorasick::new(&needles).expect("anchor automaton is too large");
+
+        MultiScanner {
+            patterns,
+            automaton,
+            anchors,
+            unanchored,
+            max_needle_end,
+        }
+    }
+
+    /// Find all occurrences of all patterns in `haystack`
+    ///
+    /// Returns `(pattern_id, offset)` pairs ordered by offset, then by pattern id.
+    /// Overlapping occurrences are reported separately.
+    pub fn find_all(&self, haystack: &[u8]) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+
+        for hit in self.automaton.find_overlapping_iter(haystack) {
+            for anchor in &self.anchors[hit.pattern().as_usize()] {
+                let Some(start) = hit.start().checked_sub(anchor.offset) else {
+                    continue;
+                };
+
+                let pattern = &self.patterns[anchor.pattern_id];
+                if backends::matches_at(pattern, haystack, start) {
+                    matches.push((anchor.pattern_id, start));
+                }
+            }
+        }
+
+        for &pattern_id in &self.unanchored {
+            let pattern = &self.patterns[pattern_id];
+            if let Some(last) = haystack.len().checked_sub(pattern.unpadded_size) {
+                matches.extend(
+                    (0..=last)
+                        .filter(|&offset| backends::matches_at(pattern, haystack, offset))
+                        .map(|offset| (pattern_id, offset)),
+                );
+            }
+        }
+
+        matches.sort_unstable_by_key(|&(pattern_id, offset)| (offset, pattern_id));
+        matches
+    }
+
+    /// Find the first occurrence of any pattern in `haystack`
+    ///
+    /// Returns the `(pattern_id, offset)` pair with the lowest offset, the lowest pattern id
+    /// if several patterns match there. This is the first entry [`MultiScanner::find_all`]
+    /// would report, but the scan stops as soon as no earlier match is possible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::multi::MultiScanner;
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let scanner = MultiScanner::new([Pattern::new("48 89 ?? 24"), Pattern::new("c3 cc")]);
+    /// let binary = [0x90, 0x48, 0x89, 0x5c, 0x24, 0xc3, 0xcc];
+    ///
+    /// assert_eq!(scanner.find_first_any(&binary), Some((0, 1)));
+    /// ```
+    pub fn find_first_any(&self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let mut first = self
+            .unanchored
+            .iter()
+            .filter_map(|&pattern_id| {
+                backends::find_in_slice(&self.patterns[pattern_id], None, haystack, 0)
+                    .map(|offset| (offset, pattern_id))
+            })
+            .min();
+
+        for hit in self.automaton.find_overlapping_iter(haystack) {
+            // hits are reported by their end, which bounds the start of the patterns using them
+            if first
+                .is_some_and(|(offset, _)| hit.end().saturating_sub(self.max_needle_end) > offset)
+            {
+                break;
+            }
+
+            for anchor in &self.anchors[hit.pattern().as_usize()] {
+                let Some(start) = hit.start().checked_sub(anchor.offset) else {
+                    continue;
+                };
+
+                let pattern = &self.patterns[anchor.pattern_id];
+                if first.is_none_or(|first| (start, anchor.pattern_id) < first)
+                    && backends::matches_at(pattern, haystack, start)
+                {
+                    first = Some((start, anchor.pattern_id));
+                }
+            }
+        }
+
+        first.map(|(offset, pattern_id)| (pattern_id, offset))
+    }
+
+    /// The patterns being scanned for, indexed by pattern id
+    pub fn patterns(&self) -> &[Pattern] {
+        &self.patterns
+    }
+}
+
+impl FromIterator<Pattern> for MultiScanner {
+    /// Create a new [`MultiScanner`], see [`MultiScanner::new`]
+    fn from_iter<T: IntoIterator<Item = Pattern>>(iter: T) -> Self {
+        MultiScanner::new(iter)
+    }
+}
+
+/// Offset and length of the longest run of fully-masked bytes, the earliest one on ties
+fn longest_concrete_run(pattern: &Pattern) -> Option<(usize, usize)> {
+    let mut longest: Option<(usize, usize)> = None;
+
+    let mut run_start = 0;
+    for (offset, &mask) in pattern.mask[..pattern.unpadded_size].iter().enumerate() {
+        if mask != 0xff {
+            run_start = offset + 1;
+            continue;
+        }
+
+        let len = offset + 1 - run_start;
+        if longest.is_none_or(|(_, longest)| len > longest) {
+            longest = Some((run_start, len));
+        }
+    }
+
+    longest
+}
@@ -0,0 +1,116 @@
+//! Scanning large haystacks on several threads
+//!
+//! Only available with the `rayon` feature.
+
+use crate::{ScanMode, Scanner};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+/// Configuration of [`Scanner::find_all_parallel`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParallelConfig {
+    /// Number of threads of the pool the scan runs on, rayon's default if `None`
+    ///
+    /// Ignored if `use_current_pool` is set.
+    pub threads: Option<usize>,
+    /// Number of bytes each task scans for match starts, zero is treated as one
+    ///
+    /// Every task buffers its own matches before they're merged, smaller chunks spread
+    /// the work more evenly, larger ones have less overhead.
+    pub chunk_bytes: usize,
+    /// Run on the current rayon pool instead of building a new one
+    ///
+    /// This is the pool installed with [`rayon::ThreadPool::install`] when called from
+    /// within it, and rayon's global pool otherwise.
+    pub use_current_pool: bool,
+}
+
+impl ParallelConfig {
+    /// Default number of bytes of each chunk, 16 MiB
+    pub const DEFAULT_CHUNK_BYTES: usize = 16 * 1024 * 1024;
+}
+
+impl Default for ParallelConfig {
+    /// Chunks of [`ParallelConfig::DEFAULT_CHUNK_BYTES`] on the current pool
+    fn default() -> Self {
+        ParallelConfig {
+            threads: None,
+            chunk_bytes: Self::DEFAULT_CHUNK_BYTES,
+            use_current_pool: true,
+        }
+    }
+}
+
+impl Scanner {
+    /// Find the offsets of all occurrences of the pattern in the binary, using several threads
+    ///
+    /// The binary is split into chunks of `config.chunk_bytes`, which are scanned in
+    /// parallel for matches starting within them, including those extending into the
+    /// next chunk. The result is the same as that of [`Scanner::find_all_offsets`],
+    /// in ascending order regardless of the number of threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new thread pool is requested and rayon fails to build it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::parallel::ParallelConfig;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    /// let config = ParallelConfig {
+    ///     threads: Some(2),
+    ///     chunk_bytes: 4,
+    ///     use_current_pool: false,
+    /// };
+    ///
+    /// let scanner = Scanner::new("48 89 5c");
+    /// assert_eq!(scanner.find_all_parallel(None, &binary, &config), [0, 5]);
+    /// ```
+    pub fn find_all_parallel(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        config: &ParallelConfig,
+    ) -> Vec<usize> {
+        let scan = || {
+            let chunk_bytes = config.chunk_bytes.max(1);
+            let chunk_count = binary.len().div_ceil(chunk_bytes).max(1);
+
+            (0..chunk_count)
+                .into_par_iter()
+                .map(|chunk| {
+                    let start = chunk * chunk_bytes;
+                    // empty patterns also match at the very end, which belongs to the last chunk
+                    let end = if chunk + 1 == chunk_count {
+                        binary.len() + 1
+                    } else {
+                        start + chunk_bytes
+                    };
+
+                    self.find_all_starting_in(preferred_scan_mode, binary, start, end)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let chunks = if config.use_current_pool {
+            scan()
+        } else {
+            ThreadPoolBuilder::new()
+                .num_threads(config.threads.unwrap_or(0))
+                .build()
+                .expect("failed to build the scan thread pool")
+                .install(scan)
+        };
+
+        // reserve once for all matches, instead of growing while appending chunk by chunk
+        let mut offsets = Vec::with_capacity(chunks.iter().map(Vec::len).sum());
+        for chunk in chunks {
+            offsets.extend(chunk);
+        }
+
+        offsets
+    }
+}
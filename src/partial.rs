@@ -0,0 +1,128 @@
+//! Diagnosing patterns that don't match
+
+use crate::pattern::Pattern;
+use crate::Scanner;
+
+impl Scanner {
+    /// Find the longest prefix of the pattern that occurs in `binary`
+    ///
+    /// Returns the offset and the length of the longest matching prefix, the earliest one
+    /// if there are several, or `None` if not even the first byte of the pattern occurs.
+    /// A full match is reported with the length of the whole pattern. Prefixes cut off by
+    /// the end of `binary` are taken into account, so this tells how far a signature got
+    /// before diverging, e.g. after an update changed an operand.
+    ///
+    /// Unlike the other scans, every position is compared against the pattern,
+    /// which makes this considerably slower.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0xc3];
+    ///
+    /// let scanner = Scanner::new("48 8b 05 ?? ?? ?? ?? 84 c0");
+    /// assert_eq!(scanner.find_best_partial(&binary), Some((1, 7)));
+    /// ```
+    pub fn find_best_partial(&self, binary: &[u8]) -> Option<(usize, usize)> {
+        let size = self.pattern.unpadded_size;
+        let mut best: Option<(usize, usize)> = None;
+
+        for offset in 0..binary.len() {
+            let matched = self.matched_prefix_len(binary, offset);
+
+            if matched > best.map_or(0, |(_, len)| len) {
+                best = Some((offset, matched));
+
+                if matched == size {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Find every occurrence of the longest prefix of the pattern in `binary`
+    ///
+    /// Like [`Scanner::find_best_partial`], but all offsets tying for the longest prefix are
+    /// reported in ascending order, each with the length of the prefix. When a signature's
+    /// target moved, this shows every decoy that gets just as far as the real relocation.
+    /// The list is empty if not even the first byte of the pattern occurs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = [0x48, 0x8b, 0x05, 0x10, 0xcc, 0x48, 0x8b, 0x05, 0x20, 0xc3];
+    ///
+    /// let scanner = Scanner::new("48 8b 05 ?? 84 c0");
+    /// assert_eq!(scanner.find_best_partial_all(&binary), [(0, 4), (5, 4)]);
+    /// ```
+    pub fn find_best_partial_all(&self, binary: &[u8]) -> Vec<(usize, usize)> {
+        let mut best = Vec::new();
+        let mut best_len = 0;
+
+        for offset in 0..binary.len() {
+            let matched = self.matched_prefix_len(binary, offset);
+
+            if matched > best_len {
+                best.clear();
+                best_len = matched;
+            }
+            if matched == best_len && matched != 0 {
+                best.push((offset, matched));
+            }
+        }
+
+        best
+    }
+
+    /// Length of the longest prefix of the pattern matching `binary` at `offset`
+    fn matched_prefix_len(&self, binary: &[u8], offset: usize) -> usize {
+        let size = self.pattern.unpadded_size;
+        let data = &self.pattern.data[..size];
+        let mask = &self.pattern.mask[..size];
+
+        binary[offset..]
+            .iter()
+            .zip(data.iter().zip(mask))
+            .take_while(|&(&value, (&data, &mask))| (value ^ data) & mask == 0)
+            .count()
+    }
+}
+
+impl Pattern {
+    /// List every byte of the pattern which disagrees with `data` at `at`
+    ///
+    /// Each entry is the offset of the byte within the pattern, the expected byte and the
+    /// actual one. Wildcards are skipped, and partially masked bytes are only reported if one
+    /// of their masked bits differs. Pattern bytes past the end of `data` can't disagree and
+    /// are skipped as well. Passing the offset from [`Scanner::find_best_partial`] shows
+    /// everything that changed at the closest candidate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let data = [0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0x85, 0xc0];
+    ///
+    /// let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? 84 c0");
+    /// assert_eq!(pattern.diff_at(&data, 1), [(7, 0x84, 0x85)]);
+    /// ```
+    pub fn diff_at(&self, data: &[u8], at: usize) -> Vec<(usize, u8, u8)> {
+        let size = self.unpadded_size;
+
+        data.get(at..)
+            .unwrap_or_default()
+            .iter()
+            .zip(self.data[..size].iter().zip(&self.mask[..size]))
+            .enumerate()
+            .filter(|&(_, (&actual, (&expected, &mask)))| (actual ^ expected) & mask != 0)
+            .map(|(offset, (&actual, (&expected, _)))| (offset, expected, actual))
+            .collect()
+    }
+}
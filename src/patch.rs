@@ -0,0 +1,215 @@
+//! Patching bytes at match sites
+
+use crate::{is_valid_range, ScanMode, ScanResult, Scanner};
+use std::{ptr, slice};
+
+/// Bytes to write at a fixed offset into every match of a pattern
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::patch::Patch;
+///
+/// // replace the byte after the match start with a `nop`
+/// let nop = Patch::new(1, vec![0x90]);
+///
+/// // only set the lowest bit of the first byte of the match
+/// let low_bit = Patch::new(0, vec![0x01]).with_mask(vec![0x01]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Patch {
+    offset: usize,
+    bytes: Vec<u8>,
+    mask: Option<Vec<u8>>,
+}
+
+impl Patch {
+    /// Create a new [`Patch`] replacing `bytes.len()` bytes, starting `offset` bytes into the match
+    pub fn new(offset: usize, bytes: Vec<u8>) -> Self {
+        Patch {
+            offset,
+            bytes,
+            mask: None,
+        }
+    }
+
+    /// Only modify the bits which are set in `mask`, leaving the others untouched
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` is not as long as the replacement bytes.
+    pub fn with_mask(mut self, mask: Vec<u8>) -> Self {
+        assert_eq!(
+            mask.len(),
+            self.bytes.len(),
+            "patch mask and replacement bytes differ in length"
+        );
+
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Offset of the patched bytes from the start of the match
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Replacement bytes
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Mask of the bits to modify, `None` if the bytes are replaced entirely
+    pub fn mask(&self) -> Option<&[u8]> {
+        self.mask.as_deref()
+    }
+
+    fn apply(&self, target: &mut [u8]) {
+        match &self.mask {
+            Some(mask) => {
+                for ((target, &byte), &mask) in target.iter_mut().zip(&self.bytes).zip(mask) {
+                    *target = (*target & !mask) | (byte & mask);
+                }
+            }
+            None => target.copy_from_slice(&self.bytes),
+        }
+    }
+}
+
+impl Scanner {
+    /// Apply `patch` to every occurrence of the pattern in the region
+    ///
+    /// See [`Scanner::patch_matches`].
+    /// Changing the memory protection of the region is left to the caller.
+    ///
+    /// # Safety
+    ///
+    /// * `region` - is a valid pointer, writable for `len` bytes
+    ///
+    /// * `len` - corresponds to a valid size of `region`
+    pub unsafe fn patch_at_matches(&self, region: *mut u8, len: usize, patch: &Patch) -> usize {
+        if !is_valid_range(region, len) {
+            return 0;
+        }
+
+        // SAFETY: the caller guarantees `region` is valid for reads and writes of `len` bytes
+        let region = unsafe { slice::from_raw_parts_mut(region, len) };
+
+        self.patch_matches(region, patch)
+    }
+
+    /// Apply `patch` to every occurrence of the pattern in `region`, returning the number of patched sites
+    ///
+    /// Matches are patched in ascending order. Every site is searched for in the region as
+    /// it is after the previous patches, so a match destroyed by patching an overlapping site
+    /// is skipped instead of being patched with stale data. Sites at which the patch would
+    /// extend past the end of the region are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::patch::Patch;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let mut region = [0x74, 0x05, 0xcc, 0x74, 0x10];
+    ///
+    /// // turn conditional short jumps into unconditional ones
+    /// let scanner = Scanner::new("74 ??");
+    /// assert_eq!(scanner.patch_matches(&mut region, &Patch::new(0, vec![0xeb])), 2);
+    ///
+    /// assert_eq!(region, [0xeb, 0x05, 0xcc, 0xeb, 0x10]);
+    /// ```
+    pub fn patch_matches(&self, region: &mut [u8], patch: &Patch) -> usize {
+        let mut patched = 0;
+
+        let mut start = 0;
+        while let Some(offset) = self.find_in_slice(None, region, start) {
+            start = offset + 1;
+
+            let Some(target) = offset.checked_add(patch.offset).and_then(|patch_start| {
+                region.get_mut(patch_start..)?.get_mut(..patch.bytes.len())
+            }) else {
+                continue;
+            };
+
+            patch.apply(target);
+            patched += 1;
+        }
+
+        patched
+    }
+
+    /// Find the first occurrence of the pattern in `data`, keeping the result writable
+    ///
+    /// The same as [`Scanner::find`] over `data`, the cursor applied, but as the address is
+    /// derived from the mutable reference [`ScanResult::as_mut_ptr`] provides a pointer to
+    /// write through, with no need to cast away the constness of [`ScanResult::get_addr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let mut data = [0xcc, 0x74, 0x05, 0x90];
+    ///
+    /// let result = Scanner::new("74 ^ ??").find_in_mut(None, &mut data);
+    /// // SAFETY: the pointer came from `data`, which isn't accessed otherwise in the meantime
+    /// unsafe { result.as_mut_ptr().unwrap().write(0x10) };
+    /// assert_eq!(data, [0xcc, 0x74, 0x10, 0x90]);
+    /// ```
+    pub fn find_in_mut(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        data: &mut [u8],
+    ) -> ScanResult {
+        let Some(offset) = self.find_in_slice(preferred_scan_mode, data, 0) else {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        };
+
+        // derived from the whole buffer, so offsets from the result may reach before it
+        ScanResult {
+            // SAFETY: the match is within `data`, and the cursor is at most one past its end
+            addr: unsafe { data.as_mut_ptr().add(offset + self.pattern.cursor) }.cast_const(),
+            writable: true,
+        }
+    }
+
+    /// Overwrite the bytes at the first occurrence of the pattern in `data` with `patch`
+    ///
+    /// Returns the offset of the patched match, which is also where writing starts.
+    /// If there's no match, or `patch` would extend past the end of `data` from the first
+    /// match, nothing is written and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let mut data = [0xcc, 0x74, 0x05, 0x90];
+    ///
+    /// let scanner = Scanner::new("74 ??");
+    /// assert_eq!(scanner.find_and_patch(&mut data, None, &[0xeb]), Some(1));
+    /// assert_eq!(data, [0xcc, 0xeb, 0x05, 0x90]);
+    ///
+    /// // the patch doesn't fit after the match
+    /// assert_eq!(Scanner::new("05 90").find_and_patch(&mut data, None, &[0x90; 3]), None);
+    /// assert_eq!(data, [0xcc, 0xeb, 0x05, 0x90]);
+    /// ```
+    pub fn find_and_patch(
+        &self,
+        data: &mut [u8],
+        preferred_scan_mode: Option<ScanMode>,
+        patch: &[u8],
+    ) -> Option<usize> {
+        let offset = self.find_in_slice(preferred_scan_mode, data, 0)?;
+
+        data.get_mut(offset..)?
+            .get_mut(..patch.len())?
+            .copy_from_slice(patch);
+
+        Some(offset)
+    }
+}
@@ -1,31 +1,150 @@
 //! IDA-style pattern
 
 use crate::aligned_bytes::AlignedBytes;
+use crate::backends;
+use elain::{Align, Alignment};
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+/// Named byte classes usable as ranges, e.g. `[digit]`, see [`crate::range_pattern`]
+const BYTE_CLASSES: [(&str, RangeInclusive<u8>); 4] = [
+    ("digit", 0x30..=0x39),
+    ("upper", 0x41..=0x5a),
+    ("lower", 0x61..=0x7a),
+    ("print", 0x20..=0x7e),
+];
 
 /// An IDA-style binary pattern
+///
+/// Patterns are immutable after construction and are both [`Send`] and [`Sync`].
 pub struct Pattern {
-    pub(crate) data: Box<AlignedBytes<32>>,
-    pub(crate) mask: Box<AlignedBytes<32>>,
+    pub(crate) data: Box<AlignedBytes<{ Pattern::ALIGNMENT }>>,
+    pub(crate) mask: Box<AlignedBytes<{ Pattern::ALIGNMENT }>>,
     pub(crate) unpadded_size: usize,
+    pub(crate) cursor: usize,
+    pub(crate) labels: Vec<(String, usize)>,
 }
 
 impl Pattern {
-    const ALIGNMENT: usize = 32;
+    /// Alignment of the pattern buffers
+    ///
+    /// The buffers are padded to a multiple of this size, which covers
+    /// the widest supported vector loads (64 bytes for AVX-512).
+    pub const ALIGNMENT: usize = 64;
 
     /// Create a new IDA-style [`Pattern`] instance
     ///
+    /// Besides hex bytes and `?`/`??` wildcards, `?{n}` can be used as a shorthand
+    /// for `n` consecutive wildcard bytes. A single `^` marks the byte whose address
+    /// [`Scanner::find`](crate::Scanner::find) reports instead of the match start,
+    /// see [`Pattern::cursor`]. Any number of named markers such as `@disp` can be placed
+    /// as well, see [`Pattern::labels`]. Bytes can be separated by any whitespace or written
+    /// as runs of hex digits without separators, which are read two digits at a time, so
+    /// `4889 5C24` is the same as `48 89 5C 24`. A `?` ends a run. `// line` and
+    /// `/* block */` comments are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is malformed, see [`Pattern::try_new`].
+    ///
     /// # Example
     ///
     /// ```
     /// use lightningscanner::pattern::Pattern;
     ///
     /// Pattern::new("48 89 5c 24 ?? 48 89 6c");
+    /// Pattern::new("48 8b ?{4} c3");
+    /// Pattern::new("e8 ?? ?? ?? ?? ^ 48 8b");
+    /// Pattern::new("48 8b 05 @disp ?? ?? ?? ?? @next c3");
+    /// Pattern::new("48 8B 05 ?? ?? ?? ?? // load g_foo");
     /// ```
     pub fn new(pattern: &str) -> Self {
+        match Self::try_new(pattern) {
+            Ok(pattern) => pattern,
+            Err(err) => panic!("invalid pattern: {}", err),
+        }
+    }
+
+    /// Create a new IDA-style [`Pattern`] instance, reporting malformed patterns as errors
+    ///
+    /// Patterns made of wildcards only match everywhere and are rejected as well,
+    /// the empty pattern is accepted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::{Pattern, PatternError};
+    ///
+    /// assert!(Pattern::try_new("48 8b ?{4} c3").is_ok());
+    /// assert_eq!(
+    ///     Pattern::try_new("48 8b ?{x} c3").err(),
+    ///     Some(PatternError::MalformedQuantifier { position: 6 })
+    /// );
+    /// assert_eq!(Pattern::try_new("?? ?? ??").err(), Some(PatternError::AllWildcards));
+    /// ```
+    pub fn try_new(pattern: &str) -> Result<Self, PatternError> {
+        Self::parse(pattern, None)
+    }
+
+    /// Create a new IDA-style [`Pattern`] instance storing `fill` as the data of its wildcards
+    ///
+    /// Wildcards are normally stored as `0x00` data bytes, which looks just like a concrete
+    /// zero byte in the pattern's buffers, see [`Pattern::raw_data`]. A sentinel like `0x2a`
+    /// sets them apart when inspecting the buffers, e.g. in a debugger. The mask of a
+    /// wildcard is still `0x00`, so the fill never takes part in matching or comparisons.
+    ///
+    /// Copies made with [`Clone`] and [`Pattern::pad_to`] keep the fill, patterns derived
+    /// otherwise store wildcards as `0x00` again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is malformed, see [`Pattern::try_new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new_with_wildcard_fill("48 00 ?? c3", 0x2a);
+    ///
+    /// assert_eq!(pattern.raw_data(), [0x48, 0x00, 0x2a, 0xc3]);
+    /// assert_eq!(pattern, Pattern::new("48 00 ?? c3"));
+    /// ```
+    pub fn new_with_wildcard_fill(pattern: &str, fill: u8) -> Self {
+        let mut pattern = Self::new(pattern);
+
+        let data = pattern
+            .data
+            .iter()
+            .zip(pattern.mask.iter())
+            .enumerate()
+            .map(|(offset, (&data, &mask))| match mask {
+                0x00 if offset < pattern.unpadded_size => fill,
+                _ => data,
+            })
+            .collect::<Vec<_>>();
+        pattern.data = AlignedBytes::new(&data);
+
+        pattern
+    }
+
+    /// Parse an IDA-style pattern, collecting `[lo-hi]` byte ranges into `ranges` if given
+    ///
+    /// Ranges are collected with the index of their byte and make it match the bits which
+    /// all values of the range share, without `ranges` they're rejected as invalid bytes.
+    pub(crate) fn parse(
+        pattern: &str,
+        mut ranges: Option<&mut Vec<(usize, RangeInclusive<u8>)>>,
+    ) -> Result<Self, PatternError> {
         let pattern = pattern.chars().collect::<Vec<_>>();
 
         let mut data = Vec::new();
         let mut mask = Vec::new();
+        let mut cursor = None;
+        let mut labels = Vec::<(String, usize)>::new();
 
         let mut i = 0;
         while i < pattern.len() {
@@ -35,8 +154,78 @@ impl Pattern {
             i += 1;
 
             match symbol {
-                ' ' => continue,
+                symbol if symbol.is_whitespace() => continue,
+                '/' if next_symbol == '/' => {
+                    while i < pattern.len() && pattern[i] != '\n' {
+                        i += 1;
+                    }
+
+                    continue;
+                }
+                '/' if next_symbol == '*' => {
+                    let position = i - 1;
+
+                    let end = pattern[i + 1..]
+                        .windows(2)
+                        .position(|symbols| symbols == ['*', '/'])
+                        .ok_or(PatternError::UnterminatedComment { position })?;
+                    i += 1 + end + 2;
+
+                    continue;
+                }
+                '^' => {
+                    if cursor.replace(data.len()).is_some() {
+                        return Err(PatternError::DuplicateCursor { position: i - 1 });
+                    }
+
+                    continue;
+                }
+                '@' => {
+                    let position = i - 1;
+
+                    let name_len = pattern[i..]
+                        .iter()
+                        .take_while(|symbol| symbol.is_ascii_alphanumeric() || **symbol == '_')
+                        .count();
+
+                    if name_len == 0 {
+                        return Err(PatternError::MalformedLabel { position });
+                    }
+
+                    let name = pattern[i..i + name_len].iter().collect::<String>();
+                    if labels.iter().any(|(label, _)| *label == name) {
+                        return Err(PatternError::DuplicateLabel { position });
+                    }
+
+                    labels.push((name, data.len()));
+                    i += name_len;
+
+                    continue;
+                }
+                '[' if ranges.is_some() => {
+                    let range = Self::parse_range(&pattern, &mut i)?;
+                    // the bits above the highest one differing between the bounds are shared
+                    let differing = range.start() ^ range.end();
+                    let byte_mask = !(u8::MAX.checked_shr(differing.leading_zeros()).unwrap_or(0));
+
+                    if let Some(ranges) = ranges.as_deref_mut() {
+                        ranges.push((data.len(), range.clone()));
+                    }
+                    data.push(range.start() & byte_mask);
+                    mask.push(byte_mask);
+
+                    continue;
+                }
                 '?' => {
+                    if next_symbol == '{' {
+                        let count = Self::parse_quantifier(&pattern, &mut i)?;
+
+                        data.resize(data.len() + count, 0x00);
+                        mask.resize(mask.len() + count, 0x00);
+
+                        continue;
+                    }
+
                     data.push(0x00);
                     mask.push(0x00);
 
@@ -47,6 +236,13 @@ impl Pattern {
                     continue;
                 }
                 _ => {
+                    if symbol.is_ascii_hexdigit() && Self::ends_token(next_symbol) {
+                        return Err(PatternError::OddNibbleCount { position: i - 1 });
+                    }
+                    if !symbol.is_ascii_hexdigit() || !next_symbol.is_ascii_hexdigit() {
+                        return Err(PatternError::InvalidByte { position: i - 1 });
+                    }
+
                     let byte = (Self::char_to_byte(symbol) << 4) | Self::char_to_byte(next_symbol);
 
                     data.push(byte);
@@ -55,29 +251,280 @@ impl Pattern {
                     i += 1;
                 }
             }
+        }
+
+        if Self::is_all_wildcards(&mask) {
+            return Err(PatternError::AllWildcards);
+        }
+
+        let mut pattern = Self::from_unpadded(data, mask).with_cursor(cursor.unwrap_or(0));
+        pattern.labels = labels;
+
+        Ok(pattern)
+    }
+
+    /// Create a new [`Pattern`] instance from a Cheat Engine style AOB string
+    ///
+    /// Bytes are written as two hex digits, `*`, `?` and `??` are wildcard bytes. Bytes may be
+    /// separated by whitespace or written without separators, in which case they're taken
+    /// two characters at a time and a `*` still stands for a single byte. Like with
+    /// [`Pattern::try_new`], AOBs made of wildcards only are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::{Pattern, PatternError};
+    ///
+    /// let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? c3");
+    ///
+    /// assert_eq!(Pattern::from_ce_aob("488B05????????C3"), Ok(pattern.clone()));
+    /// assert_eq!(Pattern::from_ce_aob("48 8B 05 * * * * C3"), Ok(pattern));
+    /// assert_eq!(
+    ///     Pattern::from_ce_aob("488B0"),
+    ///     Err(PatternError::OddNibbleCount { position: 4 })
+    /// );
+    /// ```
+    pub fn from_ce_aob(aob: &str) -> Result<Self, PatternError> {
+        let aob = aob.chars().collect::<Vec<_>>();
+
+        let mut data = Vec::new();
+        let mut mask = Vec::new();
+
+        let mut i = 0;
+        while i < aob.len() {
+            let symbol = aob[i];
+            let next_symbol = aob.get(i + 1).copied().unwrap_or(' ');
 
-            if symbol == ' ' {
-                continue;
+            match (symbol, next_symbol) {
+                (symbol, _) if symbol.is_whitespace() => {
+                    i += 1;
+                    continue;
+                }
+                ('*', _) => i += 1,
+                ('?', '?') => i += 2,
+                // a lone `?` has to be separated on both sides, otherwise it's half a byte
+                ('?', next_symbol)
+                    if next_symbol.is_whitespace()
+                        && aob[..i].last().is_none_or(|symbol| symbol.is_whitespace()) =>
+                {
+                    i += 1
+                }
+                (high, low) if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                    data.push((Self::char_to_byte(high) << 4) | Self::char_to_byte(low));
+                    mask.push(0xff);
+
+                    i += 2;
+                    continue;
+                }
+                (symbol, next_symbol)
+                    if symbol.is_ascii_hexdigit() && Self::ends_token(next_symbol) =>
+                {
+                    return Err(PatternError::OddNibbleCount { position: i })
+                }
+                _ => return Err(PatternError::InvalidByte { position: i }),
             }
+
+            data.push(0x00);
+            mask.push(0x00);
         }
 
-        let unpadded_size = data.len();
+        if Self::is_all_wildcards(&mask) {
+            return Err(PatternError::AllWildcards);
+        }
 
-        let count = f32::ceil(unpadded_size as f32 / Self::ALIGNMENT as f32) as usize;
-        let padding_size = count * Self::ALIGNMENT - unpadded_size;
+        Ok(Self::from_unpadded(data, mask))
+    }
 
-        data.resize(unpadded_size + padding_size, 0);
-        mask.resize(unpadded_size + padding_size, 0);
+    /// Create a new [`Pattern`] instance from a hex dump pasted from IDA
+    ///
+    /// Every line may start with an address column, a word containing a colon such as
+    /// `.text:0000000140001000`, which is skipped. The bytes of the line are then taken from
+    /// the words made of two hex digits, `??` and `?` being wildcard bytes, and continue
+    /// until the first word that isn't one, so trailing ASCII or disassembly columns are
+    /// dropped. An ASCII column which only consists of such words is recognized by
+    /// rendering the line's bytes, with their printable characters in place.
+    /// Like with [`Pattern::try_new`], dumps made of wildcards only are rejected, and so
+    /// are dumps without a single byte, such as text pasted from the wrong window.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let dump = "\
+    /// .text:0000000140001000 48 89 5C 24 08 57 48 83  EC 20 48 8B F9 E8 12 34  H.\\$.WH.. H....4
+    /// .text:0000000140001010 56 78 48 85 C0                                   VxH..";
+    ///
+    /// assert_eq!(
+    ///     Pattern::from_ida_dump(dump),
+    ///     Ok(Pattern::new("48 89 5c 24 08 57 48 83 ec 20 48 8b f9 e8 12 34 56 78 48 85 c0"))
+    /// );
+    /// ```
+    pub fn from_ida_dump(text: &str) -> Result<Self, PatternError> {
+        let mut data = Vec::new();
+        let mut mask = Vec::new();
 
-        Pattern {
-            data: AlignedBytes::new(&data),
-            mask: AlignedBytes::new(&mask),
-            unpadded_size,
+        for line in text.lines() {
+            let mut rest = line.trim_start();
+            if let Some((address, bytes)) = rest.split_once(char::is_whitespace) {
+                if address.contains(':') {
+                    rest = bytes;
+                }
+            }
+
+            let line_start = data.len();
+            loop {
+                rest = rest.trim_start();
+                if data.len() > line_start && Self::is_ascii_column(rest, &data[line_start..]) {
+                    break;
+                }
+
+                let (word, after) =
+                    rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+                match word.as_bytes() {
+                    [b'?'] | [b'?', b'?'] => {
+                        data.push(0x00);
+                        mask.push(0x00);
+                    }
+                    &[high, low] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                        data.push(
+                            (Self::char_to_byte(high as char) << 4)
+                                | Self::char_to_byte(low as char),
+                        );
+                        mask.push(0xff);
+                    }
+                    _ => break,
+                }
+
+                rest = after;
+            }
+        }
+
+        if mask.is_empty() {
+            return Err(PatternError::NoBytes);
+        }
+        if Self::is_all_wildcards(&mask) {
+            return Err(PatternError::AllWildcards);
+        }
+
+        Ok(Self::from_unpadded(data, mask))
+    }
+
+    /// Check if `column` is the ASCII rendering of `bytes` in a hex dump
+    ///
+    /// Printable bytes have to be shown as themselves, any character may stand for the
+    /// others, and trailing spaces may have been trimmed.
+    fn is_ascii_column(column: &str, bytes: &[u8]) -> bool {
+        let column = column.trim_end().chars().collect::<Vec<_>>();
+
+        column.len() <= bytes.len()
+            && bytes
+                .iter()
+                .enumerate()
+                .all(|(i, &byte)| match column.get(i) {
+                    Some(&symbol) => !(0x20..=0x7e).contains(&byte) || symbol == byte as char,
+                    None => byte == b' ',
+                })
+    }
+
+    /// Lowest [`Pattern::mask_density`] accepted by [`Pattern::try_new_strict`]
+    pub const MIN_STRICT_DENSITY: f64 = 0.25;
+
+    /// Create a new IDA-style [`Pattern`] instance, rejecting patterns that are mostly wildcards
+    ///
+    /// Patterns with a [`Pattern::mask_density`] below [`Pattern::MIN_STRICT_DENSITY`]
+    /// match almost anywhere and are reported as [`PatternError::TooManyWildcards`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::{Pattern, PatternError};
+    ///
+    /// assert!(Pattern::try_new_strict("48 8b ?? ?? c3").is_ok());
+    /// assert_eq!(
+    ///     Pattern::try_new_strict("48 ?{7}").err(),
+    ///     Some(PatternError::TooManyWildcards { concrete: 1, len: 8 })
+    /// );
+    /// ```
+    pub fn try_new_strict(pattern: &str) -> Result<Self, PatternError> {
+        let pattern = Self::try_new(pattern)?;
+
+        if pattern.mask_density() < Self::MIN_STRICT_DENSITY {
+            return Err(PatternError::TooManyWildcards {
+                concrete: pattern.concrete_bytes(),
+                len: pattern.unpadded_size,
+            });
+        }
+
+        Ok(pattern)
+    }
+
+    /// Parse the `{n}` part of a `?{n}` wildcard quantifier, `i` pointing at the opening brace
+    fn parse_quantifier(pattern: &[char], i: &mut usize) -> Result<usize, PatternError> {
+        let err = PatternError::MalformedQuantifier { position: *i - 1 };
+
+        let digits_start = *i + 1;
+        let digits_len = pattern[digits_start..]
+            .iter()
+            .take_while(|symbol| symbol.is_ascii_digit())
+            .count();
+        let digits_end = digits_start + digits_len;
+
+        if digits_len == 0 || pattern.get(digits_end) != Some(&'}') {
+            return Err(err);
+        }
+
+        let count = pattern[digits_start..digits_end]
+            .iter()
+            .try_fold(0usize, |count, symbol| {
+                count
+                    .checked_mul(10)?
+                    .checked_add(symbol.to_digit(10)? as usize)
+            })
+            .filter(|&count| count != 0)
+            .ok_or(err)?;
+
+        *i = digits_end + 1;
+        Ok(count)
+    }
+
+    /// Parse the `lo-hi]` part of a `[lo-hi]` byte range, `i` pointing past the opening bracket
+    ///
+    /// The name of a byte class, as in `[digit]`, is accepted in place of `lo-hi`.
+    fn parse_range(pattern: &[char], i: &mut usize) -> Result<RangeInclusive<u8>, PatternError> {
+        let err = PatternError::MalformedRange { position: *i - 1 };
+
+        for (name, range) in BYTE_CLASSES {
+            let rest = &pattern[*i..];
+            if rest.len() > name.len()
+                && rest.iter().copied().take(name.len()).eq(name.chars())
+                && rest[name.len()] == ']'
+            {
+                *i += name.len() + 1;
+                return Ok(range);
+            }
+        }
+
+        let symbols = pattern.get(*i..*i + 6).ok_or(err)?;
+        let byte = |high: char, low: char| {
+            (high.is_ascii_hexdigit() && low.is_ascii_hexdigit())
+                .then(|| (Self::char_to_byte(high) << 4) | Self::char_to_byte(low))
+        };
+
+        let lo = byte(symbols[0], symbols[1]).ok_or(err)?;
+        let hi = byte(symbols[3], symbols[4]).ok_or(err)?;
+        if symbols[2] != '-' || symbols[5] != ']' || lo > hi {
+            return Err(err);
         }
+
+        *i += 6;
+        Ok(lo..=hi)
     }
 
     /// Create a new [`Pattern`] instance based upon a string literal.
     ///
+    /// Every character becomes concrete bytes, see [`Pattern::from_ascii_str`].
+    ///
     /// # Example
     ///
     /// ```
@@ -86,14 +533,98 @@ impl Pattern {
     /// Pattern::new_string("LocalPlayer");
     /// ```
     pub fn new_string(string: &str) -> Self {
+        Self::from_ascii_str(string)
+    }
+
+    /// Create a new [`Pattern`] matching the bytes of a narrow string
+    ///
+    /// Unlike with IDA-style patterns, `?` is a character like any other and becomes a
+    /// concrete byte, there are no wildcards. Characters outside of ASCII are encoded as UTF-8.
+    /// No terminator is added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::from_ascii_str("a?b"), Pattern::new("61 3f 62"));
+    /// ```
+    pub fn from_ascii_str(string: &str) -> Self {
         let bytes = string.as_bytes();
 
-        let mut data = bytes.to_vec();
-        let mut mask = vec![0xff; bytes.len()];
+        Self::from_unpadded(bytes.to_vec(), vec![0xff; bytes.len()])
+    }
+
+    /// Create a new [`Pattern`] matching a wide string, encoded as little endian UTF-16
+    ///
+    /// As with [`Pattern::from_ascii_str`], every character including `?` becomes concrete
+    /// bytes and no terminator is added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::from_utf16_str("a?"), Pattern::new("61 00 3f 00"));
+    /// ```
+    pub fn from_utf16_str(string: &str) -> Self {
+        let bytes = string
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let mask = vec![0xff; bytes.len()];
+
+        Self::from_unpadded(bytes, mask)
+    }
+
+    /// Create a new [`Pattern`] instance from bytes and a mask byte for each of them
+    ///
+    /// Every bit set in a mask byte has to match the corresponding bit of the data byte,
+    /// cleared bits match anything. A mask byte of `0xff` makes its byte concrete, `0x00`
+    /// makes it a wildcard, and anything in between only checks some of its bits, e.g.
+    /// `0xf8` ignores the low three bits. Data bits the mask ignores don't matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` and `mask` differ in length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    /// use lightningscanner::Scanner;
+    ///
+    /// // push of any of rax..rdi, then a call
+    /// let pattern = Pattern::from_masked_bytes(&[0x50, 0xe8], &[0xf8, 0xff]);
+    /// assert_eq!(pattern, Pattern::from_masked_bytes(&[0x57, 0xe8], &[0xf8, 0xff]));
+    ///
+    /// let binary = [0x4f, 0xe8, 0x53, 0xe8];
+    /// assert_eq!(Scanner::from(pattern).find_all_offsets(None, &binary), [2]);
+    /// ```
+    pub fn from_masked_bytes(data: &[u8], mask: &[u8]) -> Self {
+        assert_eq!(
+            data.len(),
+            mask.len(),
+            "data and mask of a pattern must have the same length"
+        );
+
+        Self::from_unpadded(data.to_vec(), mask.to_vec())
+    }
+
+    /// Create a new [`Pattern`] from unpadded data and mask bytes of the same length
+    ///
+    /// Data bits the mask ignores are cleared, only [`Pattern::new_with_wildcard_fill`] sets
+    /// some of them.
+    pub(crate) fn from_unpadded(mut data: Vec<u8>, mut mask: Vec<u8>) -> Self {
+        debug_assert_eq!(data.len(), mask.len());
+
+        for (data, &mask) in data.iter_mut().zip(&mask) {
+            *data &= mask;
+        }
 
         let unpadded_size = data.len();
 
-        let count = f32::ceil(unpadded_size as f32 / Self::ALIGNMENT as f32) as usize;
+        let count = unpadded_size.div_ceil(Self::ALIGNMENT);
         let padding_size = count * Self::ALIGNMENT - unpadded_size;
 
         data.resize(unpadded_size + padding_size, 0);
@@ -103,9 +634,84 @@ impl Pattern {
             data: AlignedBytes::new(&data),
             mask: AlignedBytes::new(&mask),
             unpadded_size,
+            cursor: 0,
+            labels: Vec::new(),
         }
     }
 
+    /// Offset of the byte marked with `^` from the start of the pattern, 0 if there's no marker
+    ///
+    /// Every scan returning a [`ScanResult`](crate::ScanResult), such as
+    /// [`Scanner::find`](crate::Scanner::find) and [`Scanner::find_all`](crate::Scanner::find_all),
+    /// reports the address of this byte instead of the match start, and so does
+    /// [`Signature::find_in`](crate::sigfile::Signature::find_in). The other offset based scans
+    /// keep reporting match starts. A marker at the very end of the pattern points right past
+    /// the matched bytes, which is one past the end of the binary for a match at its end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::new("e8 ?? ?? ?? ?? ^ 48 8b").cursor(), 5);
+    /// assert_eq!(Pattern::new("e8 ?? ?? ?? ??").cursor(), 0);
+    /// ```
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Set the offset of the byte reported instead of the match start, see [`Pattern::cursor`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cursor` is larger than the size of the pattern.
+    pub fn with_cursor(mut self, cursor: usize) -> Self {
+        assert!(
+            cursor <= self.unpadded_size,
+            "cursor {} is outside of a pattern of {} bytes",
+            cursor,
+            self.unpadded_size
+        );
+
+        self.cursor = cursor;
+        self
+    }
+
+    /// Named markers of the pattern and their offsets from its start, in pattern order
+    ///
+    /// A marker written as `@name` labels the byte following it, so with
+    /// [`Scanner::find_labeled`](crate::Scanner::find_labeled) the address of every
+    /// interesting byte in a match can be looked up by name. Names consist of ASCII
+    /// letters, digits and underscores.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 8b 05 @disp ?? ?? ?? ?? @next c3");
+    ///
+    /// assert_eq!(
+    ///     pattern.labels().collect::<Vec<_>>(),
+    ///     [("disp", 3), ("next", 7)]
+    /// );
+    /// ```
+    pub fn labels(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.labels
+            .iter()
+            .map(|(label, offset)| (label.as_str(), *offset))
+    }
+
+    /// Check if a non-empty pattern has a wildcard for every byte
+    fn is_all_wildcards(mask: &[u8]) -> bool {
+        !mask.is_empty() && mask.iter().all(|&mask| mask == 0x00)
+    }
+
+    /// Check if `symbol` ends a byte token, `'\0'` standing for the end of the pattern
+    fn ends_token(symbol: char) -> bool {
+        symbol == '\0' || symbol.is_whitespace()
+    }
+
     const fn char_to_byte(c: char) -> u8 {
         if c >= 'a' && c <= 'z' {
             c as u8 - b'a' + 0xA
@@ -119,25 +725,930 @@ impl Pattern {
     }
 }
 
-impl From<&str> for Pattern {
-    fn from(value: &str) -> Self {
-        Pattern::new(value)
+impl Pattern {
+    /// Check how many times the pattern occurs in a reference binary
+    ///
+    /// Overlapping occurrences are counted separately. A signature that is safe to ship
+    /// is expected to produce a [`ValidationResult`] with a `count` of exactly one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let reference = [0x48, 0x89, 0x5c, 0x24, 0x10, 0x48, 0x89, 0x6c];
+    ///
+    /// let result = Pattern::new("48 89 5c").validate_against(&reference);
+    /// assert_eq!(result.count, 1);
+    /// assert_eq!(result.first, Some(0));
+    /// ```
+    pub fn validate_against(&self, reference: &[u8]) -> ValidationResult {
+        let mut result = ValidationResult {
+            count: 0,
+            first: None,
+        };
+
+        let mut start = 0;
+        while let Some(offset) = backends::find_in_slice(self, None, reference, start) {
+            result.first.get_or_insert(offset);
+            result.count += 1;
+            start = offset + 1;
+        }
+
+        result
     }
 }
 
-impl Pattern {
-    /// Create a new [`Pattern`] from its raw parts.
+/// Result of validating a [`Pattern`] against a reference binary
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationResult {
+    /// Number of occurrences of the pattern in the reference binary
+    pub count: usize,
+    /// Offset of the first occurrence, if any
+    pub first: Option<usize>,
+}
+
+impl ValidationResult {
+    /// Check if the pattern occurs exactly once
+    pub fn is_unique(&self) -> bool {
+        self.count == 1
+    }
+}
+
+impl Pattern {
+    /// Create a new [`Pattern`] with the byte order of this one reversed
+    ///
+    /// Useful for scanning byte-swapped data, such as reversed memory images.
+    /// The reversed pattern has no cursor or labels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::new("48 ?? c3").reversed(), Pattern::new("c3 ?? 48"));
+    /// ```
+    pub fn reversed(&self) -> Pattern {
+        let mut data = self.data[..self.unpadded_size].to_vec();
+        let mut mask = self.mask[..self.unpadded_size].to_vec();
+
+        data.reverse();
+        mask.reverse();
+
+        Self::from_unpadded(data, mask)
+    }
+
+    /// Create a copy of this pattern with the bytes at `indices` turned into wildcards
+    ///
+    /// The cursor and labels are kept. Useful for wildcarding bytes which turned out to
+    /// vary, such as the offsets reported by [`Pattern::diff_at`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is outside of the pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 8b 05 10 20 30 40");
+    ///
+    /// assert_eq!(pattern.wildcard_at(&[4, 3]), Pattern::new("48 8b 05 ?? ?? 30 40"));
+    /// ```
+    pub fn wildcard_at(&self, indices: &[usize]) -> Pattern {
+        let mut mask = self.mask[..self.unpadded_size].to_vec();
+
+        for &index in indices {
+            assert!(
+                index < self.unpadded_size,
+                "index {} is outside of a pattern of {} bytes",
+                index,
+                self.unpadded_size
+            );
+
+            mask[index] = 0x00;
+        }
+
+        let mut pattern = Self::from_unpadded(self.data[..self.unpadded_size].to_vec(), mask)
+            .with_cursor(self.cursor);
+        pattern.labels = self.labels.clone();
+        pattern
+    }
+
+    /// Create a new [`Pattern`] matching this one, `gap` arbitrary bytes and then `other`
+    ///
+    /// The cursor of this pattern is kept, or taken from `other` if this one's is at its
+    /// start. Labels of both patterns are kept where they were placed, a label of `other`
+    /// is dropped if this pattern already has one of the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the joined pattern overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let call = Pattern::new("e8");
+    /// let test = Pattern::new("84 c0 74");
+    ///
+    /// assert_eq!(call.join_with_gap(&test, 4), Pattern::new("e8 ?? ?? ?? ?? 84 c0 74"));
+    /// ```
+    pub fn join_with_gap(&self, other: &Pattern, gap: usize) -> Pattern {
+        let other_start = self
+            .unpadded_size
+            .checked_add(gap)
+            .expect("joined pattern length overflows");
+
+        let mut data = self.data[..self.unpadded_size].to_vec();
+        let mut mask = self.mask[..self.unpadded_size].to_vec();
+        data.resize(other_start, 0);
+        mask.resize(other_start, 0);
+        data.extend_from_slice(&other.data[..other.unpadded_size]);
+        mask.extend_from_slice(&other.mask[..other.unpadded_size]);
+
+        let cursor = match (self.cursor, other.cursor) {
+            (0, 0) => 0,
+            (0, cursor) => other_start + cursor,
+            (cursor, _) => cursor,
+        };
+
+        let mut pattern = Self::from_unpadded(data, mask).with_cursor(cursor);
+        pattern.labels = self.labels.clone();
+        for (label, offset) in &other.labels {
+            if !self.labels.iter().any(|(existing, _)| existing == label) {
+                pattern.labels.push((label.clone(), other_start + offset));
+            }
+        }
+
+        pattern
+    }
+
+    /// Create a new [`Pattern`] matching this one `count` times in a row
+    ///
+    /// The cursor and labels are kept where they are in the first copy. Repeating a pattern
+    /// zero times results in an empty pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the repeated pattern overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// // a table of 3 entries pointing into the same page
+    /// let entry = Pattern::new("?? ?? 40 00");
+    ///
+    /// assert_eq!(entry.repeat(3), Pattern::new("?? ?? 40 00 ?? ?? 40 00 ?? ?? 40 00"));
+    /// ```
+    pub fn repeat(&self, count: usize) -> Pattern {
+        self.unpadded_size
+            .checked_mul(count)
+            .expect("repeated pattern length overflows");
+
+        let data = self.data[..self.unpadded_size].repeat(count);
+        let mask = self.mask[..self.unpadded_size].repeat(count);
+
+        if count == 0 {
+            return Self::from_unpadded(data, mask);
+        }
+
+        let mut pattern = Self::from_unpadded(data, mask).with_cursor(self.cursor);
+        pattern.labels = self.labels.clone();
+        pattern
+    }
+}
+
+impl Pattern {
+    /// Length of the pattern in bytes, without padding
+    pub fn len(&self) -> usize {
+        self.unpadded_size
+    }
+
+    /// Check if the pattern has no bytes
+    pub fn is_empty(&self) -> bool {
+        self.unpadded_size == 0
+    }
+
+    /// Data bytes of the pattern as stored, without padding
+    ///
+    /// Only the bits set in the corresponding [mask](Pattern::raw_mask) byte take part in
+    /// matching. The others are zero, unless the pattern was created with
+    /// [`Pattern::new_with_wildcard_fill`].
+    pub fn raw_data(&self) -> &[u8] {
+        &self.data[..self.unpadded_size]
+    }
+
+    /// Mask bytes of the pattern as stored, without padding
+    pub fn raw_mask(&self) -> &[u8] {
+        &self.mask[..self.unpadded_size]
+    }
+
+    /// Data bytes with the bits the mask ignores cleared, without padding
+    pub(crate) fn masked_data(&self) -> impl Iterator<Item = u8> + '_ {
+        self.raw_data()
+            .iter()
+            .zip(self.raw_mask())
+            .map(|(&data, &mask)| data & mask)
+    }
+
+    /// Length of the pattern's buffers in bytes, including padding
+    ///
+    /// This is a multiple of [`Pattern::ALIGNMENT`], or of the alignment passed to
+    /// [`Pattern::pad_to`].
+    pub fn padded_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Create a copy of this pattern with its buffers padded to a multiple of `align` bytes
+    ///
+    /// Backends read the buffers in whole vectors, so the padding never shrinks below
+    /// [`Pattern::ALIGNMENT`], which is also the alignment of the buffers in memory.
+    /// Smaller alignments are already satisfied and only copy the pattern. The padding
+    /// is masked out, so the copy matches exactly what this pattern matches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` isn't a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 8b ?? c3").pad_to(128);
+    ///
+    /// assert_eq!(pattern.len(), 4);
+    /// assert_eq!(pattern.padded_len(), 128);
+    /// ```
+    pub fn pad_to(&self, align: usize) -> Pattern {
+        assert!(
+            align.is_power_of_two(),
+            "alignment of {} bytes is not a power of two",
+            align
+        );
+
+        let align = align.max(Self::ALIGNMENT);
+        // empty patterns still get a whole unit of padding
+        let padded_len = self
+            .unpadded_size
+            .checked_next_multiple_of(align)
+            .expect("padded pattern length overflows")
+            .max(align);
+
+        let mut data = self.data[..self.unpadded_size].to_vec();
+        let mut mask = self.mask[..self.unpadded_size].to_vec();
+        data.resize(padded_len, 0);
+        mask.resize(padded_len, 0);
+
+        Pattern {
+            data: AlignedBytes::new(&data),
+            mask: AlignedBytes::new(&mask),
+            unpadded_size: self.unpadded_size,
+            cursor: self.cursor,
+            labels: self.labels.clone(),
+        }
+    }
+
+    /// Create a new [`Pattern`] from the bytes of this one within `range`
+    ///
+    /// The range is in unpadded byte positions. The cursor and labels within the range
+    /// are carried over relative to its start, a cursor outside of it is reset to 0 and
+    /// labels outside of it are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 8b ?? ?? c3");
+    ///
+    /// assert_eq!(pattern.slice(1..4), Ok(Pattern::new("8b ?? ??")));
+    /// assert!(pattern.slice(3..6).is_err());
+    /// ```
+    pub fn slice(&self, range: Range<usize>) -> Result<Pattern, SliceError> {
+        let Range { start, end } = range;
+
+        if start > end || end > self.unpadded_size {
+            return Err(SliceError {
+                start,
+                end,
+                len: self.unpadded_size,
+            });
+        }
+
+        let contains = |offset: usize| (start..=end).contains(&offset);
+
+        let cursor = Some(self.cursor)
+            .filter(|&cursor| contains(cursor))
+            .map_or(0, |cursor| cursor - start);
+
+        let mut pattern = Self::from_unpadded(
+            self.data[start..end].to_vec(),
+            self.mask[start..end].to_vec(),
+        )
+        .with_cursor(cursor);
+
+        pattern.labels = self
+            .labels
+            .iter()
+            .filter(|(_, offset)| contains(*offset))
+            .map(|(label, offset)| (label.clone(), offset - start))
+            .collect();
+
+        Ok(pattern)
+    }
+}
+
+/// Error slicing a [`Pattern`] with a range outside of it, see [`Pattern::slice`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SliceError {
+    /// Start of the requested range
+    pub start: usize,
+    /// End of the requested range
+    pub end: usize,
+    /// Length of the pattern in bytes
+    pub len: usize,
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "range {}..{} is outside of a pattern of {} bytes",
+            self.start, self.end, self.len
+        )
+    }
+}
+
+impl std::error::Error for SliceError {}
+
+impl Pattern {
+    /// Fingerprint of the concrete bytes of the pattern and their positions
+    ///
+    /// Wildcards, partially masked bytes and padding don't contribute, so patterns requiring
+    /// the same concrete bytes at the same positions fingerprint identically no matter how
+    /// their wildcards are written. The fingerprint is stable across runs and platforms,
+    /// which makes it suitable for caching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let a = Pattern::new("48 8b ?? ?? c3");
+    /// let b = Pattern::new("48 8b ?{2} c3 ??");
+    ///
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// assert_ne!(a.fingerprint(), Pattern::new("48 8b ?? c3").fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        // 64-bit FNV-1a
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        let data = &self.data[..self.unpadded_size];
+        let mask = &self.mask[..self.unpadded_size];
+
+        data.iter()
+            .zip(mask)
+            .enumerate()
+            .filter(|&(_, (_, &mask))| mask == 0xff)
+            .flat_map(|(offset, (&byte, _))| {
+                (offset as u64).to_le_bytes().into_iter().chain([byte])
+            })
+            .fold(OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(PRIME)
+            })
+    }
+
+    /// Remove every pattern equal to an earlier one from `patterns`, keeping their order
+    ///
+    /// Patterns are compared with [`PartialEq`], so patterns written differently but parsing
+    /// to the same bytes, cursor and labels are duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let mut patterns = vec![
+    ///     Pattern::new("48 8B ?? ?? C3"),
+    ///     Pattern::new("488b ?{2} c3"),
+    ///     Pattern::new("48 8b ?? c3"),
+    /// ];
+    /// Pattern::dedup(&mut patterns);
+    ///
+    /// assert_eq!(patterns, [Pattern::new("48 8b ?? ?? c3"), Pattern::new("48 8b ?? c3")]);
+    /// ```
+    pub fn dedup(patterns: &mut Vec<Pattern>) {
+        let keep = Self::first_occurrences(patterns, |pattern| pattern);
+        Self::retain_flagged(patterns, keep);
+    }
+
+    /// Remove every pattern requiring the same concrete bytes as an earlier one from `patterns`
+    ///
+    /// Patterns are compared by their concrete bytes and positions, which is what
+    /// [`Pattern::fingerprint`] covers. With `distinct_wildcards`, wildcards and partially
+    /// masked bytes have to agree as well, so patterns only differing in trailing wildcards
+    /// are kept apart. Cursors and labels are ignored either way. The order is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let patterns = vec![Pattern::new("48 8b ?? c3"), Pattern::new("48 8b ?? c3 ??")];
+    ///
+    /// let mut merged = patterns.clone();
+    /// Pattern::dedup_by_fingerprint(&mut merged, false);
+    /// assert_eq!(merged.len(), 1);
+    ///
+    /// let mut kept = patterns.clone();
+    /// Pattern::dedup_by_fingerprint(&mut kept, true);
+    /// assert_eq!(kept.len(), 2);
+    /// ```
+    pub fn dedup_by_fingerprint(patterns: &mut Vec<Pattern>, distinct_wildcards: bool) {
+        let keep = if distinct_wildcards {
+            Self::first_occurrences(patterns, |pattern| {
+                let size = pattern.unpadded_size;
+                (
+                    pattern.masked_data().collect::<Vec<_>>(),
+                    &pattern.mask[..size],
+                )
+            })
+        } else {
+            Self::first_occurrences(patterns, |pattern| {
+                let size = pattern.unpadded_size;
+                pattern.data[..size]
+                    .iter()
+                    .zip(&pattern.mask[..size])
+                    .enumerate()
+                    .filter(|&(_, (_, &mask))| mask == 0xff)
+                    .map(|(offset, (&byte, _))| (offset, byte))
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        Self::retain_flagged(patterns, keep);
+    }
+
+    /// Flag every pattern whose key differs from those of all earlier ones
+    fn first_occurrences<'a, K: Hash + Eq>(
+        patterns: &'a [Pattern],
+        key: impl Fn(&'a Pattern) -> K,
+    ) -> Vec<bool> {
+        let mut seen = HashSet::with_capacity(patterns.len());
+
+        patterns
+            .iter()
+            .map(|pattern| seen.insert(key(pattern)))
+            .collect()
+    }
+
+    /// Keep the patterns flagged in `keep`
+    fn retain_flagged(patterns: &mut Vec<Pattern>, keep: Vec<bool>) {
+        let mut keep = keep.into_iter();
+        patterns.retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+impl Pattern {
+    /// Create an equivalent pattern without trailing wildcard bytes, see [`Pattern::normalize_with`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::new("48 8B ?? ??").normalize(), Pattern::new("48 8b"));
+    /// ```
+    pub fn normalize(&self) -> Pattern {
+        self.normalize_with(true)
+    }
+
+    /// Create a pattern in normal form, trimming trailing wildcard bytes if `trim_trailing_wildcards` is set
+    ///
+    /// Bits of the data which the mask ignores are cleared and the padding chosen with
+    /// [`Pattern::pad_to`] is dropped, so patterns matching the same bytes compare equal
+    /// and have the same [`Pattern::canonical_string`] once normalized.
+    ///
+    /// Trimming keeps every byte up to the [cursor](Pattern::cursor) and the last
+    /// [label](Pattern::labels). Otherwise it only changes matching at the end of a haystack:
+    /// a pattern with `n` trailing wildcard bytes can't match within the last `n` bytes of
+    /// a haystack, as its wildcards would extend past the end, while the trimmed pattern
+    /// matches there wherever its remaining bytes do. Every match of the full pattern is
+    /// still a match of the trimmed one. Callers relying on the full length, for example to
+    /// read the bytes under the wildcards after a match, should not trim.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let pattern = Pattern::new("48 8b ?? ??");
+    /// let binary = [0x48, 0x8b, 0x05, 0x90, 0x48, 0x8b, 0x05];
+    ///
+    /// let full = Scanner::from(pattern.normalize_with(false));
+    /// let trimmed = Scanner::from(pattern.normalize_with(true));
+    ///
+    /// // the second occurrence leaves no room for both wildcards
+    /// assert_eq!(full.find_all_offsets(None, &binary), [0]);
+    /// assert_eq!(trimmed.find_all_offsets(None, &binary), [0, 4]);
+    /// ```
+    pub fn normalize_with(&self, trim_trailing_wildcards: bool) -> Pattern {
+        let mask = &self.mask[..self.unpadded_size];
+
+        let mut len = self.unpadded_size;
+        if trim_trailing_wildcards {
+            let referenced = self
+                .labels
+                .iter()
+                .map(|&(_, offset)| offset)
+                .fold(self.cursor, usize::max);
+            let concrete = mask
+                .iter()
+                .rposition(|&mask| mask != 0x00)
+                .map_or(0, |last| last + 1);
+
+            len = concrete.max(referenced).min(len);
+        }
+
+        let data = self.data[..len]
+            .iter()
+            .zip(&mask[..len])
+            .map(|(&data, &mask)| data & mask)
+            .collect();
+
+        let mut pattern = Self::from_unpadded(data, mask[..len].to_vec()).with_cursor(self.cursor);
+        pattern.labels = self.labels.clone();
+        pattern
+    }
+
+    /// IDA-style string of the pattern, the same for all patterns which compare equal
+    ///
+    /// Bytes are written as lowercase hex separated by single spaces, wildcards as `??`
+    /// and the cursor and labels where they were placed. The string parses back into an
+    /// equal pattern with [`Pattern::new`] as long as every byte is either concrete or a
+    /// wildcard, which holds for every pattern parsed from a string.
+    ///
+    /// Partially masked bytes, e.g. from [`Pattern::from_masked_bytes`], don't round-trip.
+    /// Bytes matching a range of values are written as `[lo-hi]`, which only
+    /// [`RangePattern`](crate::range_pattern::RangePattern) accepts, bytes only masking their
+    /// low nibble as `?x`, which no parser accepts, and other partially masked bytes as `??`,
+    /// which parses as a full wildcard.
+    ///
+    /// The pattern isn't normalized first, `pattern.normalize().canonical_string()` gives
+    /// the same string for patterns which only differ in their trailing wildcards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48  8B 05 ?{2} ^ @disp ?  c3 // comment");
+    ///
+    /// assert_eq!(pattern.canonical_string(), "48 8b 05 ?? ?? ^ @disp ?? c3");
+    /// assert_eq!(pattern.to_string(), pattern.canonical_string());
+    /// ```
+    pub fn canonical_string(&self) -> String {
+        self.tokens(self.unpadded_size).join(" ")
+    }
+
+    /// Rewrite an IDA-style pattern in a canonical form, the same for every spelling of it
+    ///
+    /// Bytes are written as uppercase hex separated by single spaces, wildcards and their
+    /// `?{n}` quantifiers as `??` for every byte, and the cursor and labels where they were
+    /// placed, comments are dropped. Apart from its hex digits being uppercase, this is the
+    /// [`Pattern::canonical_string`] of the parsed pattern, so it parses back into an equal
+    /// pattern and can key caches of signatures written in different styles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::{Pattern, PatternError};
+    ///
+    /// assert_eq!(Pattern::canonicalize("4889 ? 5c"), Ok("48 89 ?? 5C".to_owned()));
+    /// assert_eq!(Pattern::canonicalize("48  89 ?{1} 5C"), Ok("48 89 ?? 5C".to_owned()));
+    /// assert_eq!(
+    ///     Pattern::canonicalize("48 8"),
+    ///     Err(PatternError::OddNibbleCount { position: 3 })
+    /// );
+    /// ```
+    pub fn canonicalize(pattern: &str) -> Result<String, PatternError> {
+        let canonical = Self::try_new(pattern)?.canonical_string();
+
+        // label names are case sensitive
+        let tokens = canonical.split(' ').map(|token| {
+            if token.starts_with('@') {
+                token.to_owned()
+            } else {
+                token.to_ascii_uppercase()
+            }
+        });
+
+        Ok(tokens.collect::<Vec<_>>().join(" "))
+    }
+
+    /// IDA-style string of the pattern with its bytes separated by spaces, e.g. `48 9D ?? 5C`
+    ///
+    /// Bytes are written as uppercase hex, wildcards as `??`. This is meant for exporting
+    /// signatures to other tools, so only what every tool understands is written: partially
+    /// masked bytes, including ranges, are widened to `??`, and the cursor and labels are
+    /// left out. Use [`Pattern::canonical_string`] to keep them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 9d ? ^ 5c");
+    ///
+    /// assert_eq!(pattern.as_ida_spaced(), "48 9D ?? 5C");
+    /// assert_eq!(Pattern::new(&pattern.as_ida_spaced()), pattern.with_cursor(0));
+    /// ```
+    pub fn as_ida_spaced(&self) -> String {
+        self.ida_tokens().collect::<Vec<_>>().join(" ")
+    }
+
+    /// IDA-style string of the pattern without any separators, e.g. `489D??5C`
+    ///
+    /// Every byte takes exactly two characters, so a wildcard is always written as `??` and
+    /// a run of `n` wildcards as `2n` question marks, keeping the bytes at even positions.
+    /// Otherwise this is the same as [`Pattern::as_ida_spaced`]. [`Pattern::new`] parses
+    /// both forms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let pattern = Pattern::new("48 9d ? ? 5c");
+    ///
+    /// assert_eq!(pattern.as_ida_compact(), "489D????5C");
+    /// assert_eq!(Pattern::new(&pattern.as_ida_compact()), pattern);
+    /// ```
+    pub fn as_ida_compact(&self) -> String {
+        self.ida_tokens().collect()
+    }
+
+    /// Tokens of the first `len` bytes, with the cursor and labels placed within them
+    ///
+    /// Shared by [`Pattern::canonical_string`] and the signature in the [`Debug`] output.
+    fn tokens(&self, len: usize) -> Vec<String> {
+        let mut tokens = Vec::new();
+
+        for offset in 0..=len {
+            if self.cursor == offset && self.cursor != 0 {
+                tokens.push("^".to_owned());
+            }
+            for (label, _) in self.labels.iter().filter(|(_, at)| *at == offset) {
+                tokens.push(format!("@{}", label));
+            }
+
+            if offset == len {
+                break;
+            }
+
+            let (data, mask) = (self.data[offset], self.mask[offset]);
+            tokens.push(match mask {
+                0xff => format!("{:02x}", data),
+                0x00 => "??".to_owned(),
+                // the masked bits are the high ones, so the byte matches a range of values
+                mask if mask.leading_ones() + mask.trailing_zeros() == 8 => {
+                    format!("[{:02x}-{:02x}]", data & mask, data | !mask)
+                }
+                0x0f => format!("?{:x}", data & 0x0f),
+                _ => "??".to_owned(),
+            });
+        }
+
+        tokens
+    }
+
+    /// Two-character token of every byte in the exported dialects
+    fn ida_tokens(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.unpadded_size).map(|offset| match self.mask[offset] {
+            0xff => format!("{:02X}", self.data[offset]),
+            _ => "??".to_owned(),
+        })
+    }
+}
+
+impl Clone for Pattern {
+    fn clone(&self) -> Self {
+        // the buffers are copied as a whole to keep the padding chosen with `pad_to`
+        Pattern {
+            data: AlignedBytes::new(&self.data),
+            mask: AlignedBytes::new(&self.mask),
+            unpadded_size: self.unpadded_size,
+            cursor: self.cursor,
+            labels: self.labels.clone(),
+        }
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.mask[..self.unpadded_size] == other.mask[..other.unpadded_size]
+            && self.masked_data().eq(other.masked_data())
+            && self.cursor == other.cursor
+            && self.labels == other.labels
+    }
+}
+
+impl Eq for Pattern {}
+
+impl Hash for Pattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.mask[..self.unpadded_size].hash(state);
+        self.masked_data().for_each(|data| state.write_u8(data));
+        self.cursor.hash(state);
+        self.labels.hash(state);
+    }
+}
+
+/// Number of pattern bytes shown in the signature printed by the [`Debug`] implementation
+const DEBUG_SIGNATURE_LEN: usize = 64;
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pattern")
+            .field("signature", &Signature(self))
+            .field("len", &self.unpadded_size)
+            .finish()
+    }
+}
+
+impl fmt::Display for Pattern {
+    /// Write the [`Pattern::canonical_string`] of the pattern
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.canonical_string())
+    }
+}
+
+/// IDA-style signature of a pattern, truncated after [`DEBUG_SIGNATURE_LEN`] bytes
+///
+/// Written the same way as [`Pattern::canonical_string`].
+struct Signature<'a>(&'a Pattern);
+
+impl fmt::Debug for Signature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pattern = self.0;
+        let shown = pattern.unpadded_size.min(DEBUG_SIGNATURE_LEN);
+
+        let tokens = pattern.tokens(shown);
+
+        if shown < pattern.unpadded_size {
+            write!(
+                f,
+                "\"{} ...\" ({} bytes)",
+                tokens.join(" "),
+                pattern.unpadded_size
+            )
+        } else {
+            write!(f, "\"{}\"", tokens.join(" "))
+        }
+    }
+}
+
+impl From<&str> for Pattern {
+    fn from(value: &str) -> Self {
+        Pattern::new(value)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    /// Parse an IDA-style pattern, see [`Pattern::try_new`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pattern::try_new(s)
+    }
+}
+
+impl Pattern {
+    /// Create a new [`Pattern`] from its raw parts.
     ///
     /// This is intended for use in the `create_pattern!` macro.
-    pub fn from_parts(
-        data: Box<AlignedBytes<32>>,
-        mask: Box<AlignedBytes<32>>,
+    /// Buffers of any alignment are accepted, they're copied and re-padded
+    /// to [`Pattern::ALIGNMENT`] if needed, [`Pattern::pad_to`] pads the result further.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` or `mask` is shorter than `unpadded_size`.
+    // the boxed parameters are kept for compatibility with the original signature
+    #[allow(clippy::boxed_local)]
+    pub fn from_parts<const N: usize>(
+        data: Box<AlignedBytes<N>>,
+        mask: Box<AlignedBytes<N>>,
         unpadded_size: usize,
-    ) -> Self {
-        Self {
-            data,
-            mask,
-            unpadded_size,
+    ) -> Self
+    where
+        Align<N>: Alignment,
+    {
+        Self::from_unpadded(
+            data[..unpadded_size].to_vec(),
+            mask[..unpadded_size].to_vec(),
+        )
+    }
+}
+
+/// Error parsing a [`Pattern`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PatternError {
+    /// A byte isn't made of two hex digits
+    InvalidByte {
+        /// Character position of the byte's first character
+        position: usize,
+    },
+    /// A hex digit isn't followed by the second digit of its byte, as in `48 8`
+    OddNibbleCount {
+        /// Character position of the lone digit
+        position: usize,
+    },
+    /// A `/* */` comment isn't closed
+    UnterminatedComment {
+        /// Character position of the comment's `/*`
+        position: usize,
+    },
+    /// A `?{n}` wildcard quantifier is not closed, has no count, or its count is zero or too large
+    MalformedQuantifier {
+        /// Character position of the quantifier's `?`
+        position: usize,
+    },
+    /// The pattern contains more than one `^` cursor marker
+    DuplicateCursor {
+        /// Character position of the second marker
+        position: usize,
+    },
+    /// An `@` label marker isn't followed by a name
+    MalformedLabel {
+        /// Character position of the marker's `@`
+        position: usize,
+    },
+    /// The pattern contains two `@` label markers with the same name
+    DuplicateLabel {
+        /// Character position of the second marker
+        position: usize,
+    },
+    /// A `[lo-hi]` byte range isn't made of two hex bytes, or its lower bound exceeds the upper one
+    MalformedRange {
+        /// Character position of the range's `[`
+        position: usize,
+    },
+    /// Every byte of a non-empty pattern is a wildcard, so it would match everywhere
+    AllWildcards,
+    /// A hex dump passed to [`Pattern::from_ida_dump`] doesn't contain a single byte
+    NoBytes,
+    /// Too few bytes of the pattern are concrete, see [`Pattern::try_new_strict`]
+    TooManyWildcards {
+        /// Number of fully-masked bytes
+        concrete: usize,
+        /// Length of the pattern in bytes
+        len: usize,
+    },
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::InvalidByte { position } => {
+                write!(f, "invalid hex byte at position {}", position)
+            }
+            PatternError::OddNibbleCount { position } => {
+                write!(f, "lone hex digit at position {}", position)
+            }
+            PatternError::UnterminatedComment { position } => {
+                write!(f, "unterminated comment at position {}", position)
+            }
+            PatternError::MalformedQuantifier { position } => {
+                write!(f, "malformed wildcard quantifier at position {}", position)
+            }
+            PatternError::DuplicateCursor { position } => {
+                write!(f, "duplicate cursor marker at position {}", position)
+            }
+            PatternError::MalformedLabel { position } => {
+                write!(f, "label marker without a name at position {}", position)
+            }
+            PatternError::DuplicateLabel { position } => {
+                write!(f, "duplicate label marker at position {}", position)
+            }
+            PatternError::MalformedRange { position } => {
+                write!(f, "malformed byte range at position {}", position)
+            }
+            PatternError::AllWildcards => write!(f, "pattern consists of wildcards only"),
+            PatternError::NoBytes => write!(f, "hex dump doesn't contain any bytes"),
+            PatternError::TooManyWildcards { concrete, len } => {
+                write!(f, "only {} of {} pattern bytes are concrete", concrete, len)
+            }
         }
     }
 }
+
+impl std::error::Error for PatternError {}
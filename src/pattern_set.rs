@@ -0,0 +1,192 @@
+//! Named collections of patterns
+
+use crate::pattern::Pattern;
+
+/// A collection of named patterns
+#[derive(Default)]
+pub struct PatternSet {
+    entries: Vec<(String, Pattern)>,
+}
+
+/// Pair of patterns in a [`PatternSet`] where one subsumes the other
+///
+/// Every match of `specific` is also a match of `general`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Redundancy<'a> {
+    /// Name of the more general pattern
+    pub general: &'a str,
+    /// Name of the more specific pattern
+    pub specific: &'a str,
+}
+
+impl PatternSet {
+    /// Create an empty [`PatternSet`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named pattern to the set
+    pub fn insert(&mut self, name: impl Into<String>, pattern: Pattern) {
+        self.entries.push((name.into(), pattern));
+    }
+
+    /// Find a pattern by name
+    pub fn get(&self, name: &str) -> Option<&Pattern> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, pattern)| pattern)
+    }
+
+    /// Iterate over the named patterns in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Pattern)> {
+        self.entries
+            .iter()
+            .map(|(name, pattern)| (name.as_str(), pattern))
+    }
+
+    /// Number of patterns in the set
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the set contains no patterns
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find all pairs of patterns where one subsumes the other
+    ///
+    /// Identical patterns subsume each other, they are reported once
+    /// with the one inserted first as the general pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    /// use lightningscanner::pattern_set::{PatternSet, Redundancy};
+    ///
+    /// let mut set = PatternSet::new();
+    /// set.insert("call", Pattern::new("e8 ?? ?? ?? ??"));
+    /// set.insert("call_test", Pattern::new("e8 ?? ?? ?? ?? 84 c0"));
+    ///
+    /// assert_eq!(
+    ///     set.find_redundant(),
+    ///     [Redundancy { general: "call", specific: "call_test" }]
+    /// );
+    /// ```
+    pub fn find_redundant(&self) -> Vec<Redundancy<'_>> {
+        let mut redundant = Vec::new();
+
+        for (i, (first_name, first)) in self.entries.iter().enumerate() {
+            for (second_name, second) in &self.entries[i + 1..] {
+                let (general, specific) = if first.subsumes(second) {
+                    (first_name, second_name)
+                } else if second.subsumes(first) {
+                    (second_name, first_name)
+                } else {
+                    continue;
+                };
+
+                redundant.push(Redundancy { general, specific });
+            }
+        }
+
+        redundant
+    }
+}
+
+impl<N: Into<String>> FromIterator<(N, Pattern)> for PatternSet {
+    fn from_iter<T: IntoIterator<Item = (N, Pattern)>>(iter: T) -> Self {
+        let mut set = PatternSet::new();
+        for (name, pattern) in iter {
+            set.insert(name, pattern);
+        }
+
+        set
+    }
+}
+
+impl Pattern {
+    /// Check if every match of `other` is also a match of this pattern
+    ///
+    /// Both patterns are compared from their first byte. This pattern subsumes `other`
+    /// if it's not longer than `other`, and at every position each bit this pattern
+    /// constrains is constrained to the same value by `other`.
+    /// In particular, a pattern subsumes every pattern it's a prefix of.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let general = Pattern::new("48 ?? 05");
+    /// let specific = Pattern::new("48 8b 05 ??");
+    ///
+    /// assert!(general.subsumes(&specific));
+    /// assert!(!specific.subsumes(&general));
+    /// ```
+    pub fn subsumes(&self, other: &Pattern) -> bool {
+        if self.unpadded_size > other.unpadded_size {
+            return false;
+        }
+
+        (0..self.unpadded_size).all(|i| {
+            let mask = self.mask[i];
+
+            mask & !other.mask[i] == 0 && self.data[i] & mask == other.data[i] & mask
+        })
+    }
+
+    /// Check if this pattern is at least as specific as `other`
+    ///
+    /// This is the converse of [`Pattern::subsumes`]: wherever `other` requires a bit,
+    /// this pattern requires the same value, so every match of this pattern is also a
+    /// match of `other` starting at the same offset. This pattern may require additional
+    /// bytes, in place of wildcards of `other` or past its end.
+    /// Identical patterns are subpatterns of each other, `self` is strictly more specific
+    /// if additionally `!other.is_subpattern_of(self)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let specific = Pattern::new("48 8b 05 ??");
+    /// let general = Pattern::new("48 ?? 05");
+    ///
+    /// // `general` is redundant in the presence of `specific`
+    /// assert!(specific.is_subpattern_of(&general));
+    /// assert!(!general.is_subpattern_of(&specific));
+    /// ```
+    pub fn is_subpattern_of(&self, other: &Pattern) -> bool {
+        other.subsumes(self)
+    }
+
+    /// Number of leading positions at which both patterns require the same bytes
+    ///
+    /// A position agrees if both masks are identical and so are the masked data bytes,
+    /// so a wildcard only agrees with another wildcard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let a = Pattern::new("48 8b ?? 24 c3");
+    /// let b = Pattern::new("48 8b ?? 24 90");
+    ///
+    /// assert_eq!(a.common_prefix_len(&b), 4);
+    /// ```
+    pub fn common_prefix_len(&self, other: &Pattern) -> usize {
+        let len = self.unpadded_size.min(other.unpadded_size);
+
+        (0..len)
+            .take_while(|&i| {
+                let mask = self.mask[i];
+
+                mask == other.mask[i] && self.data[i] & mask == other.data[i] & mask
+            })
+            .count()
+    }
+}
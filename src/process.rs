@@ -0,0 +1,380 @@
+//! Cross-process scanning
+//!
+//! Only available on Linux and Android.
+//!
+//! The memory of another process is read through `/proc/<pid>/mem`, which requires
+//! the same permissions as attaching to the process with `ptrace`.
+
+use crate::{ScanError, ScanMode, Scanner};
+use std::fs::{self, File};
+use std::io;
+use std::ops::ControlFlow;
+use std::os::unix::fs::FileExt;
+use std::time::{Duration, Instant};
+
+/// Amount of memory read from the target process at once
+const READ_SIZE: usize = 1024 * 1024;
+
+/// Mapped memory region of a process
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// Address of the first byte of the region
+    pub start: usize,
+    /// Address one byte past the end of the region
+    pub end: usize,
+    /// The region is readable
+    pub readable: bool,
+    /// The region is writable
+    pub writable: bool,
+    /// The region is executable
+    pub executable: bool,
+    /// Path of the file backing the region, or a pseudo-path such as `[heap]`
+    pub path: Option<String>,
+}
+
+impl Region {
+    /// Size of the region in bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Check if the region is empty
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    fn parse(line: &str) -> Option<Region> {
+        // the path is last and may contain spaces itself
+        let mut fields = line.splitn(6, ' ');
+
+        let (start, end) = fields.next()?.split_once('-')?;
+        let permissions = fields.next()?.as_bytes();
+
+        // offset, device and inode
+        fields.nth(2)?;
+        let path = fields
+            .next()
+            .map(str::trim_start)
+            .filter(|path| !path.is_empty())
+            .map(str::to_owned);
+
+        Some(Region {
+            start: usize::from_str_radix(start, 16).ok()?,
+            end: usize::from_str_radix(end, 16).ok()?,
+            readable: permissions.first() == Some(&b'r'),
+            writable: permissions.get(1) == Some(&b'w'),
+            executable: permissions.get(2) == Some(&b'x'),
+            path,
+        })
+    }
+}
+
+/// List the mapped memory regions of a process
+pub fn regions(pid: u32) -> io::Result<Vec<Region>> {
+    let maps = fs::read_to_string(format!("/proc/{}/maps", pid))?;
+
+    maps.lines()
+        .map(|line| {
+            Region::parse(line).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "malformed memory map entry")
+            })
+        })
+        .collect()
+}
+
+/// Restriction of a process scan to regions with certain permissions
+///
+/// The default filter lets every readable region through.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::process::ScanFilter;
+///
+/// // code which isn't patched at runtime
+/// let filter = ScanFilter {
+///     executable: true,
+///     writable: Some(false),
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ScanFilter {
+    /// Only scan executable regions
+    pub executable: bool,
+    /// Only scan writable regions if `Some(true)`, non-writable ones if `Some(false)`
+    pub writable: Option<bool>,
+}
+
+impl ScanFilter {
+    /// Filter letting executable regions through only
+    pub const CODE: ScanFilter = ScanFilter {
+        executable: true,
+        writable: None,
+    };
+
+    /// Check if `region` passes the filter
+    ///
+    /// Unreadable regions are never scanned, regardless of the filter.
+    pub fn matches(&self, region: &Region) -> bool {
+        (region.executable || !self.executable)
+            && self
+                .writable
+                .is_none_or(|writable| region.writable == writable)
+    }
+}
+
+/// Options for scanning the memory of a process
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ProcessScanOptions {
+    /// Preferred scan mode to use (Avx2, Sse42, Scalar)
+    pub preferred_scan_mode: Option<ScanMode>,
+    /// Abort the scan with [`ScanError::TimedOut`] once it takes longer than this
+    ///
+    /// The timeout is checked between reads of the target's memory,
+    /// so a single slow read can exceed it.
+    pub timeout: Option<Duration>,
+    /// Permissions of the regions to scan, every readable region by default
+    pub filter: ScanFilter,
+}
+
+impl Scanner {
+    /// Find the address of the first occurrence of the pattern in the memory of a process
+    ///
+    /// Regions are scanned in ascending address order, unreadable regions and those not
+    /// passing [`ProcessScanOptions::filter`] are skipped. Restricting the scan to
+    /// executable regions speeds up the search for code signatures considerably and
+    /// keeps copies of them in data or on the heap from matching.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lightningscanner::process::{ProcessScanOptions, ScanFilter};
+    /// use lightningscanner::Scanner;
+    /// use std::time::Duration;
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    /// let options = ProcessScanOptions {
+    ///     timeout: Some(Duration::from_secs(5)),
+    ///     filter: ScanFilter::CODE,
+    ///     ..ProcessScanOptions::default()
+    /// };
+    ///
+    /// let address = scanner.find_in_process(1234, options).unwrap();
+    /// println!("{:x?}", address);
+    /// ```
+    pub fn find_in_process(
+        &self,
+        pid: u32,
+        options: ProcessScanOptions,
+    ) -> Result<Option<usize>, ScanError> {
+        let mut result = None;
+
+        self.scan_process(pid, options, |address| {
+            result = Some(address);
+            ControlFlow::Break(())
+        })?;
+
+        Ok(result)
+    }
+
+    /// Find the addresses of all occurrences of the pattern in the memory of a process
+    ///
+    /// See [`Scanner::find_in_process`].
+    pub fn find_all_in_process(
+        &self,
+        pid: u32,
+        options: ProcessScanOptions,
+    ) -> Result<Vec<usize>, ScanError> {
+        let mut results = Vec::new();
+
+        self.scan_process(pid, options, |address| {
+            results.push(address);
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(results)
+    }
+
+    /// Iterate over the addresses of the occurrences of the pattern in the memory of a process
+    ///
+    /// Regions passing `filter` are read on demand, a chunk at a time, so stopping early
+    /// leaves the rest of the target's memory unread. Addresses are yielded in ascending
+    /// order, as with [`Scanner::find_all_in_process`].
+    ///
+    /// Failing to read a region yields an error and continues with the next region, failing
+    /// to open the target's memory or to list its regions yields a single error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use lightningscanner::process::ScanFilter;
+    /// use lightningscanner::Scanner;
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    ///
+    /// for address in scanner.find_iter_process(1234, ScanFilter::CODE).take(10) {
+    ///     match address {
+    ///         Ok(address) => println!("{:#x}", address),
+    ///         Err(err) => eprintln!("{}", err),
+    ///     }
+    /// }
+    /// ```
+    pub fn find_iter_process(
+        &self,
+        pid: u32,
+        filter: ScanFilter,
+    ) -> impl Iterator<Item = io::Result<usize>> + '_ {
+        ProcessMatches::new(self, pid, filter)
+    }
+
+    fn scan_process(
+        &self,
+        pid: u32,
+        options: ProcessScanOptions,
+        mut on_match: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Result<(), ScanError> {
+        let deadline = options
+            .timeout
+            .and_then(|timeout| Instant::now().checked_add(timeout));
+
+        let memory = File::open(format!("/proc/{}/mem", pid))?;
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+        let mut buffer = vec![0; READ_SIZE + overlap];
+
+        let regions = regions(pid)?;
+        for region in regions
+            .iter()
+            .filter(|region| region.readable && options.filter.matches(region))
+        {
+            let mut address = region.start;
+
+            while address < region.end {
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Err(ScanError::TimedOut);
+                }
+
+                let len = buffer.len().min(region.end - address);
+                let Ok(read) = memory.read_at(&mut buffer[..len], address as u64) else {
+                    // the rest of the region can't be read, e.g. a guard page
+                    break;
+                };
+                if read == 0 {
+                    break;
+                }
+
+                // matches starting in the overlap are reported by the next read
+                let last_read = address + read >= region.end || read < len;
+                let step = if last_read { read } else { READ_SIZE };
+
+                for offset in self.find_all_offsets(options.preferred_scan_mode, &buffer[..read]) {
+                    if offset >= step {
+                        break;
+                    }
+                    if on_match(address + offset).is_break() {
+                        return Ok(());
+                    }
+                }
+
+                if read < len {
+                    break;
+                }
+                address += step;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator of [`Scanner::find_iter_process`]
+struct ProcessMatches<'a> {
+    scanner: &'a Scanner,
+    /// Error opening the target's memory or listing its regions, yielded first
+    error: Option<io::Error>,
+    memory: Option<File>,
+    regions: std::vec::IntoIter<Region>,
+    /// Next address to read and the end of the region being read
+    current: Option<(usize, usize)>,
+    buffer: Vec<u8>,
+    /// Matches of the last read which weren't yielded yet
+    pending: std::vec::IntoIter<usize>,
+}
+
+impl<'a> ProcessMatches<'a> {
+    fn new(scanner: &'a Scanner, pid: u32, filter: ScanFilter) -> Self {
+        let opened =
+            File::open(format!("/proc/{}/mem", pid)).and_then(|memory| Ok((memory, regions(pid)?)));
+
+        let (memory, regions, error) = match opened {
+            Ok((memory, regions)) => (Some(memory), regions, None),
+            Err(err) => (None, Vec::new(), Some(err)),
+        };
+        let regions = regions
+            .into_iter()
+            .filter(|region| region.readable && filter.matches(region))
+            .collect::<Vec<_>>();
+
+        let overlap = scanner.pattern.unpadded_size.saturating_sub(1);
+
+        ProcessMatches {
+            scanner,
+            error,
+            memory,
+            regions: regions.into_iter(),
+            current: None,
+            buffer: vec![0; READ_SIZE + overlap],
+            pending: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl Iterator for ProcessMatches<'_> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+        let memory = self.memory.as_ref()?;
+
+        loop {
+            if let Some(address) = self.pending.next() {
+                return Some(Ok(address));
+            }
+
+            let Some((address, end)) = self.current else {
+                let region = self.regions.next()?;
+                self.current = Some((region.start, region.end));
+                continue;
+            };
+            if address >= end {
+                self.current = None;
+                continue;
+            }
+
+            let len = self.buffer.len().min(end - address);
+            let read = match memory.read_at(&mut self.buffer[..len], address as u64) {
+                Ok(read) => read,
+                Err(err) => {
+                    self.current = None;
+                    return Some(Err(err));
+                }
+            };
+
+            // matches starting in the overlap are reported by the next read
+            let last_read = address + read >= end || read < len;
+            let step = if last_read { read } else { READ_SIZE };
+
+            self.pending = self
+                .scanner
+                .find_all_offsets(None, &self.buffer[..read])
+                .into_iter()
+                .take_while(|&offset| offset < step)
+                .map(|offset| address + offset)
+                .collect::<Vec<_>>()
+                .into_iter();
+
+            self.current = (!last_read).then_some((address + step, end));
+        }
+    }
+}
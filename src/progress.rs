@@ -0,0 +1,166 @@
+//! Long scans reporting their progress or being cancelled
+
+use crate::{is_valid_range, ScanMode, ScanResult, Scanner};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{ptr, slice};
+
+impl Scanner {
+    /// Number of bytes scanned between two calls of the callback of [`Scanner::find_with_progress`]
+    pub const PROGRESS_INTERVAL: usize = 4 * 1024 * 1024;
+
+    /// Find the first occurrence of the pattern in the binary, reporting the progress of the scan
+    ///
+    /// `progress` is called with the number of bytes scanned so far after every
+    /// [`Scanner::PROGRESS_INTERVAL`] bytes, and with `binary_size` once the whole binary
+    /// has been scanned without a match. It's not called anymore once a match is found.
+    /// The result is the same as that of [`Scanner::find`].
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    ///
+    /// let binary = vec![0x00; 10 * 1024 * 1024];
+    /// let mut reports = Vec::new();
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe {
+    ///     scanner.find_with_progress(None, binary.as_ptr(), binary.len(), |scanned| {
+    ///         reports.push(scanned)
+    ///     })
+    /// };
+    ///
+    /// assert!(!result.is_valid());
+    /// assert_eq!(reports, [4 * 1024 * 1024, 8 * 1024 * 1024, 10 * 1024 * 1024]);
+    /// ```
+    pub unsafe fn find_with_progress(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        mut progress: impl FnMut(usize),
+    ) -> ScanResult {
+        if !is_valid_range(binary_ptr, binary_size) {
+            return ScanResult {
+                addr: ptr::null(),
+                writable: false,
+            };
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        let offset = self.find_in_steps(
+            preferred_scan_mode,
+            binary,
+            Self::PROGRESS_INTERVAL,
+            |scanned| {
+                progress(scanned);
+                ControlFlow::Continue(())
+            },
+        );
+
+        ScanResult {
+            addr: offset.map_or(ptr::null(), |offset| {
+                binary[offset + self.pattern.cursor..].as_ptr()
+            }),
+            writable: false,
+        }
+    }
+
+    /// Number of bytes scanned between two checks of the flag of [`Scanner::find_cancellable`]
+    pub const CANCEL_CHECK_INTERVAL: usize = 64 * 1024;
+
+    /// Find the offset of the first occurrence of the pattern in the binary, unless cancelled
+    ///
+    /// `cancel` is checked before the scan and after every [`Scanner::CANCEL_CHECK_INTERVAL`]
+    /// bytes. Once it's set, the scan stops and `None` is returned, the same as if there
+    /// was no match. The offset is that of the match start, regardless of the pattern's
+    /// [cursor](crate::pattern::Pattern::cursor).
+    ///
+    /// # Safety
+    ///
+    /// * `binary_ptr` - is a valid pointer
+    ///
+    /// * `binary_size` - corresponds to a valid size of `binary`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::Scanner;
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    /// let cancel = AtomicBool::new(false);
+    ///
+    /// let scanner = Scanner::new("48 89 5c 24");
+    /// let result = unsafe { scanner.find_cancellable(None, binary.as_ptr(), binary.len(), &cancel) };
+    ///
+    /// assert_eq!(result, Some(2));
+    /// ```
+    pub unsafe fn find_cancellable(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary_ptr: *const u8,
+        binary_size: usize,
+        cancel: &AtomicBool,
+    ) -> Option<usize> {
+        if !is_valid_range(binary_ptr, binary_size) || cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        // SAFETY: the caller guarantees `binary_ptr` is valid for `binary_size` bytes
+        let binary = unsafe { slice::from_raw_parts(binary_ptr, binary_size) };
+
+        self.find_in_steps(
+            preferred_scan_mode,
+            binary,
+            Self::CANCEL_CHECK_INTERVAL,
+            |_| {
+                if cancel.load(Ordering::Relaxed) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        )
+    }
+
+    /// Find the first occurrence of the pattern in `binary`, `step` bytes at a time
+    ///
+    /// `between_steps` is called with the number of bytes scanned after every step without
+    /// a match, including the last one, and stops the scan when it breaks.
+    pub(crate) fn find_in_steps(
+        &self,
+        preferred_scan_mode: Option<ScanMode>,
+        binary: &[u8],
+        step: usize,
+        mut between_steps: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Option<usize> {
+        let overlap = self.pattern.unpadded_size.saturating_sub(1);
+
+        let mut start = 0usize;
+        loop {
+            let scanned = start.saturating_add(step).min(binary.len());
+
+            // matches starting within the step may extend past it
+            let end = scanned.saturating_add(overlap).min(binary.len());
+            if let Some(offset) = self.find_in_range(preferred_scan_mode, binary, start..end) {
+                return Some(offset);
+            }
+
+            if between_steps(scanned).is_break() || scanned == binary.len() {
+                return None;
+            }
+
+            start = scanned;
+        }
+    }
+}
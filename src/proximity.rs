@@ -0,0 +1,124 @@
+//! Matches qualified by a nearby second pattern
+
+use crate::pattern::Pattern;
+use crate::Scanner;
+
+/// Where the second pattern of a [`ProximityQuery`] has to occur relative to the first one
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The second pattern starts at or before the start of the first one
+    Before,
+    /// The second pattern starts at or after the start of the first one
+    After,
+    /// The second pattern starts on either side of the start of the first one
+    Either,
+}
+
+/// Search for a pattern which only counts when a second pattern occurs close to it
+///
+/// The distance between two matches is the distance between their start offsets,
+/// and matches at exactly `max_distance` bytes are accepted. The matches may overlap.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::pattern::Pattern;
+/// use lightningscanner::proximity::{Direction, ProximityQuery};
+///
+/// let haystack = [0x84, 0xc0, 0xcc, 0xcc, 0x84, 0xc0, 0x74, 0x05];
+///
+/// let query = ProximityQuery::new(
+///     Pattern::new("84 c0"),
+///     Pattern::new("74 ??"),
+///     2,
+///     Direction::After,
+/// );
+///
+/// let result = query.find(&haystack).unwrap();
+/// assert_eq!((result.a(), result.b()), (4, 6));
+/// ```
+#[derive(Debug)]
+pub struct ProximityQuery {
+    a: Scanner,
+    b: Scanner,
+    max_distance: usize,
+    direction: Direction,
+}
+
+/// Offsets of both patterns of a [`ProximityQuery`] match
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ProximityMatch {
+    a: usize,
+    b: usize,
+}
+
+impl ProximityMatch {
+    /// Offset of the first pattern from the beginning of the haystack
+    pub fn a(&self) -> usize {
+        self.a
+    }
+
+    /// Offset of the second pattern from the beginning of the haystack
+    pub fn b(&self) -> usize {
+        self.b
+    }
+}
+
+impl ProximityQuery {
+    /// Create a new [`ProximityQuery`] for `a`, qualified by `b` within `max_distance` bytes
+    pub fn new(a: Pattern, b: Pattern, max_distance: usize, direction: Direction) -> Self {
+        ProximityQuery {
+            a: Scanner::from(a),
+            b: Scanner::from(b),
+            max_distance,
+            direction,
+        }
+    }
+
+    /// Find the first occurrence of the first pattern with the second one close enough to it
+    ///
+    /// If several occurrences of the second pattern qualify, the closest one is reported,
+    /// the earlier one on a tie.
+    pub fn find(&self, haystack: &[u8]) -> Option<ProximityMatch> {
+        let b_size = self.b.pattern.unpadded_size;
+
+        let mut start = 0;
+        while let Some(a) = self.a.find_in_slice(None, haystack, start) {
+            start = a + 1;
+
+            let (first, last) = match self.direction {
+                Direction::Before => (a.saturating_sub(self.max_distance), a),
+                Direction::After => (a, a.saturating_add(self.max_distance)),
+                Direction::Either => (
+                    a.saturating_sub(self.max_distance),
+                    a.saturating_add(self.max_distance),
+                ),
+            };
+
+            // only matches starting at `last` at the latest fit into the window
+            let end = last.saturating_add(b_size).min(haystack.len());
+            if first >= end {
+                continue;
+            }
+
+            let mut closest: Option<usize> = None;
+            let mut b_start = first;
+            while let Some(b) = self.b.find_in_range(None, haystack, b_start..end) {
+                if closest.is_none_or(|closest| b.abs_diff(a) < closest.abs_diff(a)) {
+                    closest = Some(b);
+                }
+                if b >= a {
+                    // every later match is further away
+                    break;
+                }
+                b_start = b + 1;
+            }
+
+            if let Some(b) = closest {
+                return Some(ProximityMatch { a, b });
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,174 @@
+//! Patterns matching ranges of byte values
+
+use crate::pattern::{Pattern, PatternError};
+use crate::{masked_eq_at, ScanMode, Scanner};
+use std::ops::RangeInclusive;
+
+/// IDA-style pattern whose bytes may also be ranges of values, written as `[lo-hi]`
+///
+/// A range byte matches every value from `lo` to `hi`, both included. The range is
+/// turned into a masked byte matching the bits all of its values share, so the SIMD
+/// scan finds candidates which are then checked against the range itself.
+///
+/// A few ranges of ASCII characters can be written by name instead:
+///
+/// | Class     | Range     |
+/// |-----------|-----------|
+/// | `[digit]` | `[30-39]` |
+/// | `[upper]` | `[41-5a]` |
+/// | `[lower]` | `[61-7a]` |
+/// | `[print]` | `[20-7e]` |
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::range_pattern::RangePattern;
+///
+/// // any of the REX.W prefixes
+/// let pattern = RangePattern::new("[48-4f] 89 5c 24");
+///
+/// assert!(pattern.matches_at(&[0x4c, 0x89, 0x5c, 0x24], 0));
+/// assert!(!pattern.matches_at(&[0x40, 0x89, 0x5c, 0x24], 0));
+///
+/// // "v" followed by a version number
+/// let version = RangePattern::new("76 [digit] 2e [digit]");
+/// assert!(version.matches_at(b"v1.2", 0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangePattern {
+    pattern: Pattern,
+    ranges: Vec<(usize, RangeInclusive<u8>)>,
+}
+
+impl RangePattern {
+    /// Create a new [`RangePattern`] instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is malformed, see [`RangePattern::try_new`].
+    pub fn new(pattern: &str) -> Self {
+        match Self::try_new(pattern) {
+            Ok(pattern) => pattern,
+            Err(err) => panic!("invalid pattern: {}", err),
+        }
+    }
+
+    /// Create a new [`RangePattern`] instance, reporting malformed patterns as errors
+    ///
+    /// Everything [`Pattern::try_new`] accepts is accepted as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::PatternError;
+    /// use lightningscanner::range_pattern::RangePattern;
+    ///
+    /// assert!(RangePattern::try_new("[b8-bf] ?? ?? ?? ?? c3").is_ok());
+    /// assert_eq!(
+    ///     RangePattern::try_new("[bf-b8] ?? ?? ?? ?? c3").err(),
+    ///     Some(PatternError::MalformedRange { position: 0 })
+    /// );
+    /// ```
+    pub fn try_new(pattern: &str) -> Result<Self, PatternError> {
+        let mut ranges = Vec::new();
+        let pattern = Pattern::parse(pattern, Some(&mut ranges))?;
+
+        Ok(RangePattern { pattern, ranges })
+    }
+
+    /// Masked pattern every match of the range pattern also matches
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Indices of the range bytes along with their ranges, in ascending order
+    pub fn ranges(&self) -> &[(usize, RangeInclusive<u8>)] {
+        &self.ranges
+    }
+
+    /// Length of the pattern in bytes
+    pub fn len(&self) -> usize {
+        self.pattern.unpadded_size
+    }
+
+    /// Check if the pattern has no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the pattern matches `haystack` at `offset`
+    ///
+    /// Matches extending past the end of the haystack are rejected.
+    pub fn matches_at(&self, haystack: &[u8], offset: usize) -> bool {
+        masked_eq_at(haystack, offset, &self.pattern) && self.ranges_match_at(haystack, offset)
+    }
+
+    fn ranges_match_at(&self, haystack: &[u8], offset: usize) -> bool {
+        self.ranges
+            .iter()
+            .all(|(index, range)| range.contains(&haystack[offset + index]))
+    }
+}
+
+/// Scanner for a [`RangePattern`]
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::range_pattern::{RangePattern, RangeScanner};
+///
+/// let binary = [0x40, 0x89, 0x5c, 0x24, 0x4c, 0x89, 0x5c, 0x24];
+/// let scanner = RangeScanner::new(RangePattern::new("[48-4f] 89 5c 24"));
+///
+/// assert_eq!(scanner.find(None, &binary), Some(4));
+/// ```
+#[derive(Debug)]
+pub struct RangeScanner {
+    scanner: Scanner,
+    pattern: RangePattern,
+}
+
+impl RangeScanner {
+    /// Create a new [`RangeScanner`] for `pattern`
+    pub fn new(pattern: RangePattern) -> Self {
+        RangeScanner {
+            scanner: Scanner::from(pattern.pattern.clone()),
+            pattern,
+        }
+    }
+
+    /// The pattern this scanner searches for
+    pub fn pattern(&self) -> &RangePattern {
+        &self.pattern
+    }
+
+    /// Find the offset of the first match of the pattern in `haystack`
+    pub fn find(&self, preferred_scan_mode: Option<ScanMode>, haystack: &[u8]) -> Option<usize> {
+        self.matches(preferred_scan_mode, haystack).next()
+    }
+
+    /// Find the offsets of all matches of the pattern in `haystack`
+    ///
+    /// Overlapping matches are reported separately, in ascending order.
+    pub fn find_all(&self, preferred_scan_mode: Option<ScanMode>, haystack: &[u8]) -> Vec<usize> {
+        self.matches(preferred_scan_mode, haystack).collect()
+    }
+
+    fn matches<'a>(
+        &'a self,
+        preferred_scan_mode: Option<ScanMode>,
+        haystack: &'a [u8],
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut start = 0;
+
+        std::iter::from_fn(move || {
+            let offset = self
+                .scanner
+                .find_in_slice(preferred_scan_mode, haystack, start)?;
+            start = offset + 1;
+
+            Some(offset)
+        })
+        .filter(move |&offset| self.pattern.ranges_match_at(haystack, offset))
+    }
+}
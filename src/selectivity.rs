@@ -0,0 +1,206 @@
+//! Pattern selectivity estimation
+
+use crate::pattern::Pattern;
+
+/// Bytes that are the most common in x86-64 machine code, ordered from the most common one
+const COMMON_BYTES: [u8; 32] = [
+    0x00, 0xff, 0x48, 0x8b, 0x89, 0xcc, 0x0f, 0x24, 0x4c, 0x01, 0xe8, 0x85, 0x44, 0x83, 0x8d, 0x74,
+    0xc0, 0x20, 0x08, 0x10, 0xc3, 0x40, 0x49, 0x41, 0x45, 0x75, 0x84, 0x04, 0x02, 0xeb, 0x28, 0x30,
+];
+
+/// Frequency rank of a byte, 0 being the most common one
+///
+/// Bytes outside of [`COMMON_BYTES`] share the rarest rank.
+fn frequency_rank(byte: u8) -> usize {
+    COMMON_BYTES
+        .iter()
+        .position(|&common| common == byte)
+        .unwrap_or(COMMON_BYTES.len())
+}
+
+/// Count how often every byte value occurs in `data`
+///
+/// The counts are indexed by byte value. Bytes are counted into several tables which are
+/// summed at the end, so consecutive equal bytes don't wait on each other's increments.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::selectivity::byte_histogram;
+///
+/// let histogram = byte_histogram(&[0x48, 0x8b, 0x48, 0xc3]);
+///
+/// assert_eq!(histogram[0x48], 2);
+/// assert_eq!(histogram[0xc3], 1);
+/// assert_eq!(histogram[0x00], 0);
+/// ```
+pub fn byte_histogram(data: &[u8]) -> [u64; 256] {
+    let mut tables = [[0u64; 256]; 4];
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        for (table, &byte) in tables.iter_mut().zip(chunk) {
+            table[byte as usize] += 1;
+        }
+    }
+    for &byte in chunks.remainder() {
+        tables[0][byte as usize] += 1;
+    }
+
+    let mut histogram = tables[0];
+    for table in &tables[1..] {
+        for (count, &other) in histogram.iter_mut().zip(table) {
+            *count += other;
+        }
+    }
+
+    histogram
+}
+
+/// Estimate of how selective a [`Pattern`] is
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SelectivityReport {
+    /// Number of fully-masked bytes
+    pub concrete_bytes: usize,
+    /// Length of the longest run of consecutive fully-masked bytes
+    pub longest_concrete_run: usize,
+    /// Number of bits constrained by the mask
+    pub constrained_bits: u32,
+    /// Probability of the pattern matching at a single position of uniformly random data
+    ///
+    /// Every constrained bit halves the probability, so a fully-masked byte contributes `1/256`.
+    pub false_positive_probability: f64,
+    /// The rarest fully-masked byte of the pattern
+    pub anchor: Option<Anchor>,
+}
+
+/// Byte of a pattern that is the least likely to occur in machine code
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Anchor {
+    /// Offset of the byte in the pattern
+    pub offset: usize,
+    /// Value of the byte
+    pub byte: u8,
+    /// Frequency rank of the byte in x86-64 machine code, 0 being the most common one
+    pub rank: usize,
+}
+
+impl SelectivityReport {
+    /// Check if this report describes a pattern that is expected
+    /// to produce more false positives than the one described by `other`
+    ///
+    /// Patterns are compared by the number of constrained bits,
+    /// ties are broken by the longest concrete run.
+    pub fn is_weaker_than(&self, other: &SelectivityReport) -> bool {
+        (self.constrained_bits, self.longest_concrete_run)
+            < (other.constrained_bits, other.longest_concrete_run)
+    }
+}
+
+impl Pattern {
+    /// Fraction of the pattern's bytes that are fully masked
+    ///
+    /// An empty pattern has a density of zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// assert_eq!(Pattern::new("48 8b ?? c3").mask_density(), 0.75);
+    /// ```
+    pub fn mask_density(&self) -> f64 {
+        if self.unpadded_size == 0 {
+            return 0.0;
+        }
+
+        self.concrete_bytes() as f64 / self.unpadded_size as f64
+    }
+
+    /// Number of fully-masked bytes
+    pub(crate) fn concrete_bytes(&self) -> usize {
+        self.mask[..self.unpadded_size]
+            .iter()
+            .filter(|&&mask| mask == 0xff)
+            .count()
+    }
+
+    /// Estimate how selective the pattern is
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let report = Pattern::new("48 8b ?? c3").selectivity();
+    ///
+    /// assert_eq!(report.concrete_bytes, 3);
+    /// assert_eq!(report.longest_concrete_run, 2);
+    /// assert_eq!(report.anchor.unwrap().byte, 0xc3);
+    /// ```
+    pub fn selectivity(&self) -> SelectivityReport {
+        let data = &self.data[..self.unpadded_size];
+        let mask = &self.mask[..self.unpadded_size];
+
+        let mut concrete_bytes = 0;
+        let mut longest_concrete_run = 0;
+        let mut current_run = 0;
+        let mut constrained_bits = 0;
+        let mut anchor: Option<Anchor> = None;
+
+        for (offset, (&byte, &mask)) in data.iter().zip(mask).enumerate() {
+            constrained_bits += mask.count_ones();
+
+            if mask != 0xff {
+                current_run = 0;
+                continue;
+            }
+
+            concrete_bytes += 1;
+            current_run += 1;
+            longest_concrete_run = longest_concrete_run.max(current_run);
+
+            let rank = frequency_rank(byte);
+            if anchor.is_none_or(|anchor| rank > anchor.rank) {
+                anchor = Some(Anchor { offset, byte, rank });
+            }
+        }
+
+        SelectivityReport {
+            concrete_bytes,
+            longest_concrete_run,
+            constrained_bits,
+            false_positive_probability: 0.5f64.powi(constrained_bits as i32),
+            anchor,
+        }
+    }
+
+    /// Offset of the fully-masked byte which is the rarest according to `histogram`
+    ///
+    /// The histogram is usually that of the data to be scanned, see [`byte_histogram`].
+    /// The earliest byte wins ties, patterns without a fully-masked byte have none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    /// use lightningscanner::selectivity::byte_histogram;
+    ///
+    /// let histogram = byte_histogram(&[0x48, 0x8b, 0x48, 0xc3, 0xc3, 0x8b]);
+    ///
+    /// assert_eq!(Pattern::new("48 8b ?? c3 90").rarest_byte_offset(&histogram), Some(4));
+    /// assert_eq!(Pattern::new("?? 48 c3").rarest_byte_offset(&histogram), Some(1));
+    /// ```
+    pub fn rarest_byte_offset(&self, histogram: &[u64; 256]) -> Option<usize> {
+        (0..self.unpadded_size)
+            .filter(|&offset| self.mask[offset] == 0xff)
+            .min_by_key(|&offset| histogram[self.data[offset] as usize])
+    }
+
+    /// Check if the pattern is expected to produce more false positives than `other`
+    ///
+    /// See [`SelectivityReport::is_weaker_than`].
+    pub fn is_weaker_than(&self, other: &Pattern) -> bool {
+        self.selectivity().is_weaker_than(&other.selectivity())
+    }
+}
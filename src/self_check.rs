@@ -0,0 +1,67 @@
+//! Cross-backend consistency checks
+
+use crate::pattern::Pattern;
+use crate::{backends, ScanMode, Scanner};
+use std::fmt;
+
+/// Check that every scan mode supported by the running CPU finds the same matches
+///
+/// All occurrences of `pattern` in `haystack` are searched for with every supported backend,
+/// as well as with [`ScanMode::Auto`], and compared against the scalar backend.
+///
+/// # Example
+///
+/// ```
+/// use lightningscanner::pattern::Pattern;
+///
+/// let haystack = [0x48, 0x89, 0x5c, 0x24, 0x08, 0x48, 0x89, 0x5c];
+///
+/// assert!(lightningscanner::self_check(&Pattern::new("48 89 5c"), &haystack).is_ok());
+/// ```
+pub fn self_check(pattern: &Pattern, haystack: &[u8]) -> Result<(), BackendMismatch> {
+    let scanner = Scanner::from(pattern.clone());
+    let expected = scanner.find_all_offsets(Some(ScanMode::Scalar), haystack);
+
+    for mode in [ScanMode::Auto]
+        .into_iter()
+        .chain(backends::supported_modes())
+    {
+        let found = scanner.find_all_offsets(Some(mode), haystack);
+
+        let first_difference =
+            (0..expected.len().max(found.len())).find(|&i| expected.get(i) != found.get(i));
+
+        if let Some(i) = first_difference {
+            return Err(BackendMismatch {
+                mode,
+                expected: expected.get(i).copied(),
+                found: found.get(i).copied(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Disagreement between a scan mode and the scalar backend, see [`self_check`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BackendMismatch {
+    /// Scan mode which disagrees with the scalar backend
+    pub mode: ScanMode,
+    /// First offset reported by the scalar backend that differs, `None` if it reported fewer matches
+    pub expected: Option<usize>,
+    /// Offset reported by `mode` in its place, `None` if it reported fewer matches
+    pub found: Option<usize>,
+}
+
+impl fmt::Display for BackendMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} scan mode reported a match at {:?} where the scalar one reported {:?}",
+            self.mode, self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for BackendMismatch {}
@@ -0,0 +1,162 @@
+//! Binary serialization of compiled patterns
+//!
+//! A pattern is encoded as the `LSPT` magic, a format version byte,
+//! the unpadded length as a little-endian `u32`, followed by the data and mask bytes.
+//! A batch of patterns is encoded as the `LSPB` magic, a format version byte,
+//! the pattern count as a little-endian `u32`, followed by the encoded patterns.
+//!
+//! Padding is never stored, it's re-applied when decoding.
+//! The [cursor](Pattern::cursor) and [labels](Pattern::labels) aren't stored either,
+//! decoded patterns have none.
+
+use crate::pattern::Pattern;
+use std::fmt;
+
+const PATTERN_MAGIC: [u8; 4] = *b"LSPT";
+const BATCH_MAGIC: [u8; 4] = *b"LSPB";
+const VERSION: u8 = 1;
+
+/// Error decoding a serialized pattern
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The input doesn't start with the expected magic bytes
+    InvalidMagic,
+    /// The input was produced by an unsupported format version
+    UnsupportedVersion(u8),
+    /// The input ended before the encoded pattern did
+    UnexpectedEof,
+    /// The input continues after the encoded pattern
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidMagic => write!(f, "invalid magic"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            DecodeError::TrailingBytes => write!(f, "trailing bytes after the encoded data"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Pattern {
+    /// Serialize the pattern into a versioned binary format
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::pattern::Pattern;
+    ///
+    /// let bytes = Pattern::new("48 89 5c 24 ?? 48 89 6c").to_bytes();
+    /// let pattern = Pattern::from_bytes(&bytes).unwrap();
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 + self.unpadded_size * 2);
+        self.encode(&mut bytes);
+        bytes
+    }
+
+    /// Deserialize a pattern produced by [`Pattern::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Pattern, DecodeError> {
+        let mut reader = Reader(bytes);
+        let pattern = Pattern::decode(&mut reader)?;
+        reader.finish()?;
+
+        Ok(pattern)
+    }
+
+    /// Serialize a batch of patterns into a single blob
+    pub fn batch_to_bytes(patterns: &[Pattern]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(&BATCH_MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&encode_len(patterns.len()));
+
+        for pattern in patterns {
+            pattern.encode(&mut bytes);
+        }
+
+        bytes
+    }
+
+    /// Deserialize a batch of patterns produced by [`Pattern::batch_to_bytes`]
+    pub fn batch_from_bytes(bytes: &[u8]) -> Result<Vec<Pattern>, DecodeError> {
+        let mut reader = Reader(bytes);
+        let count = reader.header(BATCH_MAGIC)?;
+
+        // every pattern takes at least 9 bytes, so a corrupted count can't cause a huge allocation
+        let mut patterns = Vec::with_capacity(count.min(bytes.len() / 9));
+        for _ in 0..count {
+            patterns.push(Pattern::decode(&mut reader)?);
+        }
+        reader.finish()?;
+
+        Ok(patterns)
+    }
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&PATTERN_MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&encode_len(self.unpadded_size));
+        bytes.extend(self.masked_data());
+        bytes.extend_from_slice(&self.mask[..self.unpadded_size]);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Pattern, DecodeError> {
+        let len = reader.header(PATTERN_MAGIC)?;
+
+        let data = reader.take(len)?.to_vec();
+        let mask = reader.take(len)?.to_vec();
+
+        Ok(Pattern::from_unpadded(data, mask))
+    }
+}
+
+fn encode_len(len: usize) -> [u8; 4] {
+    u32::try_from(len)
+        .expect("length exceeds the serialization format limit")
+        .to_le_bytes()
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.0.len() < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    /// Read a magic, a version and a length
+    fn header(&mut self, magic: [u8; 4]) -> Result<usize, DecodeError> {
+        if self.take(4)? != magic {
+            return Err(DecodeError::InvalidMagic);
+        }
+
+        let version = self.take(1)?[0];
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let len = self.take(4)?;
+        Ok(u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize)
+    }
+
+    fn finish(self) -> Result<(), DecodeError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+}
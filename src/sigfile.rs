@@ -0,0 +1,319 @@
+//! Signature database files
+//!
+//! A signature file contains one named pattern per line, optionally followed by
+//! comma-separated attributes:
+//!
+//! ```text
+//! # player structure
+//! local_player = "48 8b 05 ?? ?? ?? ?? 48 85 c0", offset = +3, module = "game.dll"
+//! // the tick function
+//! tick = "40 53 48 83 ec 20"
+//! ```
+//!
+//! Comments start with `#` or `//` and run to the end of the line.
+
+use crate::backends;
+use crate::pattern::{Pattern, PatternError};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A named pattern loaded from a signature file
+pub struct Signature {
+    /// Name of the signature
+    pub name: String,
+    /// Pattern of the signature
+    pub pattern: Pattern,
+    /// Offset added to the match address when resolving the signature
+    pub offset: isize,
+    /// Module the signature is expected to be found in
+    pub module: Option<String>,
+    text: String,
+}
+
+impl Signature {
+    /// Pattern string the signature was parsed from
+    pub fn pattern_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Find the first occurrence of the signature in `binary`, with the pattern's
+    /// [cursor](Pattern::cursor) and the offset applied
+    ///
+    /// Returns `None` if there's no match or the offset moves the result outside of `binary`.
+    pub fn find_in(&self, binary: &[u8]) -> Option<usize> {
+        let offset = backends::find_in_slice(&self.pattern, None, binary, 0)?;
+
+        (offset + self.pattern.cursor)
+            .checked_add_signed(self.offset)
+            .filter(|&offset| offset < binary.len())
+    }
+}
+
+/// A parsed signature file
+#[derive(Default)]
+pub struct SignatureFile {
+    signatures: Vec<Signature>,
+}
+
+impl SignatureFile {
+    /// Parse the contents of a signature file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lightningscanner::sigfile::SignatureFile;
+    ///
+    /// let file = SignatureFile::parse(r#"
+    ///     // comment
+    ///     tick = "40 53 48 83 ec 20", offset = +2
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(file.get("tick").unwrap().offset, 2);
+    /// ```
+    pub fn parse(contents: &str) -> Result<SignatureFile, SigFileError> {
+        let mut file = SignatureFile::default();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let err = |kind| SigFileError::Syntax {
+                line: line_number,
+                kind,
+            };
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, rest) = line
+                .split_once('=')
+                .ok_or(err(SyntaxErrorKind::MissingPattern))?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(err(SyntaxErrorKind::InvalidName));
+            }
+            if file.get(name).is_some() {
+                return Err(err(SyntaxErrorKind::DuplicateName));
+            }
+
+            let mut fields = split_fields(rest).into_iter();
+            let text = fields
+                .next()
+                .and_then(unquote)
+                .ok_or(err(SyntaxErrorKind::MissingPattern))?;
+            let pattern = Pattern::try_new(text)
+                .map_err(|source| err(SyntaxErrorKind::InvalidPattern(source)))?;
+
+            let mut signature = Signature {
+                name: name.to_owned(),
+                pattern,
+                offset: 0,
+                module: None,
+                text: text.to_owned(),
+            };
+
+            for field in fields {
+                let (key, value) = field
+                    .split_once('=')
+                    .ok_or(err(SyntaxErrorKind::InvalidAttribute))?;
+
+                match key.trim() {
+                    "offset" => {
+                        let value = value.trim();
+                        let value = value.strip_prefix('+').unwrap_or(value);
+                        signature.offset = value
+                            .parse()
+                            .map_err(|_| err(SyntaxErrorKind::InvalidAttribute))?;
+                    }
+                    "module" => {
+                        let module =
+                            unquote(value).ok_or(err(SyntaxErrorKind::InvalidAttribute))?;
+                        signature.module = Some(module.to_owned());
+                    }
+                    _ => return Err(err(SyntaxErrorKind::UnknownAttribute)),
+                }
+            }
+
+            file.signatures.push(signature);
+        }
+
+        Ok(file)
+    }
+
+    /// Load and parse a signature file
+    pub fn load(path: impl AsRef<Path>) -> Result<SignatureFile, SigFileError> {
+        let contents = fs::read_to_string(path).map_err(SigFileError::Io)?;
+        SignatureFile::parse(&contents)
+    }
+
+    /// Write the signatures into a file that can be loaded back with [`SignatureFile::load`]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Find a signature by name
+    pub fn get(&self, name: &str) -> Option<&Signature> {
+        self.signatures
+            .iter()
+            .find(|signature| signature.name == name)
+    }
+
+    /// Iterate over the signatures in file order
+    pub fn iter(&self) -> impl Iterator<Item = &Signature> {
+        self.signatures.iter()
+    }
+
+    /// Number of signatures in the file
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Check if the file contains no signatures
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+}
+
+impl IntoIterator for SignatureFile {
+    type Item = Signature;
+    type IntoIter = std::vec::IntoIter<Signature>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.signatures.into_iter()
+    }
+}
+
+impl fmt::Display for SignatureFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for signature in &self.signatures {
+            write!(f, "{} = \"{}\"", signature.name, signature.text)?;
+
+            if signature.offset != 0 {
+                write!(f, ", offset = {:+}", signature.offset)?;
+            }
+            if let Some(module) = &signature.module {
+                write!(f, ", module = \"{}\"", module)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove a `#` or `//` comment, ignoring comment markers inside of quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+
+    for (i, symbol) in line.char_indices() {
+        match symbol {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            '/' if !quoted && line[i + 1..].starts_with('/') => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Split a line into comma-separated fields, ignoring commas inside of quotes
+fn split_fields(line: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (i, symbol) in line.char_indices() {
+        match symbol {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(&line[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&line[start..]);
+
+    fields
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value.trim().strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Error loading a signature file
+#[derive(Debug)]
+pub enum SigFileError {
+    /// Reading the file failed
+    Io(io::Error),
+    /// A line of the file is malformed
+    Syntax {
+        /// Line number, starting at 1
+        line: usize,
+        /// What is wrong with the line
+        kind: SyntaxErrorKind,
+    },
+}
+
+impl SigFileError {
+    /// Line number the error occurred at, if it's a syntax error
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            SigFileError::Io(_) => None,
+            SigFileError::Syntax { line, .. } => Some(*line),
+        }
+    }
+}
+
+/// Kind of a signature file syntax error
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SyntaxErrorKind {
+    /// The line has no `= "pattern"` part
+    MissingPattern,
+    /// The signature name is empty or contains whitespace
+    InvalidName,
+    /// A signature with the same name was already defined
+    DuplicateName,
+    /// The pattern string can't be parsed
+    InvalidPattern(PatternError),
+    /// An attribute has no value or its value is malformed
+    InvalidAttribute,
+    /// An attribute other than `offset` or `module` is used
+    UnknownAttribute,
+}
+
+impl fmt::Display for SigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigFileError::Io(err) => write!(f, "unable to read signature file: {}", err),
+            SigFileError::Syntax { line, kind } => {
+                write!(f, "line {}: ", line)?;
+                match kind {
+                    SyntaxErrorKind::MissingPattern => write!(f, "missing pattern"),
+                    SyntaxErrorKind::InvalidName => write!(f, "invalid signature name"),
+                    SyntaxErrorKind::DuplicateName => write!(f, "duplicate signature name"),
+                    SyntaxErrorKind::InvalidPattern(err) => write!(f, "{}", err),
+                    SyntaxErrorKind::InvalidAttribute => write!(f, "malformed attribute"),
+                    SyntaxErrorKind::UnknownAttribute => write!(f, "unknown attribute"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for SigFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SigFileError::Io(err) => Some(err),
+            SigFileError::Syntax {
+                kind: SyntaxErrorKind::InvalidPattern(err),
+                ..
+            } => Some(err),
+            SigFileError::Syntax { .. } => None,
+        }
+    }
+}
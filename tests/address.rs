@@ -0,0 +1,91 @@
+use lightningscanner::address::{FileOffset, Rva, VirtualAddress};
+use lightningscanner::file::Section;
+use lightningscanner::Scanner;
+
+const SECTIONS: [Section; 2] = [
+    Section {
+        file_offset: 0x400,
+        file_size: 0x200,
+        rva: 0x1000,
+    },
+    Section {
+        file_offset: 0x600,
+        file_size: 0x100,
+        rva: 0x3000,
+    },
+];
+
+#[test]
+fn conversions() {
+    let image_base = VirtualAddress(0x1_4000_0000);
+
+    assert_eq!(FileOffset(0x410).to_rva(&SECTIONS), Some(Rva(0x1010)));
+    assert_eq!(FileOffset(0x650).to_rva(&SECTIONS), Some(Rva(0x3050)));
+    assert_eq!(FileOffset(0x100).to_rva(&SECTIONS), None);
+    assert_eq!(FileOffset(0x700).to_rva(&SECTIONS), None);
+
+    assert_eq!(
+        Rva(0x1010).to_file_offset(&SECTIONS),
+        Some(FileOffset(0x410))
+    );
+    assert_eq!(Rva(0x2000).to_file_offset(&SECTIONS), None);
+
+    let va = Rva(0x1010).to_virtual_address(image_base).unwrap();
+    assert_eq!(va, VirtualAddress(0x1_4000_1010));
+    assert_eq!(va.to_rva(image_base), Some(Rva(0x1010)));
+    assert_eq!(VirtualAddress(0x1000).to_rva(image_base), None);
+    assert_eq!(Rva(1).to_virtual_address(VirtualAddress(usize::MAX)), None);
+
+    let buffer_base = VirtualAddress(0x5000);
+    assert_eq!(
+        FileOffset(0x10).to_virtual_address(buffer_base),
+        Some(VirtualAddress(0x5010))
+    );
+    assert_eq!(
+        VirtualAddress(0x5010).to_file_offset(buffer_base),
+        Some(FileOffset(0x10))
+    );
+    assert_eq!(VirtualAddress(0x4000).to_file_offset(buffer_base), None);
+}
+
+#[test]
+fn hex_formatting() {
+    assert_eq!(format!("{:#x}", Rva(0x1abc)), "0x1abc");
+    assert_eq!(format!("{:08X}", VirtualAddress(0x1abc)), "00001ABC");
+}
+
+#[test]
+fn typed_finds() {
+    let mut contents = vec![0x00; 0x700];
+    contents[0x100..0x104].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    contents[0x620..0x624].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let scanner = Scanner::new("48 89 5c 24");
+    assert_eq!(
+        scanner.find_file_offset(None, &contents),
+        Some(FileOffset(0x100))
+    );
+    // the first match is outside of every section
+    assert_eq!(
+        scanner.find_rva(None, &contents, &SECTIONS),
+        Some(Rva(0x3020))
+    );
+    assert_eq!(scanner.find_rva(None, &contents[..0x600], &SECTIONS), None);
+}
+
+#[test]
+fn scan_result_address() {
+    let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24];
+    let scanner = Scanner::new("48 89 ^ 5c 24");
+
+    // SAFETY: the array is valid for its length
+    let result = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    assert_eq!(
+        result.virtual_address(),
+        Some(VirtualAddress(binary[4..].as_ptr() as usize))
+    );
+
+    // SAFETY: the array is valid for its length
+    let missing = unsafe { Scanner::new("cc").find(None, binary.as_ptr(), binary.len()) };
+    assert_eq!(missing.virtual_address(), None);
+}
@@ -0,0 +1,85 @@
+use lightningscanner::patch::Patch;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{PrologueFilter, ScanMode, Scanner};
+use std::ptr;
+use std::sync::atomic::AtomicBool;
+
+const BINARY: [u8; 8] = [0xab, 0x48, 0x89, 0x5c, 0x24, 0x10, 0xcc, 0xcc];
+
+/// Sizes which make the binary at `ptr` extend past the end of the address space
+fn wrapping_sizes(ptr: *const u8) -> [usize; 3] {
+    [
+        usize::MAX,
+        usize::MAX - ptr as usize + 1,
+        usize::MAX - ptr as usize + 0x1000,
+    ]
+}
+
+#[test]
+fn first_match() {
+    let scanner = Scanner::new("48 89 5c 24 ??");
+    let binary = BINARY.as_ptr();
+
+    for size in wrapping_sizes(binary) {
+        for mode in [None, Some(ScanMode::Scalar), Some(ScanMode::Avx2)] {
+            // SAFETY: the range is rejected before the pointer is read
+            assert!(!unsafe { scanner.find(mode, binary, size) }.is_valid());
+        }
+
+        // SAFETY: the range is rejected before the pointer is read
+        unsafe {
+            assert!(!scanner
+                .find_prologue(None, binary, size, PrologueFilter::default())
+                .is_valid());
+            assert!(!scanner
+                .find_excluding(&Pattern::new("c3"), None, binary, size)
+                .is_valid());
+            assert!(!scanner
+                .find_aligned_to_instruction(None, binary, size, |_| 1)
+                .is_valid());
+            assert!(!scanner
+                .find_with_progress(None, binary, size, |_| {})
+                .is_valid());
+            assert_eq!(
+                scanner.find_cancellable(None, binary, size, &AtomicBool::new(false)),
+                None
+            );
+        }
+    }
+}
+
+#[test]
+fn all_matches() {
+    let scanner = Scanner::new("48 89 5c 24 ??");
+    let binary = BINARY.as_ptr();
+
+    for size in wrapping_sizes(binary) {
+        // SAFETY: the range is rejected before the pointer is read
+        unsafe {
+            assert!(scanner.find_all(None, binary, size).is_empty());
+            assert!(scanner.find_all_limited(None, binary, size, 4).is_empty());
+            assert!(scanner
+                .find_all_by_page(None, binary, size, 4096)
+                .is_empty());
+        }
+    }
+
+    // null pointers are rejected as well
+    // SAFETY: the pointer is rejected before being read
+    assert!(unsafe { scanner.find_all(None, ptr::null(), 16) }.is_empty());
+}
+
+#[test]
+fn patching() {
+    let mut region = BINARY;
+    let region_ptr = region.as_mut_ptr();
+
+    for size in wrapping_sizes(region_ptr) {
+        // SAFETY: the range is rejected before the pointer is read or written
+        let patched = unsafe {
+            Scanner::new("48 89").patch_at_matches(region_ptr, size, &Patch::new(0, vec![0x90]))
+        };
+        assert_eq!(patched, 0);
+    }
+    assert_eq!(region, BINARY);
+}
@@ -0,0 +1,85 @@
+use lightningscanner::aligned_bytes::AlignedBytes;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{create_pattern, ScanMode, Scanner};
+
+const DATA_SET: [u8; 32] = [
+    0xdb, 0x2f, 0x16, 0x37, 0xd5, 0xff, 0x12, 0x74, 0x7c, 0xf2, 0x27, 0xed, 0x7b, 0x2e, 0x54, 0x9a,
+    0xe2, 0xec, 0x73, 0x9e, 0xbb, 0xd1, 0x42, 0xc2, 0x0c, 0x9e, 0xa3, 0xa1, 0x10, 0xb3, 0x97, 0xf2,
+];
+
+fn find(pattern: Pattern) -> usize {
+    let scanner = Scanner::from(pattern);
+    // SAFETY: DATA_SET is a valid slice
+    let result = unsafe { scanner.find(Some(ScanMode::Avx2), DATA_SET.as_ptr(), DATA_SET.len()) };
+
+    result.get_addr() as usize - DATA_SET.as_ptr() as usize
+}
+
+#[test]
+fn aligned_bytes() {
+    let data = [0xaa; 100];
+
+    let aligned = AlignedBytes::<64>::new(&data);
+    assert_eq!(aligned.as_ptr() as usize % 64, 0);
+    assert_eq!(&aligned[..], &data[..]);
+
+    let aligned = AlignedBytes::<32>::new(&data);
+    assert_eq!(aligned.as_ptr() as usize % 32, 0);
+}
+
+#[test]
+fn aligned_bytes_layout() {
+    // the allocation has to match the padded layout the box is deallocated with
+    for len in [1, 3, 63, 64, 65, 128] {
+        let aligned = AlignedBytes::<64>::new(&vec![0x55; len]);
+
+        assert_eq!(aligned.len(), len);
+        assert_eq!(std::mem::size_of_val(&*aligned), len.next_multiple_of(64));
+        assert_eq!(std::mem::align_of_val(&*aligned), 64);
+    }
+}
+
+#[test]
+fn aligned_bytes_empty() {
+    // empty buffers still hold a whole vector of zeroes
+    let aligned = AlignedBytes::<64>::new(&[]);
+    assert_eq!(aligned.as_ptr() as usize % 64, 0);
+    assert_eq!(&aligned[..], &[0; 64][..]);
+    assert_eq!(std::mem::size_of_val(&*aligned), 64);
+
+    let aligned = AlignedBytes::<32>::new(&[]);
+    assert_eq!(aligned.as_ptr() as usize % 32, 0);
+    assert_eq!(&aligned[..], &[0; 32][..]);
+
+    // empty patterns are scanned without reading past the buffers
+    let scanner = Scanner::from(Pattern::from_parts(
+        AlignedBytes::<32>::new(&[]),
+        AlignedBytes::<32>::new(&[]),
+        0,
+    ));
+    assert_eq!(
+        scanner.find_all_offsets(Some(ScanMode::Scalar), &[0x90; 3]),
+        [0, 1, 2, 3]
+    );
+}
+
+#[test]
+fn from_32_byte_parts() {
+    let mut data = vec![0; 32];
+    let mut mask = vec![0; 32];
+    data[..3].copy_from_slice(&[0x9e, 0xbb, 0xd1]);
+    mask[..3].copy_from_slice(&[0xff, 0x00, 0xff]);
+
+    let pattern = Pattern::from_parts(
+        AlignedBytes::<32>::new(&data),
+        AlignedBytes::<32>::new(&mask),
+        3,
+    );
+    assert_eq!(find(pattern), 0x13);
+}
+
+#[test]
+fn macro_alignment() {
+    let pattern = create_pattern!("9e ?? d1 42 c2 0c 9e a3 a1 10 b3 97 f2");
+    assert_eq!(find(pattern), 0x13);
+}
@@ -0,0 +1,52 @@
+use lightningscanner::const_parser::parse_pattern;
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn rejected() {
+    for pattern in [
+        "??",
+        "? ?",
+        "?? ?? ??",
+        "?{16}",
+        "?? ^ ?? @end",
+        "?? // 48 8b",
+    ] {
+        assert_eq!(
+            Pattern::try_new(pattern).err(),
+            Some(PatternError::AllWildcards),
+            "{}",
+            pattern
+        );
+    }
+
+    assert_eq!(
+        Pattern::try_new_strict("?? ??").err(),
+        Some(PatternError::AllWildcards)
+    );
+}
+
+#[test]
+fn accepted() {
+    assert!(Pattern::try_new("").is_ok());
+    assert!(Pattern::try_new("// nothing").is_ok());
+    assert!(Pattern::try_new("?{15} c3").is_ok());
+    assert!(Pattern::try_new("48 ??").is_ok());
+}
+
+#[test]
+#[should_panic(expected = "invalid pattern: pattern consists of wildcards only")]
+fn panics() {
+    Pattern::new("?? ??");
+}
+
+#[test]
+#[should_panic(expected = "Pattern consists of wildcards only")]
+fn rejected_at_compile_time() {
+    parse_pattern::<4>("?? ?{3}");
+}
+
+#[test]
+fn accepted_at_compile_time() {
+    assert_eq!(parse_pattern::<0>("").len, 0);
+    assert_eq!(parse_pattern::<2>("?? c3").len, 2);
+}
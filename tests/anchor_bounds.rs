@@ -0,0 +1,49 @@
+//! Patterns whose anchor byte occurs right at the start of the haystack, before which the
+//! rest of the pattern doesn't fit
+
+use lightningscanner::multi::MultiScanner;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+const MODES: [Option<ScanMode>; 4] = [
+    None,
+    Some(ScanMode::Scalar),
+    Some(ScanMode::Sse42),
+    Some(ScanMode::Avx2),
+];
+
+#[test]
+fn anchor_at_the_start() {
+    // the byte fast path anchors on `c3`, the scalar backend on the last concrete byte
+    for (pattern, len) in [("?? c3", 2), ("48 ?? c3", 3), ("48 89 ?? ?? ?? c3", 300)] {
+        let scanner = Scanner::new(pattern);
+
+        let mut binary = vec![0x00; len];
+        binary[0] = 0xc3;
+
+        for mode in MODES {
+            assert_eq!(
+                scanner.find_all_offsets(mode, &binary),
+                [0usize; 0],
+                "{} with {:?}",
+                pattern,
+                mode
+            );
+
+            // SAFETY: binary is a valid slice
+            let result = unsafe { scanner.find(mode, binary.as_ptr(), binary.len()) };
+            assert!(!result.is_valid(), "{} with {:?}", pattern, mode);
+        }
+    }
+}
+
+#[test]
+fn anchor_run_at_the_start() {
+    // anchored on its longest concrete run, which starts three bytes into the pattern
+    let scanner = MultiScanner::new([Pattern::new("48 ?? ?? 8b 05 c3")]);
+    let binary = [0x8b, 0x05, 0xc3, 0x48, 0x00, 0x00, 0x8b, 0x05, 0xc3];
+
+    assert_eq!(scanner.find_all(&binary), [(0, 3)]);
+    assert_eq!(scanner.find_first_any(&binary), Some((0, 3)));
+    assert_eq!(scanner.find_first_any(&binary[..3]), None);
+}
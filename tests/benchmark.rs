@@ -0,0 +1,41 @@
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn measures_supported_modes() {
+    let haystack = vec![0xcc; 0x10000];
+
+    let scanner = Scanner::new("48 89 5c 24 ?? 57");
+    let results = scanner.benchmark(&haystack, 4);
+
+    assert_eq!(results[0].0, ScanMode::Scalar);
+    assert!(results.iter().all(|&(_, throughput)| throughput > 0.0));
+
+    #[cfg(target_feature = "avx2")]
+    assert!(results.iter().any(|&(mode, _)| mode == ScanMode::Avx2));
+}
+
+#[test]
+fn zero_iterations() {
+    let scanner = Scanner::new("48");
+    let results = scanner.benchmark(&[0x48; 64], 0);
+
+    assert!(results
+        .iter()
+        .all(|&(_, throughput)| throughput.is_finite()));
+}
+
+#[test]
+fn measures_default_scan() {
+    let haystack = vec![0xcc; 0x10000];
+
+    let scanner = Scanner::new("48 89 5c 24 ?? 57");
+    let bench = scanner.benchmark_find(&haystack, 4);
+
+    assert_eq!(bench.bytes, 4 * haystack.len());
+    assert!(bench.gibps > 0.0 && bench.gibps.is_finite());
+
+    // zero iterations still scan once
+    let bench = Scanner::new("48").benchmark_find(&[0x48; 64], 0);
+    assert_eq!(bench.bytes, 64);
+    assert!(bench.gibps.is_finite());
+}
@@ -0,0 +1,74 @@
+use lightningscanner::Scanner;
+
+const BINARY: [u8; 16] = [
+    0x48, 0x8b, 0x00, 0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20, 0xc3, 0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20,
+];
+
+#[test]
+fn longest_prefix() {
+    let scanner = Scanner::new("48 8b 05 ?? ?? 84 c0");
+
+    // the occurrence at 0x0b is just as long, but cut off by the end of the binary
+    assert_eq!(scanner.find_best_partial(&BINARY), Some((4, 5)));
+    assert_eq!(scanner.find_best_partial(&BINARY[5..]), Some((6, 5)));
+    assert_eq!(scanner.find_best_partial(&BINARY[12..]), None);
+}
+
+#[test]
+fn full_match() {
+    let scanner = Scanner::new("48 8b 05 ?? ?? c3");
+
+    assert_eq!(scanner.find_best_partial(&BINARY), Some((4, 6)));
+}
+
+#[test]
+fn no_match() {
+    let scanner = Scanner::new("e8 ?? ?? ?? ??");
+
+    assert_eq!(scanner.find_best_partial(&BINARY), None);
+    assert_eq!(scanner.find_best_partial(&[]), None);
+    assert_eq!(Scanner::new("").find_best_partial(&BINARY), None);
+}
+
+#[test]
+fn all_tied_prefixes() {
+    let scanner = Scanner::new("48 8b 05 ?? ?? 84 c0");
+
+    // the cut off occurrence at 0x0b ties with the one at 4
+    assert_eq!(scanner.find_best_partial_all(&BINARY), [(4, 5), (11, 5)]);
+    assert_eq!(scanner.find_best_partial_all(&BINARY[12..]), []);
+}
+
+#[test]
+fn all_full_matches() {
+    let scanner = Scanner::new("48 8b");
+
+    assert_eq!(
+        scanner.find_best_partial_all(&BINARY),
+        [(0, 2), (4, 2), (11, 2)]
+    );
+    assert_eq!(Scanner::new("").find_best_partial_all(&BINARY), []);
+}
+
+#[test]
+fn all_agrees_with_first() {
+    for pattern in [
+        "48 8b 05 ?? ?? 84 c0",
+        "48 8b 05 ?? ?? c3",
+        "cc 48",
+        "e8 ??",
+    ] {
+        let scanner = Scanner::new(pattern);
+
+        for start in 0..BINARY.len() {
+            let all = scanner.find_best_partial_all(&BINARY[start..]);
+            assert_eq!(
+                all.first().copied(),
+                scanner.find_best_partial(&BINARY[start..]),
+                "{} at {}",
+                pattern,
+                start
+            );
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+const BINARY: [u8; 20] = [
+    0xe8, 0x01, 0x02, 0x03, 0x04, 0x84, 0xc0, 0xcc, 0xe8, 0x05, 0x06, 0x07, 0x08, 0x90, 0x90, 0x90,
+    0x84, 0xc0, 0x74, 0x05,
+];
+
+#[test]
+fn gap_bounds_are_inclusive() {
+    let scanner = Scanner::new("e8 ?? ?? ?? ??");
+    let second = Pattern::new("84 c0");
+
+    assert_eq!(
+        scanner.find_bounded_gap(&second, 0, 0, &BINARY),
+        Some((0, 5))
+    );
+    assert_eq!(
+        scanner.find_bounded_gap(&second, 3, 3, &BINARY),
+        Some((8, 16))
+    );
+    assert_eq!(
+        scanner.find_bounded_gap(&second, 1, 3, &BINARY),
+        Some((8, 16))
+    );
+    assert_eq!(scanner.find_bounded_gap(&second, 1, 2, &BINARY), None);
+    assert_eq!(scanner.find_bounded_gap(&second, 4, 10, &BINARY), None);
+}
+
+#[test]
+fn second_cut_off() {
+    let scanner = Scanner::new("84 c0");
+
+    // the second pattern would extend past the end of the binary
+    assert_eq!(
+        scanner.find_bounded_gap(&Pattern::new("74 05 ??"), 0, 8, &BINARY),
+        None
+    );
+    assert_eq!(
+        scanner.find_bounded_gap(&Pattern::new("74 ??"), 0, 8, &BINARY),
+        Some((16, 18))
+    );
+    assert_eq!(
+        scanner.find_bounded_gap(&Pattern::new("74"), usize::MAX, usize::MAX, &BINARY),
+        None
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "byte-filter")]
+
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn finds_first_match() {
+    let haystack = [
+        0x48, 0x8b, 0x05, 0x00, 0x48, 0x8b, 0x05, 0xc3, 0x48, 0x8b, 0x00, 0xc3,
+    ];
+
+    assert_eq!(
+        Scanner::new("48 8b ?? c3").find_two_pass_filtered(&haystack),
+        Some(4)
+    );
+    assert_eq!(
+        Scanner::new("48 8b 05 c4").find_two_pass_filtered(&haystack),
+        None
+    );
+}
+
+#[test]
+fn candidates_at_the_edges() {
+    // the anchor is the last byte, its first hit leaves no room before it
+    let scanner = Scanner::new("01 02 03 ff");
+    assert_eq!(
+        scanner.find_two_pass_filtered(&[0xff, 0x01, 0x02, 0x03]),
+        None
+    );
+    assert_eq!(
+        scanner.find_two_pass_filtered(&[0xff, 0x01, 0x02, 0x03, 0xff]),
+        Some(1)
+    );
+
+    // the anchor is the first byte, its last hit leaves no room after it
+    let scanner = Scanner::new("ff 01 02 03");
+    assert_eq!(
+        scanner.find_two_pass_filtered(&[0x01, 0x02, 0xff, 0x01]),
+        None
+    );
+}
+
+#[test]
+fn patterns_without_probes() {
+    let haystack = [0x90, 0x48, 0x8b, 0x05];
+
+    assert_eq!(
+        Scanner::new("8b").find_two_pass_filtered(&haystack),
+        Some(2)
+    );
+    assert_eq!(
+        Scanner::from(Pattern::from_masked_bytes(&[0x00, 0x08], &[0x00, 0x0f]))
+            .find_two_pass_filtered(&haystack),
+        Some(0)
+    );
+    assert_eq!(Scanner::new("").find_two_pass_filtered(&haystack), Some(0));
+}
+
+#[test]
+fn same_as_regular_scan() {
+    let mut rand = Wyrand::seed(0x6279_7465);
+
+    for _ in 0..500 {
+        // a small alphabet makes the probed bytes hit the filter often
+        let len = rand.next_lim_usize(4096);
+        let haystack = (0..len)
+            .map(|_| [0x00, 0x48, 0x8b, 0xc3][rand.next_lim_usize(4)])
+            .collect::<Vec<_>>();
+
+        let size = 1 + rand.next_lim_usize(8);
+        let data = (0..size)
+            .map(|_| [0x00, 0x48, 0x8b, 0xc3, 0xcc][rand.next_lim_usize(5)])
+            .collect::<Vec<_>>();
+        let mask = (0..size)
+            .map(|_| [0x00, 0xff, 0xff, 0x0f][rand.next_lim_usize(4)])
+            .collect::<Vec<_>>();
+        let scanner = Scanner::from(Pattern::from_masked_bytes(&data, &mask));
+
+        assert_eq!(
+            scanner.find_two_pass_filtered(&haystack),
+            scanner.find_two_pass(&haystack),
+            "{:x?} {:x?}",
+            data,
+            mask
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use lightningscanner::cache::ScanCache;
+use lightningscanner::Scanner;
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+const BLOCK_SIZE: usize = 256;
+
+#[test]
+fn random_mutations() {
+    let mut rand = Wyrand::seed(0xcace);
+
+    for pattern in ["00 01 ?? 02", "03", "01 ?? ?? ?? ?? ?? 01 00", ""] {
+        let scanner = Scanner::new(pattern);
+        let mut cache = ScanCache::new().with_block_size(BLOCK_SIZE);
+
+        let mut haystack = (0..4096)
+            .map(|_| rand.next_lim_u16(4) as u8)
+            .collect::<Vec<_>>();
+
+        for _ in 0..200 {
+            // a few bytes, a whole range, or the length of the haystack changes
+            if rand.next_bool(Probability::new(0.1)) {
+                let len = 3000 + rand.next_lim_usize(2000);
+                haystack.resize_with(len, || rand.next_lim_u16(4) as u8);
+            } else if rand.next_bool(Probability::new(0.2)) {
+                let start = rand.next_lim_usize(haystack.len());
+                let end = (start + rand.next_lim_usize(600)).min(haystack.len());
+                haystack[start..end].fill(rand.next_lim_u16(4) as u8);
+            } else {
+                for _ in 0..rand.next_lim_usize(4) {
+                    let offset = rand.next_lim_usize(haystack.len());
+                    haystack[offset] = rand.next_lim_u16(4) as u8;
+                }
+            }
+
+            assert_eq!(
+                cache.scan(&scanner, &haystack),
+                scanner.find_all_offsets(None, &haystack),
+                "{}",
+                pattern
+            );
+        }
+    }
+}
+
+#[test]
+fn reuses_unchanged_blocks() {
+    let mut haystack = vec![0x00; 16 * BLOCK_SIZE];
+    let scanner = Scanner::new("48 89 5c 24");
+    let mut cache = ScanCache::new().with_block_size(BLOCK_SIZE);
+
+    assert!(cache.scan(&scanner, &haystack).is_empty());
+    assert_eq!(cache.rescanned_blocks(), 16);
+
+    assert!(cache.scan(&scanner, &haystack).is_empty());
+    assert_eq!(cache.rescanned_blocks(), 0);
+
+    // a match straddling the boundary between blocks 4 and 5
+    haystack[5 * BLOCK_SIZE - 2..5 * BLOCK_SIZE + 2].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    assert_eq!(cache.scan(&scanner, &haystack), [5 * BLOCK_SIZE - 2]);
+    assert_eq!(cache.rescanned_blocks(), 3);
+    assert_eq!(cache.matches(), [5 * BLOCK_SIZE - 2]);
+
+    // breaking the match in block 5 rescans block 4, which it starts in
+    haystack[5 * BLOCK_SIZE + 1] = 0x00;
+    assert!(cache.scan(&scanner, &haystack).is_empty());
+    assert_eq!(cache.rescanned_blocks(), 2);
+}
+
+#[test]
+fn shrinking_haystack() {
+    let mut haystack = vec![0x00; 4 * BLOCK_SIZE];
+    haystack[2 * BLOCK_SIZE - 2..2 * BLOCK_SIZE + 2].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let scanner = Scanner::new("48 89 5c 24");
+    let mut cache = ScanCache::new().with_block_size(BLOCK_SIZE);
+    assert_eq!(cache.scan(&scanner, &haystack), [2 * BLOCK_SIZE - 2]);
+
+    // the match no longer fits, although the block it starts in is unchanged
+    assert!(cache.scan(&scanner, &haystack[..2 * BLOCK_SIZE]).is_empty());
+    assert!(cache.scan(&scanner, &[]).is_empty());
+}
+
+#[test]
+fn empty_pattern_growing_haystack() {
+    let scanner = Scanner::new("");
+    let mut cache = ScanCache::new().with_block_size(4);
+
+    assert_eq!(cache.scan(&scanner, &[0; 8]), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(
+        cache.scan(&scanner, &[0; 12]),
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+    );
+    assert_eq!(cache.scan(&scanner, &[0; 8]), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn different_scanner() {
+    let haystack = [0x48, 0x89, 0x5c, 0x24, 0xc3];
+    let mut cache = ScanCache::new();
+
+    assert_eq!(cache.scan(&Scanner::new("48 89"), &haystack), [0]);
+    assert_eq!(cache.scan(&Scanner::new("c3"), &haystack), [4]);
+    assert_eq!(cache.rescanned_blocks(), 1);
+
+    cache.clear();
+    assert!(cache.matches().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "block size must not be zero")]
+fn zero_block_size() {
+    let _ = ScanCache::new().with_block_size(0);
+}
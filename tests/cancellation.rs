@@ -0,0 +1,39 @@
+use lightningscanner::{ScanMode, Scanner};
+use std::sync::atomic::AtomicBool;
+
+const INTERVAL: usize = Scanner::CANCEL_CHECK_INTERVAL;
+
+#[test]
+fn finds_matches_when_not_cancelled() {
+    let mut binary = vec![0x00; 10 * INTERVAL];
+    binary[7 * INTERVAL - 1..7 * INTERVAL + 3].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let cancel = AtomicBool::new(false);
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        // the cursor doesn't change the reported offset
+        let scanner = Scanner::new("48 89 ^ ?? 24");
+        // SAFETY: the vector is valid for its length
+        let result =
+            unsafe { scanner.find_cancellable(mode, binary.as_ptr(), binary.len(), &cancel) };
+
+        assert_eq!(result, Some(7 * INTERVAL - 1));
+    }
+
+    let scanner = Scanner::new("48 89 5c 25");
+    // SAFETY: the vector is valid for its length
+    let result = unsafe { scanner.find_cancellable(None, binary.as_ptr(), binary.len(), &cancel) };
+    assert_eq!(result, None);
+}
+
+#[test]
+fn cancelled_before_the_scan() {
+    let binary = [0x48, 0x89, 0x5c, 0x24];
+    let cancel = AtomicBool::new(true);
+
+    let scanner = Scanner::new("48 89 5c 24");
+    // SAFETY: the array is valid for its length
+    let result = unsafe { scanner.find_cancellable(None, binary.as_ptr(), binary.len(), &cancel) };
+
+    assert_eq!(result, None);
+}
@@ -0,0 +1,60 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn equivalent_spellings() {
+    let spellings = [
+        "48 89 5c 24 ?? 48 89 6c",
+        "4889 5C24 ? 4889 6C",
+        "48  89\t5c\n24 ?{1} 48 89 6c",
+        "48 89 5C 24 ?? /* saved rbx */ 48 89 6C // saved rbp",
+        "48895C24??48896C",
+    ];
+
+    for spelling in spellings {
+        assert_eq!(
+            Pattern::canonicalize(spelling).as_deref(),
+            Ok("48 89 5C 24 ?? 48 89 6C"),
+            "{}",
+            spelling
+        );
+    }
+}
+
+#[test]
+fn keeps_cursor_and_labels() {
+    assert_eq!(
+        Pattern::canonicalize("e8 ^ @callTarget ?{4} @Next c3").as_deref(),
+        Ok("E8 ^ @callTarget ?? ?? ?? ?? @Next C3")
+    );
+    assert_eq!(
+        Pattern::canonicalize("@start 8b ?? c3 ^").as_deref(),
+        Ok("@start 8B ?? C3 ^")
+    );
+}
+
+#[test]
+fn round_trips() {
+    for pattern in [
+        "48 8b 05 ?{4} c3",
+        "e8 ?? ?? ?? ?? ^ 48 8b @disp ??",
+        "90 ?? ??",
+        "",
+    ] {
+        let canonical = Pattern::canonicalize(pattern).unwrap();
+
+        assert_eq!(Pattern::new(&canonical), Pattern::new(pattern));
+        assert_eq!(Pattern::canonicalize(&canonical), Ok(canonical));
+    }
+}
+
+#[test]
+fn malformed_patterns() {
+    assert_eq!(
+        Pattern::canonicalize("48 8g"),
+        Err(PatternError::InvalidByte { position: 3 })
+    );
+    assert_eq!(
+        Pattern::canonicalize("?? ??"),
+        Err(PatternError::AllWildcards)
+    );
+}
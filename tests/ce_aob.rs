@@ -0,0 +1,54 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn same_as_ida_strings() {
+    let cases = [
+        ("488B05????????C3", "48 8b 05 ?? ?? ?? ?? c3"),
+        ("48 8B 05 * * * * C3", "48 8b 05 ?? ?? ?? ?? c3"),
+        ("48 8B 05 ? ? ?? * C3", "48 8b 05 ?? ?? ?? ?? c3"),
+        ("488b05**c3", "48 8b 05 ?? ?? c3"),
+        ("  48\t8B\n", "48 8b"),
+        ("", ""),
+    ];
+
+    for (aob, ida) in cases {
+        assert_eq!(Pattern::from_ce_aob(aob), Ok(Pattern::new(ida)), "{}", aob);
+    }
+}
+
+#[test]
+fn only_wildcards() {
+    for aob in ["*", "?", "* ?? ?", "????"] {
+        assert_eq!(
+            Pattern::from_ce_aob(aob),
+            Err(PatternError::AllWildcards),
+            "{}",
+            aob
+        );
+    }
+}
+
+#[test]
+fn malformed_aobs() {
+    let cases = [("488B0", 4), ("48 8 C3", 3), ("8", 0)];
+
+    for (aob, position) in cases {
+        assert_eq!(
+            Pattern::from_ce_aob(aob),
+            Err(PatternError::OddNibbleCount { position }),
+            "{}",
+            aob
+        );
+    }
+
+    let cases = [("48 8B?", 5), ("48?8B", 2), ("48 4? C3", 3), ("48 gg", 3)];
+
+    for (aob, position) in cases {
+        assert_eq!(
+            Pattern::from_ce_aob(aob),
+            Err(PatternError::InvalidByte { position }),
+            "{}",
+            aob
+        );
+    }
+}
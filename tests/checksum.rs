@@ -0,0 +1,64 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
+#[test]
+fn skips_matches_with_other_contents() {
+    let scanner = Scanner::new("48 8b 05 ?? ?? ?? ??");
+    let haystack = [
+        0x48, 0x8b, 0x05, 0x01, 0x00, 0x00, 0x00, 0x90, 0x48, 0x8b, 0x05, 0x02, 0x00, 0x00, 0x00,
+    ];
+
+    let expected = fnv1a(&haystack[8..]);
+    assert_eq!(
+        scanner.find_with_checksum(&haystack, expected, fnv1a),
+        Some(8)
+    );
+    assert_eq!(
+        scanner.find_with_checksum(&haystack, expected ^ 1, fnv1a),
+        None
+    );
+}
+
+#[test]
+fn hashes_exactly_the_matched_bytes() {
+    let scanner = Scanner::new("cc ?? c3");
+    let haystack = [0x00, 0xcc, 0x07, 0xc3, 0x00];
+
+    let seen = std::cell::RefCell::new(Vec::new());
+    let found = scanner.find_with_checksum(&haystack, 0, |bytes| {
+        seen.borrow_mut().push(bytes.to_vec());
+        0
+    });
+
+    assert_eq!(found, Some(1));
+    assert_eq!(seen.into_inner(), [vec![0xcc, 0x07, 0xc3]]);
+}
+
+#[test]
+fn matches_naive_search() {
+    let mut rand = Wyrand::seed(0xc4ec);
+
+    for _ in 0..1000 {
+        let scanner = Scanner::new(&format!("{:02x} ??", rand.next_lim_u16(2)));
+        let haystack = (0..rand.next_lim_usize(64))
+            .map(|_| rand.next_lim_u16(4) as u8)
+            .collect::<Vec<_>>();
+        let expected = fnv1a(&[rand.next_lim_u16(2) as u8, rand.next_lim_u16(4) as u8]);
+
+        let naive = scanner
+            .find_all_offsets(None, &haystack)
+            .into_iter()
+            .find(|&offset| fnv1a(&haystack[offset..offset + 2]) == expected);
+
+        assert_eq!(
+            scanner.find_with_checksum(&haystack, expected, fnv1a),
+            naive
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+const PATTERN: &str = "48 89 5c 24 ?? 57";
+const MATCH: [u8; 6] = [0x48, 0x89, 0x5c, 0x24, 0x10, 0x57];
+
+/// Split `data` at the given offsets
+fn split<'a>(data: &'a [u8], at: &[usize]) -> Vec<&'a [u8]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    for &end in at {
+        chunks.push(&data[start..end]);
+        start = end;
+    }
+    chunks.push(&data[start..]);
+    chunks
+}
+
+fn data_with_match(len: usize, at: usize) -> Vec<u8> {
+    let mut data = vec![0xcc; len];
+    data[at..at + MATCH.len()].copy_from_slice(&MATCH);
+    data
+}
+
+#[test]
+fn match_on_every_boundary_position() {
+    let scanner = Scanner::new(PATTERN);
+    let data = data_with_match(64, 29);
+
+    // the boundary falls before, inside and right after the match
+    for boundary in 24..=40 {
+        let chunks = split(&data, &[boundary]);
+        assert_eq!(
+            scanner.find_in_chunks(&chunks),
+            Some(29),
+            "boundary {}",
+            boundary
+        );
+    }
+}
+
+#[test]
+fn match_spanning_several_chunks() {
+    let scanner = Scanner::new(PATTERN);
+    let data = data_with_match(32, 10);
+
+    let chunks = split(&data, &[11, 12, 12, 14, 15]);
+    assert_eq!(scanner.find_in_chunks(&chunks), Some(10));
+
+    let bytes = split(&data, &(1..32).collect::<Vec<_>>());
+    assert_eq!(scanner.find_in_chunks(&bytes), Some(10));
+}
+
+#[test]
+fn not_found() {
+    let scanner = Scanner::new(PATTERN);
+    let data = data_with_match(32, 26);
+
+    // the match is cut off by the end of the last chunk
+    let chunks = split(&data[..31], &[28]);
+    assert_eq!(scanner.find_in_chunks(&chunks), None);
+    assert_eq!(scanner.find_in_chunks(&[]), None);
+}
+
+#[test]
+fn matches_contiguous_scan() {
+    let mut rand = Wyrand::seed(0xc4a2);
+
+    for _ in 0..500 {
+        let data = (0..rand.next_lim_usize(256))
+            .map(|_| rand.next_lim_usize(3) as u8)
+            .collect::<Vec<_>>();
+
+        let mut at = (0..rand.next_lim_usize(8))
+            .map(|_| rand.next_lim_usize(data.len() + 1))
+            .collect::<Vec<_>>();
+        at.sort_unstable();
+
+        let scanner = Scanner::new("00 ?? 01 02 ?? 00 01");
+        let expected = scanner
+            .find_all_offsets(None, &data)
+            .first()
+            .map(|&offset| offset as u64);
+
+        assert_eq!(scanner.find_in_chunks(&split(&data, &at)), expected);
+    }
+}
+
+#[test]
+fn scattered_segments() {
+    let scanner = Scanner::new(PATTERN);
+    let data = data_with_match(32, 13);
+
+    let contiguous = [(0x1000, &data[..16]), (0x1010, &data[16..])];
+    assert_eq!(scanner.find_scattered(&contiguous), Some(0x100d));
+
+    // the same bytes, but the second half is mapped elsewhere
+    let gap = [(0x1000, &data[..16]), (0x2000, &data[16..])];
+    assert_eq!(scanner.find_scattered(&gap), None);
+
+    let independent = [
+        (0x1000, &data[..16]),
+        (0x3000, &data[..]),
+        (0x3020, &data[..8]),
+    ];
+    assert_eq!(scanner.find_scattered(&independent), Some(0x300d));
+
+    // contiguity is only checked between consecutive segments
+    let reordered = [(0x1010, &data[16..]), (0x1000, &data[..16])];
+    assert_eq!(scanner.find_scattered(&reordered), None);
+    assert_eq!(scanner.find_scattered(&[]), None);
+}
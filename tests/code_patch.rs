@@ -0,0 +1,135 @@
+#![cfg(all(feature = "patch", any(target_os = "linux", target_os = "android")))]
+
+use lightningscanner::{process, ScanMode, Scanner};
+use std::{io, ptr, slice};
+
+struct Pages {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Pages {
+    fn new(count: usize, protection: libc::c_int) -> Self {
+        let len = count * page_size();
+
+        // SAFETY: a fresh anonymous mapping is requested
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                protection,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert_ne!(ptr, libc::MAP_FAILED);
+
+        Pages {
+            ptr: ptr as *mut u8,
+            len,
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: the mapping is readable and lives as long as `self`
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn permissions(&self) -> Vec<(bool, bool, bool)> {
+        let start = self.ptr as usize;
+
+        process::regions(std::process::id())
+            .unwrap()
+            .into_iter()
+            .filter(|region| region.start < start + self.len && region.end > start)
+            .map(|region| (region.readable, region.writable, region.executable))
+            .collect()
+    }
+}
+
+impl Drop for Pages {
+    fn drop(&mut self) {
+        // SAFETY: the mapping isn't used anymore
+        unsafe { libc::munmap(self.ptr as *mut _, self.len) };
+    }
+}
+
+fn page_size() -> usize {
+    // SAFETY: querying the page size has no preconditions
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[test]
+fn patches_read_only_memory_across_pages() {
+    let pages = Pages::new(2, libc::PROT_READ);
+    let offset = page_size() - 2;
+
+    // SAFETY: the whole range is within the mapping, nothing else uses it
+    let result = unsafe {
+        Scanner::new("00 00 00 00").find(Some(ScanMode::Scalar), pages.ptr.add(offset), 4)
+    };
+    assert!(result.is_valid());
+
+    // SAFETY: the result points into the anonymous mapping, which only this test uses
+    unsafe { result.patch_bytes(&[0xde, 0xad, 0xbe, 0xef]).unwrap() };
+
+    assert_eq!(&pages.bytes()[offset..offset + 4], [0xde, 0xad, 0xbe, 0xef]);
+    assert!(pages.bytes()[..offset].iter().all(|&byte| byte == 0));
+    assert!(pages.bytes()[offset + 4..].iter().all(|&byte| byte == 0));
+
+    assert_eq!(pages.permissions(), [(true, false, false)]);
+}
+
+#[test]
+fn keeps_executable_permission() {
+    let pages = Pages::new(1, libc::PROT_READ | libc::PROT_EXEC);
+
+    // SAFETY: the mapping starts with a zero byte
+    let result = unsafe { Scanner::new("00").find(None, pages.ptr, pages.len) };
+
+    // SAFETY: nothing is executed from the mapping
+    unsafe { result.patch_bytes(&[0xc3]).unwrap() };
+
+    assert_eq!(pages.bytes()[0], 0xc3);
+    assert_eq!(pages.permissions(), [(true, false, true)]);
+}
+
+#[test]
+fn rejects_unmapped_ranges() {
+    let pages = Pages::new(2, libc::PROT_READ);
+
+    // SAFETY: the second page isn't used anymore
+    assert_eq!(
+        unsafe { libc::munmap(pages.ptr.add(page_size()) as *mut _, page_size()) },
+        0
+    );
+    let first_page = Pages {
+        ptr: pages.ptr,
+        len: page_size(),
+    };
+    std::mem::forget(pages);
+
+    let offset = page_size() - 1;
+
+    // SAFETY: the searched byte is within the mapped page
+    let result = unsafe { Scanner::new("00").find(None, first_page.ptr.add(offset), 1) };
+
+    // SAFETY: the patch is rejected as the range isn't fully mapped
+    let err = unsafe { result.patch_bytes(&[0x90, 0x90]).unwrap_err() };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    assert!(first_page.bytes().iter().all(|&byte| byte == 0));
+    assert_eq!(first_page.permissions(), [(true, false, false)]);
+}
+
+#[test]
+fn rejects_invalid_results() {
+    // SAFETY: the slice is valid for its length
+    let result = unsafe { Scanner::new("ff").find(None, [0x00].as_ptr(), 1) };
+    assert!(!result.is_valid());
+
+    // SAFETY: invalid results are rejected before anything is written
+    let err = unsafe { result.patch_bytes(&[0x90]).unwrap_err() };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,60 @@
+use lightningscanner::multi::MultiScanner;
+use lightningscanner::pattern::{Pattern, PatternError};
+use lightningscanner::pattern_set::PatternSet;
+
+#[test]
+fn multi_scanner_from_signature_strings() {
+    let scanner = ["48 89 ?? 24", "c3 cc"]
+        .into_iter()
+        .map(str::parse)
+        .collect::<Result<MultiScanner, PatternError>>()
+        .unwrap();
+
+    let binary = [0xc3, 0xcc, 0x48, 0x89, 0x5c, 0x24];
+    assert_eq!(scanner.find_all(&binary), [(1, 0), (0, 2)]);
+    assert_eq!(scanner.patterns().len(), 2);
+}
+
+#[test]
+fn collecting_stops_at_the_first_invalid_signature() {
+    let result = ["48 89", "48 8"]
+        .into_iter()
+        .map(str::parse::<Pattern>)
+        .collect::<Result<MultiScanner, _>>();
+
+    assert_eq!(
+        result.err(),
+        Some(PatternError::OddNibbleCount { position: 3 })
+    );
+}
+
+#[test]
+fn pattern_set_from_named_patterns() {
+    let set: PatternSet = [
+        ("mov", Pattern::new("48 89 5c 24")),
+        ("ret", Pattern::new("c3")),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.get("ret"), Some(&Pattern::new("c3")));
+    assert_eq!(
+        set.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["mov", "ret"]
+    );
+
+    let owned: PatternSet = vec![(String::from("nop"), Pattern::new("90"))]
+        .into_iter()
+        .collect();
+    assert!(owned.get("nop").is_some());
+}
+
+#[test]
+fn pattern_from_str() {
+    assert_eq!("48 8B ?? c3".parse(), Ok(Pattern::new("48 8b ?? c3")));
+    assert_eq!(
+        "48 8b ?{x}".parse::<Pattern>(),
+        Err(PatternError::MalformedQuantifier { position: 6 })
+    );
+}
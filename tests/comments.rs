@@ -0,0 +1,68 @@
+use lightningscanner::create_pattern;
+use lightningscanner::fixed_pattern::FixedPattern;
+use lightningscanner::pattern::{Pattern, PatternError};
+
+const ANNOTATED: &str = "
+    48 8B 05 ?? ?? ?? ?? // load g_foo
+    /* test al, al */ 84 C0
+    74 ?? /* skip */ // the // nested marker is part of the comment
+";
+
+#[test]
+fn comments_are_ignored() {
+    let expected = Pattern::new("48 8b 05 ?? ?? ?? ?? 84 c0 74 ??");
+
+    assert_eq!(Pattern::new(ANNOTATED), expected);
+    assert_eq!(Pattern::new("48 /* a */ /**/ 8b//"), Pattern::new("48 8b"));
+    assert_eq!(
+        Pattern::new("48 /* 8b */ c3 /* // */ 90"),
+        Pattern::new("48 c3 90")
+    );
+    assert_eq!(Pattern::new("48\t8b\r\nc3"), Pattern::new("48 8b c3"));
+}
+
+#[test]
+fn comments_at_compile_time() {
+    const PATTERN: FixedPattern<16> = FixedPattern::new(ANNOTATED);
+
+    assert_eq!(Pattern::from(&PATTERN), Pattern::new(ANNOTATED));
+    assert_eq!(create_pattern!(ANNOTATED), Pattern::new(ANNOTATED));
+    assert_eq!(
+        create_pattern!("48 /* 8b */ c3 /* // */ 90"),
+        Pattern::new("48 c3 90")
+    );
+}
+
+#[test]
+fn malformed_patterns() {
+    assert_eq!(
+        Pattern::try_new("48 /* unterminated").err(),
+        Some(PatternError::UnterminatedComment { position: 3 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 /*/ c3").err(),
+        Some(PatternError::UnterminatedComment { position: 3 })
+    );
+
+    // code left after removing comments is still checked
+    assert_eq!(
+        Pattern::try_new("48 // comment\n load g_foo").err(),
+        Some(PatternError::InvalidByte { position: 15 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 / 8b").err(),
+        Some(PatternError::InvalidByte { position: 3 })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unterminated comment")]
+fn unterminated_comment_at_compile_time() {
+    FixedPattern::<16>::new("48 /* c3");
+}
+
+#[test]
+#[should_panic(expected = "Malformed hex byte")]
+fn malformed_hex_at_compile_time() {
+    FixedPattern::<16>::new("48 xx");
+}
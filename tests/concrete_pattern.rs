@@ -0,0 +1,58 @@
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Seeded, Wyrand};
+
+fn to_ida(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn matches_general_path() {
+    let mut rand = Wyrand::seed(0xc0c0);
+
+    for _ in 0..1000 {
+        let alphabet = 2 + rand.next_lim_usize(4);
+        let pattern = (0..1 + rand.next_lim_usize(40))
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+        let haystack = (0..rand.next_lim_usize(1024))
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        let scanner = Scanner::new(&to_ida(&pattern));
+
+        // SAFETY: haystack is a valid slice
+        let concrete = unsafe { scanner.find(None, haystack.as_ptr(), haystack.len()) };
+        // SAFETY: haystack is a valid slice
+        let general =
+            unsafe { scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len()) };
+
+        assert_eq!(concrete.get_addr(), general.get_addr());
+        assert_eq!(
+            scanner.find_all_offsets(None, &haystack),
+            scanner.find_all_offsets(Some(ScanMode::Scalar), &haystack)
+        );
+    }
+}
+
+#[test]
+fn string_search() {
+    let haystack = b"the LocalPlayer and the LocalPlayerController";
+    let scanner = Scanner::new_from_str("LocalPlayer");
+
+    assert_eq!(scanner.find_all_offsets(None, haystack), [4, 24]);
+    assert_eq!(scanner.find_with_hint(haystack, 30, 8), Some(24));
+}
+
+#[test]
+fn not_found() {
+    let haystack = [0x48, 0x89, 0x5c, 0x24];
+    let scanner = Scanner::new("48 89 5c 24 00");
+
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(None, haystack.as_ptr(), haystack.len()) };
+    assert!(!result.is_valid());
+}
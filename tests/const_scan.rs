@@ -0,0 +1,49 @@
+use lightningscanner::const_scan::const_find;
+use lightningscanner::{find_pattern_const, Scanner};
+
+const BINARY: &[u8] = &[
+    0x48, 0x89, 0x5c, 0x24, 0x08, 0x57, 0x48, 0x83, 0xec, 0x20, 0x48, 0x8b, 0xd9, 0xc3,
+];
+
+fn runtime_find(pattern: &str) -> Option<usize> {
+    Scanner::new(pattern)
+        .find_all_offsets(None, BINARY)
+        .first()
+        .copied()
+}
+
+#[test]
+fn matches_runtime_scanner() {
+    const EXACT: Option<usize> = find_pattern_const!(BINARY, "48 83 ec 20");
+    const WILDCARD: Option<usize> = find_pattern_const!(BINARY, "48 ?? ?? 24 ?{2} 48");
+    const TAIL: Option<usize> = find_pattern_const!(BINARY, "8b d9 c3");
+
+    assert_eq!(EXACT, Some(6));
+    assert_eq!(WILDCARD, Some(0));
+    assert_eq!(TAIL, Some(11));
+
+    assert_eq!(EXACT, runtime_find("48 83 ec 20"));
+    assert_eq!(WILDCARD, runtime_find("48 ?? ?? 24 ?{2} 48"));
+    assert_eq!(TAIL, runtime_find("8b d9 c3"));
+}
+
+#[test]
+fn not_found() {
+    const MISSING: Option<usize> = find_pattern_const!(BINARY, "de ad be ef");
+    const PAST_END: Option<usize> = find_pattern_const!(BINARY, "d9 c3 ??");
+
+    assert_eq!(MISSING, None);
+    assert_eq!(PAST_END, None);
+    assert_eq!(PAST_END, runtime_find("d9 c3 ??"));
+}
+
+#[test]
+fn raw_parts() {
+    const OFFSET: Option<usize> = const_find(BINARY, &[0x57, 0x00, 0x83], &[0xff, 0x00, 0xff]);
+    const EMPTY: Option<usize> = const_find(BINARY, &[], &[]);
+    const TOO_LONG: Option<usize> = const_find(&[0x48], &[0x48, 0x89], &[0xff, 0xff]);
+
+    assert_eq!(OFFSET, Some(5));
+    assert_eq!(EMPTY, Some(0));
+    assert_eq!(TOO_LONG, None);
+}
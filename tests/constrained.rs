@@ -0,0 +1,79 @@
+use lightningscanner::constrained::{ConstrainedScanner, RelativeRange};
+use lightningscanner::pattern::Pattern;
+use lightningscanner::ScanMode;
+
+#[test]
+fn window_abutting_the_match() {
+    let binary = [
+        0xcc, 0x48, 0x8b, 0x05, 0x90, 0x48, 0x8b, 0x05, 0xcc, 0x48, 0x8b, 0x05,
+    ];
+
+    let before =
+        ConstrainedScanner::new(Pattern::new("48 8b 05")).with_exclusion(Pattern::new("cc"), -1..0);
+    assert_eq!(before.find_all(None, &binary), [5]);
+
+    // the window right after the match
+    let after =
+        ConstrainedScanner::new(Pattern::new("48 8b 05")).with_exclusion(Pattern::new("cc"), 3..4);
+    assert_eq!(after.find_all(None, &binary), [1, 9]);
+
+    // the window ends right before the excluded byte, so it doesn't count
+    let short = ConstrainedScanner::new(Pattern::new("48 8b 05"))
+        .with_exclusion(Pattern::new("cc"), -4..-1);
+    assert_eq!(short.find_all(None, &binary), [1, 5, 9]);
+}
+
+#[test]
+fn excluded_pattern_has_to_fit_the_window() {
+    let binary = [0xcc, 0xcc, 0x48, 0x8b];
+
+    let scanner =
+        ConstrainedScanner::new(Pattern::new("48 8b")).with_exclusion(Pattern::new("cc cc"), -1..0);
+    assert_eq!(scanner.find(None, &binary), Some(2));
+
+    let scanner =
+        ConstrainedScanner::new(Pattern::new("48 8b")).with_exclusion(Pattern::new("cc cc"), -2..0);
+    assert_eq!(scanner.find(None, &binary), None);
+
+    // the excluded pattern may overlap the match
+    let scanner =
+        ConstrainedScanner::new(Pattern::new("48 8b")).with_exclusion(Pattern::new("cc 48"), -1..1);
+    assert_eq!(scanner.find(None, &binary), None);
+}
+
+#[test]
+fn windows_past_the_edges() {
+    let binary = [0x48, 0x8b, 0x90];
+
+    let scanner = ConstrainedScanner::new(Pattern::new("48 8b"))
+        .with_exclusion(Pattern::from_masked_bytes(&[0], &[0]), -10..0)
+        .with_exclusion(Pattern::from_masked_bytes(&[0; 2], &[0; 2]), 2..10)
+        .with_exclusion(Pattern::from_masked_bytes(&[0], &[0]), isize::MIN..-1)
+        .with_exclusion(Pattern::from_masked_bytes(&[0], &[0]), 10..isize::MAX);
+    assert_eq!(scanner.find(None, &binary), Some(0));
+
+    let scanner = ConstrainedScanner::new(Pattern::new("48 8b"))
+        .with_exclusion(Pattern::new("90"), 2..isize::MAX);
+    assert_eq!(scanner.find(None, &binary), None);
+}
+
+#[test]
+fn any_exclusion_rejects() {
+    let binary = [
+        0xcc, 0x48, 0x8b, 0x00, 0x48, 0x8b, 0x90, 0x00, 0x48, 0x8b, 0x00,
+    ];
+
+    let scanner = ConstrainedScanner::new(Pattern::new("48 8b"))
+        .with_exclusion(Pattern::new("cc"), -1..0)
+        .with_exclusion(Pattern::new("90"), RelativeRange { start: 2, end: 3 });
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        assert_eq!(scanner.find_all(mode, &binary), [8]);
+        assert_eq!(scanner.find(mode, &binary), Some(8));
+    }
+
+    assert_eq!(
+        ConstrainedScanner::new(Pattern::new("48 8b")).find_all(None, &binary),
+        [1, 4, 8]
+    );
+}
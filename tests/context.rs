@@ -0,0 +1,62 @@
+use lightningscanner::context::ContextOptions;
+use lightningscanner::Scanner;
+
+const BINARY: [u8; 16] = [
+    0x48, 0x89, 0x5c, 0xcc, 0xcc, 0xcc, 0xcc, 0x48, 0x89, 0x5c, 0xcc, 0xcc, 0xcc, 0x48, 0x89, 0x5c,
+];
+
+const OPTIONS: ContextOptions = ContextOptions {
+    context_before: 2,
+    context_after: 2,
+};
+
+#[test]
+fn context_in_the_middle() {
+    let matches = Scanner::new("48 89 5c").find_all_with_context(None, &BINARY, OPTIONS);
+
+    assert_eq!(matches[1].offset(), 7);
+    assert_eq!(matches[1].context(), &BINARY[5..12]);
+    assert_eq!(matches[1].offset_in_context(), 2);
+}
+
+#[test]
+fn clamped_at_start() {
+    let matches = Scanner::new("48 89 5c").find_all_with_context(None, &BINARY, OPTIONS);
+
+    // a match at offset 0 has no bytes before it
+    assert_eq!(matches[0].offset(), 0);
+    assert_eq!(matches[0].context(), &BINARY[..5]);
+    assert_eq!(matches[0].offset_in_context(), 0);
+
+    let matches = Scanner::new("89 5c").find_all_with_context(None, &BINARY, OPTIONS);
+    assert_eq!(matches[0].context(), &BINARY[..5]);
+    assert_eq!(matches[0].offset_in_context(), 1);
+}
+
+#[test]
+fn clamped_at_end() {
+    let matches = Scanner::new("48 89 5c").find_all_with_context(None, &BINARY, OPTIONS);
+
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches[2].offset(), 13);
+    assert_eq!(matches[2].context(), &BINARY[11..]);
+    assert_eq!(matches[2].offset_in_context(), 2);
+}
+
+#[test]
+fn no_context() {
+    let matches =
+        Scanner::new("48 89 5c").find_all_with_context(None, &BINARY, ContextOptions::default());
+
+    for found in matches {
+        assert_eq!(found.context(), [0x48, 0x89, 0x5c]);
+        assert_eq!(found.offset_in_context(), 0);
+    }
+
+    let huge = ContextOptions {
+        context_before: usize::MAX,
+        context_after: usize::MAX,
+    };
+    let matches = Scanner::new("cc 48").find_all_with_context(None, &BINARY, huge);
+    assert!(matches.iter().all(|found| found.context() == BINARY));
+}
@@ -0,0 +1,72 @@
+use lightningscanner::{ScanResult, Scanner};
+
+fn find(pattern: &str, binary: &[u8]) -> ScanResult {
+    // SAFETY: the pointer and size come from a valid slice
+    unsafe { Scanner::new(pattern).find(None, binary.as_ptr(), binary.len()) }
+}
+
+fn dump(
+    result: ScanResult,
+    binary: &[u8],
+    match_len: usize,
+    before: usize,
+    after: usize,
+) -> String {
+    // SAFETY: the pointer and size come from a valid slice
+    unsafe { result.context_dump(binary.as_ptr(), binary.len(), match_len, before, after) }
+}
+
+fn strip_addresses(dump: &str) -> Vec<&str> {
+    dump.lines()
+        .map(|line| line.split_once(": ").unwrap().1)
+        .collect()
+}
+
+#[test]
+fn brackets_the_match() {
+    let binary = [0x00, 0x11, 0x48, 0x89, 0x5c, 0x22, 0x33];
+    let result = find("48 89 5c", &binary);
+
+    let dump = dump(result, &binary, 3, 1, 2);
+    assert_eq!(strip_addresses(&dump), ["11 [48 89 5c] 22 33"]);
+    assert!(dump.starts_with(&format!("{:#x}:", binary.as_ptr() as usize + 1)));
+}
+
+#[test]
+fn clamped_at_the_edges() {
+    let binary = [0x48, 0x89, 0x5c];
+    let result = find("48 89 5c", &binary);
+
+    assert_eq!(
+        strip_addresses(&dump(result, &binary, 3, 16, 16)),
+        ["[48 89 5c]"]
+    );
+    // a match length past the end of the buffer is clamped as well
+    assert_eq!(
+        strip_addresses(&dump(result, &binary, 8, 0, 0)),
+        ["[48 89 5c]"]
+    );
+}
+
+#[test]
+fn match_across_lines() {
+    let mut binary = [0x00; 32];
+    binary[14..18].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    let result = find("48 89 5c 24", &binary);
+
+    let lines = dump(result, &binary, 4, 14, 0);
+    let lines = strip_addresses(&lines);
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("00 [48 89]"));
+    assert_eq!(lines[1], "[5c 24]");
+}
+
+#[test]
+#[should_panic(expected = "outside of the buffer")]
+fn invalid_result() {
+    let binary = [0x00; 4];
+    let result = find("48 89", &binary);
+
+    dump(result, &binary, 2, 0, 0);
+}
@@ -0,0 +1,47 @@
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn stops_at_the_cap() {
+    let binary = [
+        0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c, 0x48, 0x89, 0x5c,
+    ];
+    let scanner = Scanner::new("48 89 5c");
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        assert_eq!(scanner.count_up_to(mode, &binary, 0), 0);
+        assert_eq!(scanner.count_up_to(mode, &binary, 2), 2);
+        assert_eq!(scanner.count_up_to(mode, &binary, 3), 3);
+        assert_eq!(scanner.count_up_to(mode, &binary, usize::MAX), 3);
+    }
+}
+
+#[test]
+fn counts_like_find_all_offsets() {
+    let mut binary = vec![0x00; 4096];
+    for offset in (0..4096).step_by(97) {
+        binary[offset] = 0xcc;
+    }
+
+    for pattern in ["cc", "cc 00 00", "?? cc", "00 00 00 00"] {
+        let scanner = Scanner::new(pattern);
+        let all = scanner.find_all_offsets(None, &binary).len();
+
+        assert_eq!(
+            scanner.count_up_to(None, &binary, usize::MAX),
+            all,
+            "{}",
+            pattern
+        );
+        assert_eq!(
+            scanner.count_up_to(None, &binary, all / 2),
+            all / 2,
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn empty_binary() {
+    assert_eq!(Scanner::new("cc").count_up_to(None, &[], 10), 0);
+}
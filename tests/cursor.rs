@@ -0,0 +1,113 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+use lightningscanner::sigfile::SignatureFile;
+use lightningscanner::{create_pattern, PrologueFilter, ScanMode, ScanResult, Scanner};
+
+const BINARY: [u8; 12] = [
+    0xcc, 0xe8, 0x10, 0x20, 0x30, 0x40, 0x48, 0x8b, 0xcc, 0xe8, 0x01, 0x02,
+];
+
+fn find(scanner: &Scanner, mode: Option<ScanMode>, binary: &[u8]) -> Option<usize> {
+    // SAFETY: binary is a valid slice
+    let result = unsafe { scanner.find(mode, binary.as_ptr(), binary.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - binary.as_ptr() as usize)
+}
+
+#[test]
+fn reports_marked_byte() {
+    let scanner = Scanner::new("e8 ?? ?? ?? ?? ^ 48 8b");
+
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        assert_eq!(find(&scanner, mode, &BINARY), Some(6));
+    }
+
+    // offset based scans keep reporting the match start
+    assert_eq!(scanner.find_all_offsets(None, &BINARY), [1]);
+}
+
+#[test]
+fn concrete_pattern_cursor() {
+    // the fast paths honour the cursor as well
+    assert_eq!(find(&Scanner::new("48 ^ 8b"), None, &BINARY), Some(7));
+    assert_eq!(find(&Scanner::new("^?? 8b"), None, &BINARY), Some(6));
+    assert_eq!(find(&Scanner::new("^e8"), None, &BINARY), Some(1));
+}
+
+#[test]
+fn filtered_scans() {
+    let scanner = Scanner::new("e8 ?? ?? ?? ?? ^ 48 8b");
+    let binary = BINARY;
+    let offset = |result: ScanResult| result.get_addr() as usize - binary.as_ptr() as usize;
+
+    let filter = PrologueFilter {
+        min_padding: 1,
+        ..PrologueFilter::default()
+    };
+    // SAFETY: binary is a valid slice
+    let result = unsafe { scanner.find_prologue(None, binary.as_ptr(), binary.len(), filter) };
+    assert_eq!(offset(result), 6);
+
+    // SAFETY: binary is a valid slice
+    let result =
+        unsafe { scanner.find_excluding(&Pattern::new("cc"), None, binary.as_ptr(), binary.len()) };
+    assert_eq!(offset(result), 6);
+
+    // SAFETY: binary is a valid slice
+    let result =
+        unsafe { scanner.find_aligned_to_instruction(None, binary.as_ptr(), binary.len(), |_| 1) };
+    assert_eq!(offset(result), 6);
+}
+
+#[test]
+fn signature_cursor() {
+    let file = SignatureFile::parse("call = \"e8 ?? ?? ?? ?? ^ 48 8b\", offset = 1").unwrap();
+
+    assert_eq!(file.get("call").unwrap().find_in(&BINARY), Some(7));
+}
+
+#[test]
+fn cursor_past_tail_match() {
+    // a marker after the last byte points one past the end of the binary for a match at its end
+    let scanner = Scanner::new("e8 ?? ?? ^");
+    let binary = BINARY;
+
+    // SAFETY: binary is a valid slice
+    let results = unsafe { scanner.find_all(None, binary.as_ptr(), binary.len()) };
+    let offsets = results
+        .iter()
+        .map(|result| result.get_addr() as usize - binary.as_ptr() as usize)
+        .collect::<Vec<_>>();
+
+    assert_eq!(offsets, [4, binary.len()]);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Pattern::new("^ 48 8b").cursor(), 0);
+    assert_eq!(Pattern::new("48^8b").cursor(), 1);
+    assert_eq!(Pattern::new("48 8b ^").cursor(), 2);
+    assert_eq!(Pattern::new("?{3} ^ c3").cursor(), 3);
+    assert_eq!(
+        Pattern::try_new("48 ^ 8b ^ 05").err(),
+        Some(PatternError::DuplicateCursor { position: 8 })
+    );
+
+    assert_eq!(
+        create_pattern!("e8 ?? ?? ?? ?? ^ 48 8b"),
+        Pattern::new("e8 ?? ?? ?? ?? ^ 48 8b")
+    );
+    assert_ne!(Pattern::new("48 ^ 8b"), Pattern::new("48 8b"));
+}
+
+#[test]
+#[should_panic]
+fn cursor_outside_pattern() {
+    let _ = Pattern::new("48 8b").with_cursor(3);
+}
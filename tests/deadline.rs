@@ -0,0 +1,115 @@
+use lightningscanner::deadline::{ScanOptions, ScanOutcome};
+use lightningscanner::{ScanMode, Scanner};
+use std::time::{Duration, Instant};
+
+const PATTERN: [u8; 4] = [0x48, 0x89, 0x5c, 0x24];
+
+/// Zeroed binary with the pattern at the given offsets
+fn binary_with_matches(len: usize, offsets: &[usize]) -> Vec<u8> {
+    let mut binary = vec![0x00; len];
+    for &offset in offsets {
+        binary[offset..offset + PATTERN.len()].copy_from_slice(&PATTERN);
+    }
+    binary
+}
+
+fn far_deadline() -> ScanOptions {
+    ScanOptions::new().with_deadline(Instant::now() + Duration::from_secs(3600))
+}
+
+#[test]
+fn completed() {
+    let interval = Scanner::DEADLINE_CHECK_INTERVAL;
+    let offsets = [5, interval - 2, 2 * interval + 7];
+    let binary = binary_with_matches(3 * interval, &offsets);
+
+    let scanner = Scanner::new("48 89 5c 24");
+    for options in [ScanOptions::new(), far_deadline()] {
+        assert_eq!(
+            scanner.find_all_with_options(&binary, &options),
+            ScanOutcome::Completed(offsets.to_vec())
+        );
+        assert_eq!(
+            scanner.find_with_options(&binary[6..], &options),
+            ScanOutcome::Completed(Some(interval - 8))
+        );
+    }
+
+    let options = far_deadline().with_scan_mode(ScanMode::Scalar);
+    assert_eq!(
+        Scanner::new("cc").find_with_options(&binary, &options),
+        ScanOutcome::Completed(None)
+    );
+}
+
+#[test]
+fn past_deadline() {
+    let binary = binary_with_matches(64, &[0]);
+    let options = ScanOptions::new().with_deadline(Instant::now());
+
+    let scanner = Scanner::new("48 89 5c 24");
+    assert_eq!(
+        scanner.find_with_options(&binary, &options),
+        ScanOutcome::TimedOut {
+            matches_so_far: None,
+            bytes_scanned: 0
+        }
+    );
+    assert_eq!(
+        scanner.find_all_with_options(&binary, &options),
+        ScanOutcome::TimedOut {
+            matches_so_far: vec![],
+            bytes_scanned: 0
+        }
+    );
+}
+
+#[test]
+fn timed_out_mid_scan() {
+    let interval = Scanner::DEADLINE_CHECK_INTERVAL;
+    let offsets = (0..256).map(|i| i * interval + 3).collect::<Vec<_>>();
+    let binary = binary_with_matches(256 * interval, &offsets);
+
+    let scanner = Scanner::new("48 89 ?? 24");
+    let options = ScanOptions::new().with_deadline(Instant::now() + Duration::from_micros(200));
+
+    // the scan may still finish in time on a fast machine
+    match scanner.find_all_with_options(&binary, &options) {
+        ScanOutcome::Completed(matches) => assert_eq!(matches, offsets),
+        ScanOutcome::TimedOut {
+            matches_so_far,
+            bytes_scanned,
+        } => {
+            assert_eq!(bytes_scanned % interval, 0);
+            assert!(bytes_scanned < binary.len());
+            assert_eq!(matches_so_far, offsets[..bytes_scanned / interval]);
+        }
+    }
+}
+
+#[test]
+fn empty_pattern() {
+    let scanner = Scanner::new("");
+    assert_eq!(
+        scanner.find_all_with_options(&[1, 2, 3], &ScanOptions::new()),
+        ScanOutcome::Completed(vec![0, 1, 2, 3])
+    );
+    assert_eq!(
+        scanner.find_with_options(&[], &ScanOptions::new()),
+        ScanOutcome::Completed(Some(0))
+    );
+}
+
+#[test]
+fn outcome_accessors() {
+    let completed = ScanOutcome::Completed(vec![1]);
+    assert!(completed.is_completed());
+    assert_eq!(completed.into_matches(), [1]);
+
+    let timed_out = ScanOutcome::TimedOut {
+        matches_so_far: vec![2],
+        bytes_scanned: 4,
+    };
+    assert!(!timed_out.is_completed());
+    assert_eq!(timed_out.into_matches(), [2]);
+}
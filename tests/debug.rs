@@ -0,0 +1,69 @@
+use lightningscanner::aligned_bytes::AlignedBytes;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn pattern_signature() {
+    assert_eq!(
+        format!("{:?}", Pattern::new("48 8B 05 ?? ?{2} ? C3")),
+        r#"Pattern { signature: "48 8b 05 ?? ?? ?? ?? c3", len: 8 }"#
+    );
+    assert_eq!(
+        format!("{:?}", Pattern::new("")),
+        r#"Pattern { signature: "", len: 0 }"#
+    );
+}
+
+#[test]
+fn cursor_and_labels() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            Pattern::new("e8 @rel ?? ?? ?? ?? ^ @next 48 8b @end")
+        ),
+        r#"Pattern { signature: "e8 @rel ?? ?? ?? ?? ^ @next 48 8b @end", len: 7 }"#
+    );
+}
+
+#[test]
+fn partial_masks() {
+    let data = [0x4a, 0x4a, 0x4a, 0x4a];
+    let mask = [0xf0, 0x0f, 0x80, 0xff];
+
+    let pattern = Pattern::from_parts(
+        AlignedBytes::<32>::new(&data),
+        AlignedBytes::<32>::new(&mask),
+        4,
+    );
+    assert_eq!(
+        format!("{:?}", pattern),
+        r#"Pattern { signature: "[40-4f] ?a [00-7f] 4a", len: 4 }"#
+    );
+}
+
+#[test]
+fn long_patterns_are_truncated() {
+    let pattern = Pattern::new(&"90 ".repeat(100));
+
+    let expected = format!(
+        r#"Pattern {{ signature: "{} ..." (100 bytes), len: 100 }}"#,
+        ["90"; 64].join(" ")
+    );
+    assert_eq!(format!("{:?}", pattern), expected);
+}
+
+#[test]
+fn scanner() {
+    assert_eq!(
+        format!("{:?}", Scanner::new("48 ?? c3")),
+        r#"Scanner { pattern: Pattern { signature: "48 ?? c3", len: 3 }, fast_path: None }"#
+    );
+    assert_eq!(
+        format!("{:?}", Scanner::new("48 8b")),
+        r#"Scanner { pattern: Pattern { signature: "48 8b", len: 2 }, fast_path: Some("substring") }"#
+    );
+    assert_eq!(
+        format!("{:?}", Scanner::new("?? c3")),
+        r#"Scanner { pattern: Pattern { signature: "?? c3", len: 2 }, fast_path: Some("byte") }"#
+    );
+}
@@ -0,0 +1,84 @@
+use lightningscanner::pattern::Pattern;
+use std::collections::HashSet;
+
+#[test]
+fn differently_written_duplicates() {
+    let mut patterns = [
+        "48 8B 05 ?? ?? ?? ?? C3",
+        "488b05 ?{4} c3",
+        "48 8b 05 ? ? ? ? c3 // load",
+        "48 8b 05 ?? ?? ?? ?? c3 ^",
+        "/* mov */ 48 8b 05 ???????? c3",
+    ]
+    .map(Pattern::new)
+    .to_vec();
+
+    Pattern::dedup(&mut patterns);
+
+    assert_eq!(
+        patterns,
+        [
+            Pattern::new("48 8b 05 ?? ?? ?? ?? c3"),
+            // the cursor changes what the pattern reports
+            Pattern::new("48 8b 05 ?? ?? ?? ?? c3 ^"),
+        ]
+    );
+}
+
+#[test]
+fn keeps_first_occurrence_order() {
+    let mut patterns = ["c3", "90", "C3", "cc", "90"].map(Pattern::new).to_vec();
+
+    Pattern::dedup(&mut patterns);
+
+    assert_eq!(patterns, ["c3", "90", "cc"].map(Pattern::new));
+}
+
+#[test]
+fn hash_agrees_with_eq() {
+    let set = ["48 8B ?? C3", "488b??c3", "48 8b ?{1} c3", "48 8b ?? c3 ??"]
+        .map(Pattern::new)
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Pattern::new("48 8b ?? c3")));
+}
+
+#[test]
+fn by_fingerprint() {
+    let patterns = vec![
+        Pattern::new("48 8b ?? c3"),
+        Pattern::new("48 8b ?? c3 ?? ??"),
+        Pattern::from_masked_bytes(&[0x48, 0x8b, 0x40, 0xc3], &[0xff, 0xff, 0xf0, 0xff]),
+        Pattern::new("48 8b ?? c3 ^ @end"),
+        Pattern::new("48 ?? ?? c3"),
+    ];
+
+    // only the concrete bytes count
+    let mut merged = patterns.clone();
+    Pattern::dedup_by_fingerprint(&mut merged, false);
+    assert_eq!(merged, [patterns[0].clone(), patterns[4].clone()]);
+
+    // wildcards and partial masks count as well, cursors and labels don't
+    let mut distinct = patterns.clone();
+    Pattern::dedup_by_fingerprint(&mut distinct, true);
+    assert_eq!(
+        distinct,
+        [
+            patterns[0].clone(),
+            patterns[1].clone(),
+            patterns[2].clone(),
+            patterns[4].clone()
+        ]
+    );
+}
+
+#[test]
+fn empty() {
+    let mut patterns = Vec::new();
+
+    Pattern::dedup(&mut patterns);
+    Pattern::dedup_by_fingerprint(&mut patterns, false);
+    assert!(patterns.is_empty());
+}
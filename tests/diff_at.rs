@@ -0,0 +1,67 @@
+use lightningscanner::aligned_bytes::AlignedBytes;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+const BINARY: [u8; 12] = [
+    0xcc, 0x48, 0x8b, 0x0d, 0x10, 0x20, 0x30, 0x40, 0x84, 0xc1, 0xcc, 0xcc,
+];
+
+#[test]
+fn lists_diverging_bytes() {
+    let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? 84 c0");
+
+    assert_eq!(
+        pattern.diff_at(&BINARY, 1),
+        [(2, 0x05, 0x0d), (8, 0xc0, 0xc1)]
+    );
+
+    // the candidate from the best partial match
+    let (offset, _) = Scanner::from(pattern.clone())
+        .find_best_partial(&BINARY)
+        .unwrap();
+    assert_eq!(
+        pattern.diff_at(&BINARY, offset).first(),
+        Some(&(2, 0x05, 0x0d))
+    );
+}
+
+#[test]
+fn match_has_no_differences() {
+    let pattern = Pattern::new("48 8b 0d ?? ?? ?? ?? 84 c1");
+
+    assert_eq!(pattern.diff_at(&BINARY, 1), []);
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0; 3], &[0; 3]).diff_at(&BINARY, 0),
+        []
+    );
+}
+
+#[test]
+fn partial_masks() {
+    let data = [0x40, 0x00];
+    let mask = [0xf0, 0x0f];
+    let pattern = Pattern::from_parts(
+        AlignedBytes::<32>::new(&data),
+        AlignedBytes::<32>::new(&mask),
+        2,
+    );
+
+    assert_eq!(pattern.diff_at(&[0x4f, 0xf0], 0), []);
+    assert_eq!(
+        pattern.diff_at(&[0x5f, 0xf1], 0),
+        [(0, 0x40, 0x5f), (1, 0x00, 0xf1)]
+    );
+}
+
+#[test]
+fn past_the_end() {
+    let pattern = Pattern::new("cc cc 90 90");
+
+    assert_eq!(pattern.diff_at(&BINARY, 10), []);
+    assert_eq!(
+        pattern.diff_at(&BINARY, 9),
+        [(0, 0xcc, 0xc1), (2, 0x90, 0xcc)]
+    );
+    assert_eq!(pattern.diff_at(&BINARY, 12), []);
+    assert_eq!(pattern.diff_at(&BINARY, usize::MAX), []);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "test-hooks")]
+
+use lightningscanner::{feature_detections, ScanMode, Scanner};
+
+#[test]
+fn detected_at_most_once() {
+    let binary = [0x90; 256];
+
+    for byte in 0..=255u8 {
+        let scanner = Scanner::new(&format!("{:02x} ?? 90", byte));
+        for mode in [None, Some(ScanMode::Avx2), Some(ScanMode::Sse42)] {
+            // SAFETY: the pointer and size come from a valid slice
+            unsafe { scanner.find(mode, binary.as_ptr(), binary.len()) };
+        }
+    }
+
+    // supported modes are read from the same cache
+    assert!(ScanMode::Scalar.is_supported());
+
+    let expected = usize::from(cfg!(all(target_arch = "x86_64", not(feature = "no-simd"))));
+    assert_eq!(feature_detections(), expected);
+}
@@ -0,0 +1,74 @@
+use lightningscanner::file::Section;
+use lightningscanner::{ScanMode, Scanner};
+use std::fs;
+use std::path::PathBuf;
+
+const PATTERN: &str = "a0 9e 87 00 ?? 5c";
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("lightningscanner-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn contents() -> Vec<u8> {
+    let mut contents = vec![0xcc; 0x2000];
+    contents[0x24..0x2a].copy_from_slice(&[0xa0, 0x9e, 0x87, 0x00, 0x11, 0x5c]);
+    contents[0x1400..0x1406].copy_from_slice(&[0xa0, 0x9e, 0x87, 0x00, 0x22, 0x5c]);
+    contents[0x1ffa..].copy_from_slice(&[0xa0, 0x9e, 0x87, 0x00, 0x33, 0x5c]);
+    contents
+}
+
+#[test]
+fn file_offsets() {
+    let path = temp_file("offsets", &contents());
+
+    for mode in [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2] {
+        let offsets = Scanner::new(PATTERN)
+            .find_all_in_file(&path, Some(mode))
+            .unwrap();
+        assert_eq!(offsets, [0x24, 0x1400, 0x1ffa]);
+    }
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn rvas() {
+    let path = temp_file("rvas", &contents());
+    let sections = [
+        Section {
+            file_offset: 0x400,
+            file_size: 0x1000,
+            rva: 0x1000,
+        },
+        Section {
+            file_offset: 0x1400,
+            file_size: 0xc00,
+            rva: 0x3000,
+        },
+    ];
+
+    let rvas = Scanner::new(PATTERN)
+        .find_all_rvas_in_file(&path, None, &sections)
+        .unwrap();
+    // the match in the headers is not part of any section
+    assert_eq!(rvas, [0x3000, 0x3bfa]);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn small_files() {
+    let empty = temp_file("empty", &[]);
+    let short = temp_file("short", &[0xa0, 0x9e, 0x87]);
+
+    let scanner = Scanner::new(PATTERN);
+    assert!(scanner.find_all_in_file(&empty, None).unwrap().is_empty());
+    assert!(scanner.find_all_in_file(&short, None).unwrap().is_empty());
+    assert!(scanner.find_all_in_file("/nonexistent/file", None).is_err());
+
+    fs::remove_file(empty).unwrap();
+    fs::remove_file(short).unwrap();
+}
@@ -0,0 +1,54 @@
+use lightningscanner::{ScanMode, Scanner};
+
+const MODES: [Option<ScanMode>; 4] = [
+    None,
+    Some(ScanMode::Scalar),
+    Some(ScanMode::Sse42),
+    Some(ScanMode::Avx2),
+];
+
+#[test]
+fn stops_after_max() {
+    let binary = vec![0x90; 1 << 20];
+    let scanner = Scanner::new("90 90 90");
+
+    for mode in MODES {
+        // SAFETY: the pointer and size come from a valid slice
+        let results = unsafe { scanner.find_all_limited(mode, binary.as_ptr(), binary.len(), 100) };
+
+        assert_eq!(results.len(), 100);
+        for (index, result) in results.iter().enumerate() {
+            assert_eq!(result.get_addr(), binary[index..].as_ptr());
+        }
+    }
+}
+
+#[test]
+fn fewer_matches_than_max() {
+    let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    let scanner = Scanner::new("48 89 ^ 5c");
+
+    for mode in MODES {
+        // SAFETY: the pointer and size come from a valid slice
+        let (limited, all) = unsafe {
+            (
+                scanner.find_all_limited(mode, binary.as_ptr(), binary.len(), 10),
+                scanner.find_all(mode, binary.as_ptr(), binary.len()),
+            )
+        };
+
+        assert_eq!(limited, all);
+        assert_eq!(limited.len(), 2);
+        assert_eq!(limited[1].get_addr(), binary[7..].as_ptr());
+    }
+}
+
+#[test]
+fn zero_max() {
+    let binary = [0x90; 64];
+
+    // SAFETY: the pointer and size come from a valid slice
+    let results =
+        unsafe { Scanner::new("90").find_all_limited(None, binary.as_ptr(), binary.len(), 0) };
+    assert!(results.is_empty());
+}
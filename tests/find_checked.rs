@@ -0,0 +1,53 @@
+use lightningscanner::{ScanError, Scanner};
+use std::ptr;
+
+const BINARY: [u8; 8] = [0xab, 0x48, 0x89, 0x5c, 0x24, 0x10, 0xcc, 0xcc];
+
+#[test]
+fn valid_binary() {
+    let scanner = Scanner::new("48 89 5c 24 ??");
+
+    // SAFETY: BINARY is a valid slice
+    let result = unsafe { scanner.find_checked(None, BINARY.as_ptr(), BINARY.len()) }.unwrap();
+    assert_eq!(result.get_addr(), BINARY[1..].as_ptr());
+
+    // SAFETY: BINARY is a valid slice
+    let result = unsafe { scanner.find_checked(None, BINARY[2..].as_ptr(), 6) }.unwrap();
+    assert!(!result.is_valid());
+}
+
+#[test]
+fn rejected_inputs() {
+    let scanner = Scanner::new("48 89 5c 24 ??");
+
+    // SAFETY: the pointer is rejected before being read
+    let result = unsafe { scanner.find_checked(None, ptr::null(), 16) };
+    assert!(matches!(result, Err(ScanError::NullPointer)));
+
+    // SAFETY: the size is rejected before the pointer is read
+    let result = unsafe { scanner.find_checked(None, BINARY.as_ptr(), 0) };
+    assert!(matches!(
+        result,
+        Err(ScanError::BinaryTooSmall {
+            binary_size: 0,
+            pattern_size: 5
+        })
+    ));
+
+    // SAFETY: the size is rejected before the pointer is read
+    let result = unsafe { scanner.find_checked(None, BINARY.as_ptr(), 4) };
+    assert!(matches!(result, Err(ScanError::BinaryTooSmall { .. })));
+
+    // SAFETY: the range is rejected before the pointer is read
+    let result = unsafe { scanner.find_checked(None, BINARY.as_ptr(), usize::MAX) };
+    assert!(matches!(result, Err(ScanError::AddressOverflow)));
+}
+
+#[test]
+fn find_rejects_null() {
+    let scanner = Scanner::new("48 89 5c 24 ??");
+
+    // SAFETY: a null pointer is checked before being read
+    let result = unsafe { scanner.find(None, ptr::null(), 16) };
+    assert!(!result.is_valid());
+}
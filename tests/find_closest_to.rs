@@ -0,0 +1,72 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+/// Closest match found by going through all of them, ties resolving to the lower offset
+fn closest(scanner: &Scanner, haystack: &[u8], target: usize) -> Option<usize> {
+    scanner
+        .find_all_offsets(None, haystack)
+        .into_iter()
+        .min_by_key(|&offset| offset.abs_diff(target))
+}
+
+#[test]
+fn nearest_on_either_side() {
+    let mut haystack = vec![0xcc; 0x10000];
+    haystack[0x100..0x103].copy_from_slice(&[0xa0, 0x9e, 0x87]);
+    haystack[0x9000..0x9003].copy_from_slice(&[0xa0, 0x9e, 0x87]);
+
+    let scanner = Scanner::new("a0 9e ?? 87");
+    assert_eq!(scanner.find_closest_to(&haystack, 0x4000), None);
+
+    let scanner = Scanner::new("a0 9e 87");
+    assert_eq!(scanner.find_closest_to(&haystack, 0), Some(0x100));
+    assert_eq!(scanner.find_closest_to(&haystack, 0x4000), Some(0x100));
+    assert_eq!(scanner.find_closest_to(&haystack, 0x5000), Some(0x9000));
+    assert_eq!(scanner.find_closest_to(&haystack, 0x9000), Some(0x9000));
+    assert_eq!(scanner.find_closest_to(&haystack, 0xffff), Some(0x9000));
+    assert_eq!(scanner.find_closest_to(&haystack, usize::MAX), Some(0x9000));
+
+    // halfway between both
+    assert_eq!(scanner.find_closest_to(&haystack, 0x4880), Some(0x100));
+    assert_eq!(scanner.find_closest_to(&haystack, 0x4881), Some(0x9000));
+}
+
+#[test]
+fn match_overlapping_target() {
+    let haystack = [0xcc, 0x48, 0x89, 0x5c, 0x24, 0xcc, 0xcc, 0xcc, 0xcc];
+
+    let scanner = Scanner::new("48 89 5c 24");
+    assert_eq!(scanner.find_closest_to(&haystack, 3), Some(1));
+    assert_eq!(scanner.find_closest_to(&haystack, 8), Some(1));
+    assert_eq!(scanner.find_closest_to(&haystack[..4], 0), None);
+}
+
+#[test]
+fn agrees_with_all_matches() {
+    let mut rand = Wyrand::seed(0x5eed);
+    let scanner = Scanner::new("01 ?? 02");
+
+    for _ in 0..50 {
+        // matches range from a few per haystack to a few hundred
+        let len = rand.next_lim_usize(0x8000);
+        let rarity = 4 + rand.next_lim_usize(124);
+        let haystack = (0..len)
+            .map(|_| match rand.next_lim_usize(rarity) {
+                0 => 0x01,
+                1 => 0x02,
+                _ => 0x00,
+            })
+            .collect::<Vec<_>>();
+
+        for _ in 0..20 {
+            let target = rand.next_lim_usize(len + 16);
+            assert_eq!(
+                scanner.find_closest_to(&haystack, target),
+                closest(&scanner, &haystack, target),
+                "target {:#x} in {:#x} bytes",
+                target,
+                len
+            );
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn first_common_match() {
+    let scanner = Scanner::new("cc ?? c3");
+
+    let a = [0xcc, 0x01, 0xc3, 0xcc, 0x02, 0xc3, 0xcc, 0x03, 0xc3];
+    let b = [0xcc, 0x01, 0xc2, 0x00, 0xcc, 0xc3, 0xcc, 0x04, 0xc3];
+
+    assert_eq!(scanner.find_common(&a, &b), Some(6));
+    assert_eq!(scanner.find_common(&b, &a), Some(6));
+    assert_eq!(scanner.find_common(&a, &a), Some(0));
+}
+
+#[test]
+fn buffers_of_different_lengths() {
+    let scanner = Scanner::new("90 90");
+
+    assert_eq!(
+        scanner.find_common(&[0x00, 0x90, 0x90], &[0x00, 0x90]),
+        None
+    );
+    assert_eq!(
+        scanner.find_common(&[0x00, 0x90, 0x90], &[0x00, 0x90, 0x90, 0x00]),
+        Some(1)
+    );
+    assert_eq!(scanner.find_common(&[], &[0x90, 0x90]), None);
+}
+
+#[test]
+fn matches_naive_search() {
+    let mut rand = Wyrand::seed(0x5eed);
+
+    for _ in 0..1000 {
+        let scanner = Scanner::new(&format!(
+            "{:02x} ?? {:02x}",
+            rand.next_lim_u16(2),
+            rand.next_lim_u16(2)
+        ));
+        let mut buffer = || {
+            (0..rand.next_lim_usize(64))
+                .map(|_| rand.next_lim_u16(2) as u8)
+                .collect::<Vec<_>>()
+        };
+        let (a, b) = (buffer(), buffer());
+
+        let in_a = scanner.find_all_offsets(None, &a);
+        let in_b = scanner.find_all_offsets(None, &b);
+        let expected = in_a.into_iter().find(|offset| in_b.contains(offset));
+
+        assert_eq!(scanner.find_common(&a, &b), expected);
+    }
+}
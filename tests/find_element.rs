@@ -0,0 +1,52 @@
+use lightningscanner::Scanner;
+
+#[test]
+fn reports_element_index() {
+    let values: [u32; 4] = [0x1111_1111, 0xdead_beef, 0x2222_2222, 0xdead_beef];
+    let scanner = Scanner::new("ef be ad de");
+
+    // SAFETY: u32 has no padding
+    assert_eq!(unsafe { scanner.find_element(&values) }, Some(1));
+    // SAFETY: u32 has no padding
+    assert_eq!(unsafe { scanner.find_element(&values[2..]) }, Some(1));
+    // SAFETY: u32 has no padding
+    assert_eq!(unsafe { scanner.find_element(&values[..1]) }, None);
+}
+
+#[test]
+fn skips_unaligned_matches() {
+    // the pattern first occurs at byte 2, then at the start of element 2
+    let values: [u32; 3] = [0x3412_0000, 0x0000_0000, 0x0000_3412];
+    let scanner = Scanner::new("12 34");
+
+    // SAFETY: u32 has no padding
+    assert_eq!(unsafe { scanner.find_element(&values) }, Some(2));
+}
+
+#[test]
+fn matches_spanning_elements() {
+    let values: [u16; 3] = [0x0201, 0x0403, 0x0605];
+    let scanner = Scanner::new("03 04 05");
+
+    // SAFETY: u16 has no padding
+    assert_eq!(unsafe { scanner.find_element(&values) }, Some(1));
+}
+
+#[test]
+fn zero_sized_elements_never_match() {
+    let values = [(); 16];
+
+    // SAFETY: () has no bytes at all
+    assert_eq!(unsafe { Scanner::new("").find_element(&values) }, None);
+}
+
+#[test]
+fn byte_slices_report_offsets() {
+    let bytes: [u8; 4] = [0x90, 0x48, 0x8b, 0x05];
+
+    // SAFETY: u8 has no padding
+    assert_eq!(
+        unsafe { Scanner::new("8b 05").find_element(&bytes) },
+        Some(2)
+    );
+}
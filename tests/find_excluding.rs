@@ -0,0 +1,50 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+const PATTERN: &str = "48 89 5c 24 ??";
+
+const DATA_SET: [u8; 24] = [
+    0x48, 0x89, 0x5c, 0x24, 0x08, 0x57, 0x48, 0x89, 0x5c, 0x24, 0x10, 0x56, 0x48, 0x89, 0x5c, 0x24,
+    0x18, 0x57, 0xc3, 0xcc, 0x48, 0x89, 0x5c, 0x24,
+];
+
+fn find(negative: &str, mode: Option<ScanMode>, data: &[u8]) -> Option<usize> {
+    let scanner = Scanner::new(PATTERN);
+    let negative = Pattern::new(negative);
+    // SAFETY: data is a valid slice
+    let result = unsafe { scanner.find_excluding(&negative, mode, data.as_ptr(), data.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - data.as_ptr() as usize)
+}
+
+#[test]
+fn skips_negative_matches() {
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        assert_eq!(find("?? ?? ?? ?? ?? 57", mode, &DATA_SET), Some(6));
+        assert_eq!(find("?? ?? ?? ?? 08", mode, &DATA_SET), Some(6));
+        assert_eq!(find("?? ?? ?? ?? ?? 56", mode, &DATA_SET), Some(0));
+    }
+}
+
+#[test]
+fn no_remaining_candidate() {
+    // the last occurrence is cut off by the buffer end, so it's never a candidate
+    assert_eq!(find("48", None, &DATA_SET), None);
+    assert_eq!(find("?? ?? ?? ?? 10", None, &DATA_SET[6..]), Some(6));
+}
+
+#[test]
+fn negative_past_end() {
+    // the negative pattern doesn't fit after the last match, so it can't exclude it
+    assert_eq!(
+        find("?? ?? ?? ?? ?? ?{7} 57", None, &DATA_SET[12..]),
+        Some(0)
+    );
+}
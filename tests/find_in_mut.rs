@@ -0,0 +1,56 @@
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn writes_through_result() {
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        let mut data = vec![0x90; 256];
+        data[200..204].copy_from_slice(&[0x48, 0x85, 0xc0, 0x74]);
+
+        let result = Scanner::new("48 85 c0 ^ 74").find_in_mut(mode, &mut data);
+        let ptr = result.as_mut_ptr().unwrap();
+        // SAFETY: the pointer came from `data`, which isn't accessed otherwise in the meantime
+        unsafe { ptr.write(0xeb) };
+
+        assert_eq!(data[203], 0xeb);
+    }
+}
+
+#[test]
+fn reaches_before_the_result() {
+    let mut data = [0x00, 0x11, 0x22, 0x33];
+
+    let result = Scanner::new("22").find_in_mut(None, &mut data);
+    // SAFETY: the pointer came from `data`, the offset stays within it
+    unsafe { result.as_mut_ptr().unwrap().sub(2).write(0xff) };
+
+    assert_eq!(data, [0xff, 0x11, 0x22, 0x33]);
+}
+
+#[test]
+fn same_result_as_find() {
+    let mut data = [0xcc, 0x48, 0x89, 0x5c, 0x24, 0x90];
+    let scanner = Scanner::new("48 89 ?? 24");
+
+    // SAFETY: the pointer and size come from a valid slice
+    let found = unsafe { scanner.find(None, data.as_ptr(), data.len()) };
+    let found_mut = scanner.find_in_mut(None, &mut data);
+
+    assert_eq!(found, found_mut);
+    assert_eq!(found.get_addr(), found_mut.get_addr());
+    assert_eq!(found.as_mut_ptr(), None);
+    assert!(found_mut.as_mut_ptr().is_some());
+}
+
+#[test]
+fn no_match() {
+    let mut data = [0x90; 16];
+
+    let result = Scanner::new("c3").find_in_mut(None, &mut data);
+    assert!(!result.is_valid());
+    assert_eq!(result.as_mut_ptr(), None);
+}
@@ -0,0 +1,59 @@
+use lightningscanner::Scanner;
+
+const PATTERN: &str = "a0 9e 87 00 ?? 5c";
+
+fn haystack() -> Vec<u8> {
+    let mut haystack = vec![0xcc; 0x4000];
+    haystack[0x100..0x106].copy_from_slice(&[0xa0, 0x9e, 0x87, 0x00, 0x11, 0x5c]);
+    haystack[0x2000..0x2006].copy_from_slice(&[0xa0, 0x9e, 0x87, 0x00, 0x22, 0x5c]);
+    haystack
+}
+
+#[test]
+fn inside_window() {
+    let scanner = Scanner::new(PATTERN);
+    let haystack = haystack();
+
+    assert_eq!(
+        scanner.find_with_hint(&haystack, 0x2100, 0x100),
+        Some(0x2000)
+    );
+    assert_eq!(
+        scanner.find_with_hint(&haystack, 0x1f00, 0x100),
+        Some(0x2000)
+    );
+    assert_eq!(scanner.find_with_hint(&haystack, 0x2000, 0), Some(0x2000));
+}
+
+#[test]
+fn outside_window() {
+    let scanner = Scanner::new(PATTERN);
+    let haystack = haystack();
+
+    // the windowed phase misses by a single byte, so the full scan finds the first match
+    assert_eq!(
+        scanner.find_with_hint(&haystack, 0x2101, 0x100),
+        Some(0x100)
+    );
+    assert_eq!(
+        scanner.find_with_hint(&haystack, 0x1eff, 0x100),
+        Some(0x100)
+    );
+}
+
+#[test]
+fn clamped_window() {
+    let scanner = Scanner::new(PATTERN);
+    let haystack = haystack();
+
+    assert_eq!(scanner.find_with_hint(&haystack, 0, 0x1000), Some(0x100));
+    assert_eq!(
+        scanner.find_with_hint(&haystack, 0x3fff, 0x2000),
+        Some(0x2000)
+    );
+    assert_eq!(
+        scanner.find_with_hint(&haystack, usize::MAX, usize::MAX),
+        Some(0x100)
+    );
+    assert_eq!(scanner.find_with_hint(&[], 0, 0x10), None);
+}
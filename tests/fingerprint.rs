@@ -0,0 +1,53 @@
+use lightningscanner::pattern::Pattern;
+
+#[test]
+fn wildcard_spelling() {
+    let fingerprint = Pattern::new("48 8b 05 ?? ?? ?? ?? 84 c0").fingerprint();
+
+    assert_eq!(
+        Pattern::new("48 8b 05 ? ? ? ? 84 c0").fingerprint(),
+        fingerprint
+    );
+    assert_eq!(
+        Pattern::new("48 8B 05 ?{4} 84 C0").fingerprint(),
+        fingerprint
+    );
+    assert_eq!(
+        Pattern::new("48 8b 05 ?{4} 84 c0 ?? ??").fingerprint(),
+        fingerprint
+    );
+    assert_eq!(
+        Pattern::new("48 8b 05 ?{4} ^ 84 c0").fingerprint(),
+        fingerprint
+    );
+}
+
+#[test]
+fn concrete_bytes_and_positions() {
+    let fingerprint = Pattern::new("48 8b 05 ?? 84 c0").fingerprint();
+
+    assert_ne!(Pattern::new("48 8b 05 ?? 84 c1").fingerprint(), fingerprint);
+    assert_ne!(
+        Pattern::new("48 8b 05 ?? ?? 84 c0").fingerprint(),
+        fingerprint
+    );
+    assert_ne!(
+        Pattern::new("?? 48 8b 05 ?? 84 c0").fingerprint(),
+        fingerprint
+    );
+    assert_ne!(Pattern::new("48 8b 05 ?? 84").fingerprint(), fingerprint);
+}
+
+#[test]
+fn stable() {
+    // the fingerprint must not change between releases, cached values depend on it
+    assert_eq!(Pattern::new("").fingerprint(), 0xcbf29ce484222325);
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0; 2], &[0; 2]).fingerprint(),
+        Pattern::new("").fingerprint()
+    );
+    assert_eq!(
+        Pattern::new("48 ?? c3").fingerprint(),
+        Pattern::new("48 ?? c3").fingerprint()
+    );
+}
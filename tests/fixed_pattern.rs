@@ -0,0 +1,91 @@
+use lightningscanner::fixed_pattern::FixedPattern;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts the allocations of the current thread
+struct CountingAllocator;
+
+// SAFETY: every call is forwarded to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc`
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::dealloc`
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+const PATTERN: FixedPattern<32> = FixedPattern::new("48 8b 05 ?{4} ^ 84 c0");
+const EMBEDDED: [u8; 12] = [
+    0xcc, 0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0x84, 0xc0, 0xcc, 0xcc,
+];
+const OFFSET: Option<usize> = PATTERN.find(&EMBEDDED);
+
+#[test]
+fn const_construction() {
+    assert_eq!(PATTERN.len(), 9);
+    assert!(!PATTERN.is_empty());
+    assert_eq!(PATTERN.cursor(), 7);
+    assert_eq!(OFFSET, Some(1));
+
+    assert_eq!(std::mem::align_of::<FixedPattern<32>>(), 64);
+}
+
+#[test]
+fn finds_without_allocating() {
+    let mut haystack = [0xcc; 4096];
+    haystack[3000..3009].copy_from_slice(&[0x48, 0x8b, 0x05, 0x01, 0x02, 0x03, 0x04, 0x84, 0xc0]);
+
+    let before = allocations();
+    let pattern = FixedPattern::<16>::new("48 8b 05 ?? ?? ?? ?? 84 c0");
+    let offset = pattern.find(&haystack);
+    let missing = FixedPattern::<16>::new("48 8b 05 ?? ?? ?? ?? 84 c1").find(&haystack);
+    let after = allocations();
+
+    assert_eq!(offset, Some(3000));
+    assert_eq!(missing, None);
+    assert_eq!(after, before);
+}
+
+#[test]
+fn converts_to_pattern() {
+    let pattern = Pattern::from(&PATTERN);
+
+    assert_eq!(pattern, Pattern::new("48 8b 05 ?{4} ^ 84 c0"));
+    assert_eq!(
+        Scanner::from(pattern).find_all_offsets(None, &EMBEDDED),
+        [1]
+    );
+}
+
+#[test]
+fn exact_capacity() {
+    let pattern = FixedPattern::<2>::new("?? c0");
+
+    assert_eq!(pattern.find(&EMBEDDED), Some(8));
+    assert_eq!(FixedPattern::<0>::new("").find(&EMBEDDED), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "capacity")]
+fn too_long() {
+    FixedPattern::<2>::new("48 8b 05");
+}
@@ -0,0 +1,109 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+/// Long pattern with the bytes at `offsets` masked, the other bytes being wildcards
+fn sparse(len: usize, offsets: &[(usize, u8, u8)]) -> Pattern {
+    let mut data = vec![0x00; len];
+    let mut mask = vec![0x00; len];
+    for &(offset, byte, byte_mask) in offsets {
+        data[offset] = byte;
+        mask[offset] = byte_mask;
+    }
+
+    Pattern::from_masked_bytes(&data, &mask)
+}
+
+fn assert_backends_agree(pattern: Pattern, haystack: &[u8]) {
+    let scanner = Scanner::from(pattern);
+    let expected = scanner.find_all_offsets(Some(ScanMode::Scalar), haystack);
+
+    assert_eq!(
+        scanner.find_all_offsets(Some(ScanMode::Avx2), haystack),
+        expected,
+        "{:?}",
+        scanner
+    );
+
+    #[cfg(feature = "test-hooks")]
+    {
+        lightningscanner::set_gather_verification(false);
+        assert_eq!(
+            scanner.find_all_offsets(Some(ScanMode::Avx2), haystack),
+            expected,
+            "{:?} verified unit by unit",
+            scanner
+        );
+        lightningscanner::set_gather_verification(true);
+    }
+}
+
+#[test]
+fn concrete_bytes_at_the_end() {
+    // the last dword is moved back to stay within the loaded units
+    for len in [96, 127, 128, 129, 160] {
+        let pattern = sparse(
+            len,
+            &[
+                (0, 0x48, 0xff),
+                (1, 0x8b, 0xff),
+                (len - 40, 0x05, 0xff),
+                (len - 1, 0xc3, 0xff),
+            ],
+        );
+
+        let mut haystack = vec![0xcc; 600];
+        for at in [10, 300, 600 - len] {
+            haystack[at] = 0x48;
+            haystack[at + 1] = 0x8b;
+            haystack[at + len - 40] = 0x05;
+            haystack[at + len - 1] = 0xc3;
+        }
+        // near miss failing at the very last byte
+        haystack[200] = 0x48;
+        haystack[201] = 0x8b;
+        haystack[200 + len - 40] = 0x05;
+
+        assert_backends_agree(pattern, &haystack);
+    }
+}
+
+#[test]
+fn random_sparse_patterns() {
+    let mut rand = Wyrand::seed(0x6a7e);
+
+    for _ in 0..500 {
+        let alphabet = 2 + rand.next_lim_usize(3);
+        let len = 33 + rand.next_lim_usize(400);
+
+        // a few concrete bytes up front, then a handful spread over the rest, some of them
+        // partially masked
+        let mut offsets = Vec::new();
+        for offset in 0..3 {
+            offsets.push((offset, rand.next_lim_usize(alphabet) as u8, 0xff));
+        }
+        for _ in 0..1 + rand.next_lim_usize(10) {
+            let offset = 32 + rand.next_lim_usize(len - 32);
+            let mask = if rand.next_bool(Probability::new(0.25)) {
+                0x0f
+            } else {
+                0xff
+            };
+            offsets.push((offset, rand.next_lim_usize(alphabet) as u8, mask));
+        }
+        let pattern = sparse(len, &offsets);
+
+        let haystack_len = rand.next_lim_usize(2048);
+        let mut haystack = (0..haystack_len)
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+        if haystack_len >= pattern.len() {
+            let at = rand.next_lim_usize(haystack_len - pattern.len() + 1);
+            for (offset, byte) in pattern.raw_data()[..pattern.len()].iter().enumerate() {
+                haystack[at + offset] = *byte;
+            }
+        }
+
+        assert_backends_agree(pattern, &haystack);
+    }
+}
@@ -0,0 +1,45 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn runs_without_separators() {
+    let expected = Pattern::new("48 89 5c 24 ?? 48 89 6c");
+
+    for spelling in [
+        "48895C24 ?? 48896C",
+        "4889 5C24 ?? 4889 6C",
+        "48 895c24 ??  4889\t6c",
+        "48895C24??48896C",
+        "48895c24?48896c",
+    ] {
+        assert_eq!(
+            Pattern::try_new(spelling),
+            Ok(expected.clone()),
+            "{}",
+            spelling
+        );
+    }
+}
+
+#[test]
+fn wildcards_end_a_run() {
+    assert_eq!(Pattern::new("4889?5c"), Pattern::new("48 89 ?? 5c"));
+    assert_eq!(Pattern::new("48???5c"), Pattern::new("48 ?? ?? 5c"));
+    assert_eq!(Pattern::new("48?{2}5c"), Pattern::new("48 ?? ?? 5c"));
+}
+
+#[test]
+fn odd_runs() {
+    assert_eq!(
+        Pattern::try_new("488 95c").err(),
+        Some(PatternError::OddNibbleCount { position: 2 })
+    );
+    assert_eq!(
+        Pattern::try_new("4889 5").err(),
+        Some(PatternError::OddNibbleCount { position: 5 })
+    );
+    // half a byte can't be a wildcard
+    assert_eq!(
+        Pattern::try_new("48 8?").err(),
+        Some(PatternError::InvalidByte { position: 3 })
+    );
+}
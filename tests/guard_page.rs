@@ -0,0 +1,132 @@
+#![cfg(unix)]
+
+use lightningscanner::{ScanMode, Scanner};
+use std::ptr;
+
+/// Readable pages directly followed by an inaccessible guard page
+struct Guarded {
+    ptr: *mut u8,
+    readable: usize,
+}
+
+impl Guarded {
+    fn new(readable_pages: usize) -> Self {
+        let page_size = page_size();
+        let readable = readable_pages * page_size;
+
+        // SAFETY: a fresh anonymous mapping is requested
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                readable + page_size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert_ne!(ptr, libc::MAP_FAILED);
+
+        // SAFETY: the guard page is part of the mapping created above
+        let result = unsafe {
+            libc::mprotect(
+                ptr.cast::<u8>().add(readable).cast(),
+                page_size,
+                libc::PROT_NONE,
+            )
+        };
+        assert_eq!(result, 0);
+
+        Guarded {
+            ptr: ptr.cast(),
+            readable,
+        }
+    }
+
+    fn bytes(&mut self) -> &mut [u8] {
+        // SAFETY: the readable part of the mapping lives as long as `self`
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.readable) }
+    }
+}
+
+impl Drop for Guarded {
+    fn drop(&mut self) {
+        // SAFETY: the mapping isn't used anymore
+        unsafe { libc::munmap(self.ptr.cast(), self.readable + page_size()) };
+    }
+}
+
+fn page_size() -> usize {
+    // SAFETY: querying the page size has no preconditions
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+fn modes() -> Vec<Option<ScanMode>> {
+    vec![
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+        #[cfg(feature = "portable-simd")]
+        Some(ScanMode::Portable),
+    ]
+}
+
+#[test]
+fn scans_up_to_a_guard_page() {
+    let mut region = Guarded::new(1);
+    let len = region.bytes().len();
+    region.bytes().fill(0xcc);
+
+    for pattern_len in [1, 2, 3, 15, 16, 17, 31, 32, 33, 63, 64, 65, 100] {
+        let pattern = vec!["aa"; pattern_len].join(" ");
+        let wildcard_pattern = format!("?? {}", vec!["aa"; pattern_len].join(" "));
+
+        for pattern in [pattern, wildcard_pattern] {
+            let scanner = Scanner::new(&pattern);
+
+            for binary_size in [pattern_len, pattern_len + 1, 40, 200, len] {
+                let binary_size = binary_size.min(len);
+                // SAFETY: the binary ends right at the guard page
+                let binary = unsafe { region.ptr.add(len - binary_size) };
+
+                for mode in modes() {
+                    // SAFETY: the whole binary is readable
+                    let result = unsafe { scanner.find(mode, binary, binary_size) };
+                    assert!(!result.is_valid());
+
+                    // SAFETY: the whole binary is readable
+                    let all = unsafe { scanner.find_all(mode, binary, binary_size) };
+                    assert!(all.is_empty());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn finds_matches_touching_a_guard_page() {
+    let mut region = Guarded::new(1);
+    let len = region.bytes().len();
+    region.bytes().fill(0xcc);
+
+    let tail = [0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0xc3];
+    region.bytes()[len - tail.len()..].copy_from_slice(&tail);
+
+    let scanner = Scanner::new("48 8b 05 ?? ?? ?? ?? c3");
+
+    for mode in modes() {
+        for binary_size in [tail.len(), 33, 100, len] {
+            // SAFETY: the binary ends right at the guard page
+            let binary = unsafe { region.ptr.add(len - binary_size) };
+
+            // SAFETY: the whole binary is readable
+            let result = unsafe { scanner.find(mode, binary, binary_size) };
+
+            // SAFETY: the match is within the readable part
+            assert_eq!(result.get_addr(), unsafe {
+                region.ptr.add(len - tail.len())
+            });
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn hex_view() {
+    let dump = "\
+.text:0000000140001000  48 89 5C 24 08 57 48 83  EC 20 48 8B F9 E8 12 34  H.\\$.WH.. H....4
+.text:0000000140001010  56 78 48 85 C0 74 05 C3  CC CC CC CC CC CC CC CC  VxH..t..........
+.text:0000000140001020  48 83 EC 28                                      H..(
+";
+
+    assert_eq!(
+        Pattern::from_ida_dump(dump),
+        Ok(Pattern::new(
+            "48 89 5C 24 08 57 48 83 EC 20 48 8B F9 E8 12 34
+             56 78 48 85 C0 74 05 C3 CC CC CC CC CC CC CC CC
+             48 83 EC 28"
+        ))
+    );
+}
+
+#[test]
+fn disassembly_with_opcode_bytes() {
+    let dump = "\
+seg000:00401000 55                          push    ebp
+seg000:00401001 8B EC                       mov     ebp, esp
+seg000:00401003 83 EC 10                    sub     esp, 10h
+seg000:00401006 E8 F5 0F 00 00              call    sub_402000
+seg000:0040100B C9                          leave
+seg000:0040100C C3                          retn";
+
+    assert_eq!(
+        Pattern::from_ida_dump(dump),
+        Ok(Pattern::new("55 8b ec 83 ec 10 e8 f5 0f 00 00 c9 c3"))
+    );
+}
+
+#[test]
+fn ascii_column_made_of_hex_digits() {
+    // the rendering of 41 42 20 43 44 reads as the bytes AB and CD
+    let dump = "00401000: 41 42 20 43 44  AB CD\n00401005: 31 32  12";
+
+    assert_eq!(
+        Pattern::from_ida_dump(dump),
+        Ok(Pattern::new("41 42 20 43 44 31 32"))
+    );
+
+    // trailing spaces of the rendering trimmed when pasting
+    assert_eq!(
+        Pattern::from_ida_dump(".data:1000 41 42 20 20  AB"),
+        Ok(Pattern::new("41 42 20 20"))
+    );
+}
+
+#[test]
+fn bytes_without_address_column() {
+    assert_eq!(
+        Pattern::from_ida_dump("\n  48 8B 05 ?? ?? ?? ??\n\n  C3 ?  .\n"),
+        Ok(Pattern::new("48 8b 05 ?? ?? ?? ?? c3 ??"))
+    );
+}
+
+#[test]
+fn no_bytes() {
+    for text in [
+        "",
+        "\n\n",
+        "hello world",
+        ".text:0000000140001000 ; sub_140001000 proc near",
+    ] {
+        assert_eq!(
+            Pattern::from_ida_dump(text),
+            Err(PatternError::NoBytes),
+            "{:?}",
+            text
+        );
+    }
+}
+
+#[test]
+fn all_wildcards() {
+    assert_eq!(
+        Pattern::from_ida_dump(".text:0000000140001000 ?? ??"),
+        Err(PatternError::AllWildcards)
+    );
+}
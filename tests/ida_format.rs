@@ -0,0 +1,55 @@
+use lightningscanner::pattern::Pattern;
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+#[test]
+fn spaced_and_compact() {
+    let pattern = Pattern::new("48 8b 05 ?? ?? ?? ?? c3");
+
+    assert_eq!(pattern.as_ida_spaced(), "48 8B 05 ?? ?? ?? ?? C3");
+    assert_eq!(pattern.as_ida_compact(), "488B05????????C3");
+
+    let empty = Pattern::new("");
+    assert_eq!(empty.as_ida_spaced(), "");
+    assert_eq!(empty.as_ida_compact(), "");
+}
+
+#[test]
+fn widens_partially_masked_bytes() {
+    let pattern = Pattern::from_masked_bytes(&[0x48, 0x05, 0x40, 0xc3], &[0xff, 0x0f, 0xf8, 0xff]);
+
+    assert_eq!(pattern.as_ida_spaced(), "48 ?? ?? C3");
+    assert_eq!(pattern.as_ida_compact(), "48????C3");
+}
+
+#[test]
+fn leaves_out_cursor_and_labels() {
+    let pattern = Pattern::new("e8 @call ?? ?? ?? ?? ^ 48 8b");
+
+    assert_eq!(pattern.as_ida_spaced(), "E8 ?? ?? ?? ?? 48 8B");
+    assert_eq!(pattern.as_ida_compact(), "E8????????488B");
+}
+
+#[test]
+fn round_trips_through_parser() {
+    let mut rand = Wyrand::seed(0x1da);
+
+    for _ in 0..1000 {
+        let len = rand.next_lim_usize(24);
+        let data = (0..len).map(|_| rand.next_u16() as u8).collect::<Vec<_>>();
+        let mask = (0..len)
+            .map(|_| match rand.next_bool(Probability::new(0.3)) {
+                true => 0x00,
+                false => 0xff,
+            })
+            .collect::<Vec<_>>();
+
+        if len > 0 && mask.iter().all(|&mask| mask == 0x00) {
+            continue;
+        }
+        let pattern = Pattern::from_masked_bytes(&data, &mask);
+
+        assert_eq!(Pattern::new(&pattern.as_ida_spaced()), pattern);
+        assert_eq!(Pattern::new(&pattern.as_ida_compact()), pattern);
+        assert_eq!(pattern.as_ida_compact().len(), 2 * len);
+    }
+}
@@ -0,0 +1,132 @@
+use lightningscanner::{ScanMode, Scanner};
+
+/// Length decoder for the handful of x86 instructions used in the tests
+fn decode(addr: *const u8) -> usize {
+    // SAFETY: the scanner only passes addresses within the binary
+    match unsafe { *addr } {
+        // mov eax, imm32
+        0xb8 => 5,
+        // mov r/m64, r64 with a disp8 operand
+        0x48 => 4,
+        // push rdi, ret, int3 and anything else
+        _ => 1,
+    }
+}
+
+fn find(pattern: &str, mode: Option<ScanMode>, data: &[u8]) -> Option<usize> {
+    let scanner = Scanner::new(pattern);
+    // SAFETY: data is a valid slice
+    let result =
+        unsafe { scanner.find_aligned_to_instruction(mode, data.as_ptr(), data.len(), decode) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - data.as_ptr() as usize)
+}
+
+#[test]
+fn skips_matches_inside_instructions() {
+    // mov eax, 0x57c35748; push rdi; ret
+    let data = [0xb8, 0x48, 0x57, 0xc3, 0x57, 0x57, 0xc3];
+
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        assert_eq!(find("57 c3", mode, &data), Some(5));
+        assert_eq!(find("c3", mode, &data), Some(6));
+        assert_eq!(find("b8", mode, &data), Some(0));
+    }
+}
+
+#[test]
+fn no_aligned_match() {
+    // mov eax, 0xccc3c3c3; int3
+    let data = [0xb8, 0xc3, 0xc3, 0xc3, 0xcc, 0xcc];
+
+    assert_eq!(find("c3", None, &data), None);
+    assert_eq!(find("cc", None, &data), Some(5));
+}
+
+#[test]
+fn zero_length_instructions() {
+    let data = [0x00, 0x01, 0x02];
+
+    let scanner = Scanner::new("02");
+    // SAFETY: data is a valid slice
+    let result =
+        unsafe { scanner.find_aligned_to_instruction(None, data.as_ptr(), data.len(), |_| 0) };
+
+    assert_eq!(result.get_addr(), data[2..].as_ptr());
+}
+
+fn walk(pattern: &str, data: &[u8]) -> Option<usize> {
+    let scanner = Scanner::new(pattern);
+    // SAFETY: data is a valid slice
+    unsafe {
+        scanner.find_instruction_aligned(data.as_ptr(), data.len(), |addr, remaining| {
+            decode(addr).min(remaining)
+        })
+    }
+}
+
+#[test]
+fn walk_only_tries_boundaries() {
+    // mov eax, 0x57c35748; push rdi; ret
+    let data = [0xb8, 0x48, 0x57, 0xc3, 0x57, 0x57, 0xc3];
+
+    assert_eq!(walk("57 c3", &data), Some(5));
+    assert_eq!(walk("c3", &data), Some(6));
+    assert_eq!(walk("b8", &data), Some(0));
+    assert_eq!(walk("?? 48", &data), Some(0));
+}
+
+#[test]
+fn walk_agrees_with_candidate_scan() {
+    // mov eax, 0xccc3c3c3; int3; int3
+    let data = [0xb8, 0xc3, 0xc3, 0xc3, 0xcc, 0xcc, 0xcc];
+
+    for pattern in ["c3", "cc", "cc cc", "cc cc cc", "b8 c3", "48"] {
+        assert_eq!(
+            walk(pattern, &data),
+            find(pattern, None, &data),
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn walk_passes_remaining_length() {
+    let data = [0x90; 6];
+    let scanner = Scanner::new("cc");
+
+    let remaining = std::cell::RefCell::new(Vec::new());
+    // SAFETY: data is a valid slice
+    let result = unsafe {
+        scanner.find_instruction_aligned(data.as_ptr(), data.len(), |addr, len| {
+            assert_eq!(addr as usize + len, data.as_ptr() as usize + data.len());
+            remaining.borrow_mut().push(len);
+            2
+        })
+    };
+
+    assert_eq!(result, None);
+    assert_eq!(remaining.into_inner(), [6, 4, 2]);
+}
+
+#[test]
+fn walk_zero_length_instructions() {
+    let data = [0x00, 0x01, 0x02];
+    let scanner = Scanner::new("02");
+
+    // SAFETY: data is a valid slice
+    let result = unsafe { scanner.find_instruction_aligned(data.as_ptr(), data.len(), |_, _| 0) };
+    assert_eq!(result, Some(2));
+
+    // SAFETY: data is a valid slice
+    let empty = unsafe { scanner.find_instruction_aligned(data.as_ptr(), 0, |_, _| 0) };
+    assert_eq!(empty, None);
+}
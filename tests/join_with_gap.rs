@@ -0,0 +1,68 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn inserts_wildcards() {
+    let prefix = Pattern::new("48 8b 05");
+    let suffix = Pattern::new("48 85 c0");
+
+    assert_eq!(
+        prefix.join_with_gap(&suffix, 4),
+        Pattern::new("48 8b 05 ?? ?? ?? ?? 48 85 c0")
+    );
+    assert_eq!(
+        prefix.join_with_gap(&suffix, 0),
+        Pattern::new("48 8b 05 48 85 c0")
+    );
+    assert_eq!(
+        prefix.join_with_gap(&Pattern::new(""), 2),
+        Pattern::new("48 8b 05 ?? ??")
+    );
+    assert_eq!(
+        Pattern::new("").join_with_gap(&suffix, 1),
+        Pattern::new("?? 48 85 c0")
+    );
+}
+
+#[test]
+fn keeps_partial_masks() {
+    let prefix = Pattern::from_masked_bytes(&[0x48, 0x05], &[0xff, 0x0f]);
+    let suffix = Pattern::from_masked_bytes(&[0x40], &[0xf0]);
+
+    assert_eq!(
+        prefix.join_with_gap(&suffix, 1),
+        Pattern::from_masked_bytes(&[0x48, 0x05, 0x00, 0x40], &[0xff, 0x0f, 0x00, 0xf0])
+    );
+}
+
+#[test]
+fn re_pads_long_patterns() {
+    let prefix = Pattern::new(&["90"; 40].join(" "));
+    let joined = prefix.join_with_gap(&Pattern::new("c3"), 100);
+
+    assert_eq!(joined.len(), 141);
+    assert_eq!(joined.padded_len() % Pattern::ALIGNMENT, 0);
+    assert!(joined.padded_len() >= 141);
+
+    let mut haystack = vec![0x90; 40];
+    haystack.extend([0xcc; 100]);
+    haystack.push(0xc3);
+    assert_eq!(Scanner::from(joined).find_all_offsets(None, &haystack), [0]);
+}
+
+#[test]
+fn cursor_and_labels() {
+    let prefix = Pattern::new("@call e8");
+    let suffix = Pattern::new("8b ^ @disp 05 @call ??");
+
+    let joined = prefix.join_with_gap(&suffix, 4);
+    assert_eq!(joined.cursor(), 6);
+    assert_eq!(
+        joined.labels().collect::<Vec<_>>(),
+        [("call", 0), ("disp", 6)]
+    );
+
+    // the first pattern's cursor wins
+    let joined = Pattern::new("e8 ^ ??").join_with_gap(&suffix, 0);
+    assert_eq!(joined.cursor(), 1);
+}
@@ -0,0 +1,74 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+use lightningscanner::Scanner;
+
+#[test]
+fn labels_resolve_to_haystack_offsets() {
+    let mut haystack = vec![0xcc; 100];
+    haystack[40..50].copy_from_slice(&[0xe8, 0x11, 0x22, 0x33, 0x44, 0x48, 0x89, 0xc1, 0x75, 0x02]);
+
+    let scanner = Scanner::new("@call e8 @rel ?? ?? ?? ?? 48 89 c1 @jump 75 ?? @end");
+    let result = scanner.find_labeled(&haystack).unwrap();
+
+    assert_eq!(result.offset(), 40);
+    assert_eq!(result.label("call"), Some(40));
+    assert_eq!(result.label("rel"), Some(41));
+    assert_eq!(result.label("jump"), Some(48));
+    assert_eq!(result.label("end"), Some(50));
+    assert_eq!(result.label("missing"), None);
+
+    assert_eq!(
+        result.labels().collect::<Vec<_>>(),
+        [("call", 40), ("rel", 41), ("jump", 48), ("end", 50)]
+    );
+}
+
+#[test]
+fn no_match() {
+    let scanner = Scanner::new("48 @disp 8b");
+
+    assert!(scanner.find_labeled(&[0x48, 0x89, 0x8b]).is_none());
+    assert!(scanner.find_labeled(&[]).is_none());
+}
+
+#[test]
+fn labels_dont_change_matching() {
+    let labeled = Pattern::new("48 @a 8b ?{2} @b_2 c3");
+    let plain = Pattern::new("48 8b ?{2} c3");
+
+    assert_eq!(labeled.fingerprint(), plain.fingerprint());
+    assert_ne!(labeled, plain);
+    assert_eq!(labeled.cursor(), 0);
+
+    let binary = [0x00, 0x48, 0x8b, 0x01, 0x02, 0xc3];
+    assert_eq!(
+        Scanner::from(labeled.clone()).find_all_offsets(None, &binary),
+        Scanner::from(plain).find_all_offsets(None, &binary)
+    );
+
+    assert_eq!(labeled.clone(), labeled);
+    assert_eq!(labeled.reversed().labels().count(), 0);
+}
+
+#[test]
+fn labels_with_cursor() {
+    let pattern = Pattern::new("e8 @rel ?? ?? ?? ?? ^ 48 8b");
+
+    assert_eq!(pattern.cursor(), 5);
+    assert_eq!(pattern.labels().collect::<Vec<_>>(), [("rel", 1)]);
+}
+
+#[test]
+fn malformed_labels() {
+    assert_eq!(
+        Pattern::try_new("48 @disp ?? @disp c3").err(),
+        Some(PatternError::DuplicateLabel { position: 12 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 @ c3").err(),
+        Some(PatternError::MalformedLabel { position: 3 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 c3 @").err(),
+        Some(PatternError::MalformedLabel { position: 6 })
+    );
+}
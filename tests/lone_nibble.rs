@@ -0,0 +1,45 @@
+use lightningscanner::fixed_pattern::FixedPattern;
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn trailing_lone_nibble() {
+    assert_eq!(
+        Pattern::try_new("48 8").err(),
+        Some(PatternError::OddNibbleCount { position: 3 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 8 // comment").err(),
+        Some(PatternError::OddNibbleCount { position: 3 })
+    );
+    assert_eq!(
+        Pattern::try_new("8").err(),
+        Some(PatternError::OddNibbleCount { position: 0 })
+    );
+}
+
+#[test]
+fn lone_nibble_between_bytes() {
+    assert_eq!(
+        Pattern::try_new("4 8").err(),
+        Some(PatternError::OddNibbleCount { position: 0 })
+    );
+    assert_eq!(
+        Pattern::try_new("48 8\nc3").err(),
+        Some(PatternError::OddNibbleCount { position: 3 })
+    );
+}
+
+#[test]
+fn pairs_still_parse() {
+    assert_eq!(Pattern::try_new("48 80"), Ok(Pattern::new("48 80")));
+    assert_eq!(
+        Pattern::try_new("48 8x").err(),
+        Some(PatternError::InvalidByte { position: 3 })
+    );
+}
+
+#[test]
+#[should_panic(expected = "lone hex digit")]
+fn trailing_lone_nibble_at_compile_time() {
+    FixedPattern::<4>::new("48 8");
+}
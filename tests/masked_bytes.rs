@@ -0,0 +1,91 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn low_bits_exempt_mask() {
+    // flag byte whose low three bits are free, followed by a concrete byte
+    let pattern = Pattern::from_masked_bytes(&[0x40, 0x89], &[0xf8, 0xff]);
+    let scanner = Scanner::from(pattern);
+
+    let mut binary = vec![0x00; 300];
+    let mut expected = Vec::new();
+    for (i, flag) in (0x38..=0x50u8).enumerate() {
+        let offset = i * 11;
+        binary[offset] = flag;
+        binary[offset + 1] = 0x89;
+
+        if (0x40..=0x47).contains(&flag) {
+            expected.push(offset);
+        }
+    }
+
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        assert_eq!(
+            scanner.find_all_offsets(mode, &binary),
+            expected,
+            "{:?}",
+            mode
+        );
+    }
+}
+
+#[test]
+fn partially_masked_bytes_only() {
+    // no byte is fully masked, so there's no concrete byte to key the scan on
+    let pattern = Pattern::from_masked_bytes(&[0x40, 0x00, 0x05], &[0xf0, 0x00, 0x0f]);
+    let scanner = Scanner::from(pattern);
+
+    for size in [3, 8, 31, 32, 64, 300] {
+        let mut binary = vec![0x00; size];
+        binary[size - 3] = 0x4c;
+        binary[size - 1] = 0xa5;
+
+        for mode in [
+            None,
+            Some(ScanMode::Scalar),
+            Some(ScanMode::Sse42),
+            Some(ScanMode::Avx2),
+        ] {
+            assert_eq!(
+                scanner.find_all_offsets(mode, &binary),
+                [size - 3],
+                "{} bytes with {:?}",
+                size,
+                mode
+            );
+        }
+    }
+}
+
+#[test]
+fn ignored_data_bits_are_cleared() {
+    let a = Pattern::from_masked_bytes(&[0x47, 0xff, 0x89], &[0xf8, 0x00, 0xff]);
+    let b = Pattern::new("40 ?? 89");
+
+    assert_ne!(a, b);
+    assert_eq!(
+        a,
+        Pattern::from_masked_bytes(&[0x40, 0x00, 0x89], &[0xf8, 0x00, 0xff])
+    );
+    assert_eq!(a.canonical_string(), "[40-47] ?? 89");
+}
+
+#[test]
+fn full_and_empty_masks() {
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0x48, 0x12, 0x05], &[0xff, 0x00, 0xff]),
+        Pattern::new("48 ?? 05")
+    );
+    assert_eq!(Pattern::from_masked_bytes(&[], &[]), Pattern::new(""));
+}
+
+#[test]
+#[should_panic(expected = "same length")]
+fn mismatched_lengths() {
+    Pattern::from_masked_bytes(&[0x48, 0x8b], &[0xff]);
+}
@@ -0,0 +1,68 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{masked_eq, masked_eq_at, ScanMode, Scanner};
+use tinyrand::{Rand, Seeded, Wyrand};
+
+fn reference(haystack: &[u8], data: &[u8], mask: &[u8]) -> bool {
+    haystack.len() == data.len()
+        && haystack.len() == mask.len()
+        && haystack
+            .iter()
+            .zip(data)
+            .zip(mask)
+            .all(|((&value, &data), &mask)| (value ^ data) & mask == 0)
+}
+
+#[test]
+fn matches_reference() {
+    let mut rand = Wyrand::seed(0x6d61736b);
+
+    for len in 0..40 {
+        for _ in 0..200 {
+            let haystack = (0..len)
+                .map(|_| rand.next_u16() as u8 & 0x0f)
+                .collect::<Vec<_>>();
+            let data = (0..len)
+                .map(|_| rand.next_u16() as u8 & 0x0f)
+                .collect::<Vec<_>>();
+            let mask = (0..len)
+                .map(|_| [0x00, 0xff, 0xff, 0xff, 0x0f, 0x01][rand.next_lim_usize(6)])
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                masked_eq(&haystack, &data, &mask),
+                reference(&haystack, &data, &mask),
+                "{:x?} {:x?} {:x?}",
+                haystack,
+                data,
+                mask
+            );
+        }
+    }
+}
+
+#[test]
+fn length_mismatch() {
+    assert!(masked_eq(&[], &[], &[]));
+    assert!(!masked_eq(&[0x00], &[], &[]));
+    assert!(!masked_eq(&[0x00], &[0x00], &[]));
+    assert!(!masked_eq(&[0x00; 9], &[0x00; 9], &[0x00; 8]));
+}
+
+#[test]
+fn agrees_with_scans() {
+    let mut haystack = vec![0xcc; 300];
+    haystack[200..212].copy_from_slice(&[
+        0x48, 0x89, 0x5c, 0x24, 0x08, 0x57, 0x48, 0x83, 0xec, 0x20, 0x48, 0x8b,
+    ]);
+
+    let pattern = Pattern::new("48 89 5c 24 ?? 57 48 83 ec ?? 48 8b");
+    let offsets =
+        Scanner::from(pattern.clone()).find_all_offsets(Some(ScanMode::Scalar), &haystack);
+
+    let positions = (0..haystack.len() + 2)
+        .filter(|&pos| masked_eq_at(&haystack, pos, &pattern))
+        .collect::<Vec<_>>();
+
+    assert_eq!(positions, offsets);
+    assert!(!masked_eq_at(&haystack, usize::MAX, &pattern));
+}
@@ -0,0 +1,96 @@
+//! Match offsets within a single vector unit, for every backend
+//!
+//! The backends compare whole units of the pattern at every candidate offset, so these
+//! pin down which byte of a unit each pattern byte is compared with, including across
+//! the 128-bit lanes of AVX2.
+
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+/// Modes to test, including ones the CPU doesn't support, which fall back to another backend
+const MODES: [ScanMode; 3] = [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2];
+
+/// Haystack of `len` zero bytes with `0xcc` at `index`
+fn haystack(len: usize, index: usize) -> Vec<u8> {
+    let mut haystack = vec![0x00; len];
+    haystack[index] = 0xcc;
+    haystack
+}
+
+#[test]
+fn single_byte_at_every_index() {
+    let scanner = Scanner::new("cc");
+
+    for len in [16, 32, 64] {
+        for index in 0..len {
+            let haystack = haystack(len, index);
+
+            for mode in MODES {
+                assert_eq!(
+                    scanner.find_all_offsets(Some(mode), &haystack),
+                    [index],
+                    "{:?}, {} of {} bytes",
+                    mode,
+                    index,
+                    len
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn concrete_byte_at_every_unit_position() {
+    // the pattern covers a whole unit, its only concrete byte is at `position`
+    for unit in [16, 32] {
+        for position in 0..unit {
+            let mut data = vec![0x00; unit];
+            let mut mask = vec![0x00; unit];
+            data[position] = 0xcc;
+            mask[position] = 0xff;
+            let scanner = Scanner::from(Pattern::from_masked_bytes(&data, &mask));
+
+            for index in position..position + unit {
+                let haystack = haystack(2 * unit, index);
+
+                for mode in MODES {
+                    assert_eq!(
+                        scanner.find_all_offsets(Some(mode), &haystack),
+                        [index - position],
+                        "{:?}, byte {} of a {} byte unit at {}",
+                        mode,
+                        position,
+                        unit,
+                        index
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mismatch_at_every_unit_position() {
+    // a fully concrete unit which differs from the haystack in a single byte never matches
+    for unit in [16, 32] {
+        let scanner = Scanner::from(Pattern::from_masked_bytes(
+            &vec![0x00; unit],
+            &vec![0xff; unit],
+        ));
+
+        for index in 0..unit {
+            let haystack = haystack(unit, index);
+
+            for mode in MODES {
+                assert_eq!(
+                    scanner.find_all_offsets(Some(mode), &haystack),
+                    [0usize; 0],
+                    "{:?}, {} of {} bytes",
+                    mode,
+                    index,
+                    unit
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use lightningscanner::match_list::MatchList;
+use lightningscanner::Scanner;
+use std::collections::BTreeSet;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+fn list(matches: &[usize]) -> MatchList {
+    MatchList::from(matches.to_vec())
+}
+
+#[test]
+fn sorted_and_unique() {
+    let list = MatchList::from(vec![9, 3, 3, 0, 9, 5]);
+
+    assert_eq!(list.as_slice(), [0, 3, 5, 9]);
+    assert_eq!(list.len(), 4);
+    assert!(list.contains(5));
+    assert!(!list.contains(4));
+    assert_eq!(list.iter().rev().collect::<Vec<_>>(), [9, 5, 3, 0]);
+    assert_eq!(list.clone().into_vec(), [0, 3, 5, 9]);
+    assert!(MatchList::new().is_empty());
+}
+
+#[test]
+fn set_operations() {
+    let mut rand = Wyrand::seed(0x5e75);
+
+    for _ in 0..100 {
+        let a = (0..rand.next_lim_usize(50))
+            .map(|_| rand.next_lim_usize(100))
+            .collect::<BTreeSet<_>>();
+        let b = (0..rand.next_lim_usize(50))
+            .map(|_| rand.next_lim_usize(100))
+            .collect::<BTreeSet<_>>();
+
+        let list_a = a.iter().copied().collect::<MatchList>();
+        let list_b = b.iter().copied().collect::<MatchList>();
+
+        let expected = |set: BTreeSet<usize>| set.into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            list_a.merge(&list_b).into_vec(),
+            expected(a.union(&b).copied().collect())
+        );
+        assert_eq!(
+            list_a.intersect(&list_b).into_vec(),
+            expected(a.intersection(&b).copied().collect())
+        );
+        assert_eq!(
+            list_a.difference(&list_b).into_vec(),
+            expected(a.difference(&b).copied().collect())
+        );
+    }
+}
+
+#[test]
+fn dedup_within() {
+    let mut matches = list(&[0, 3, 6, 9, 20]);
+    matches.dedup_within(4);
+    assert_eq!(matches.as_slice(), [0, 6, 20]);
+
+    // the distance is inclusive, and measured from the kept match
+    let mut matches = list(&[10, 14, 15, 19, 24]);
+    matches.dedup_within(4);
+    assert_eq!(matches.as_slice(), [10, 15, 24]);
+
+    let mut matches = list(&[1, 2, 3]);
+    matches.dedup_within(0);
+    assert_eq!(matches.as_slice(), [1, 2, 3]);
+
+    let mut matches = list(&[1, 2, 3, usize::MAX]);
+    matches.dedup_within(usize::MAX);
+    assert_eq!(matches.as_slice(), [1]);
+
+    let mut matches = MatchList::new();
+    matches.dedup_within(8);
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn retain_aligned() {
+    let mut matches = list(&[0, 3, 4, 6, 8, 12, 13]);
+    matches.retain_aligned(4);
+    assert_eq!(matches.as_slice(), [0, 4, 8, 12]);
+
+    let mut matches = list(&[0, 3, 4, 6, 9]);
+    matches.retain_aligned(3);
+    assert_eq!(matches.as_slice(), [0, 3, 6, 9]);
+}
+
+#[test]
+#[should_panic(expected = "alignment must not be zero")]
+fn retain_aligned_zero() {
+    list(&[1]).retain_aligned(0);
+}
+
+#[test]
+fn regions() {
+    let text = MatchList::from_region(0x1000, [0x20, 0x10]);
+    let data = MatchList::from_region(0x8000, [0x4]);
+
+    assert_eq!(text.merge(&data).as_slice(), [0x1010, 0x1020, 0x8004]);
+}
+
+#[test]
+fn from_find_all() {
+    let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    let scanner = Scanner::new("48 89 5c");
+
+    // SAFETY: the array is valid for its length
+    let results = unsafe { scanner.find_all(None, binary.as_ptr(), binary.len()) };
+    let addresses = MatchList::from(results);
+    let offsets = MatchList::from(scanner.find_all_offsets(None, &binary));
+
+    assert_eq!(
+        addresses,
+        MatchList::from_region(binary.as_ptr() as usize, offsets)
+    );
+}
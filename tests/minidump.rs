@@ -0,0 +1,283 @@
+#![cfg(feature = "minidump")]
+
+use lightningscanner::minidump::{DumpError, DumpMatch};
+use lightningscanner::Scanner;
+use std::fs;
+
+const PATTERN: &str = "48 89 5c 24";
+const MATCH: [u8; 4] = [0x48, 0x89, 0x5c, 0x24];
+
+/// Minimal minidump writer, the directory precedes all streams so truncating a dump
+/// cuts off the end of the last stream
+struct Dump {
+    bytes: Vec<u8>,
+    streams: Vec<(u32, u32)>,
+}
+
+/// Number of streams the directory has room for
+const MAX_STREAMS: usize = 4;
+
+impl Dump {
+    fn new() -> Self {
+        // the header and directory are filled in by `finish`
+        Dump {
+            bytes: vec![0; 32 + 12 * MAX_STREAMS],
+            streams: Vec::new(),
+        }
+    }
+
+    fn offset(&self) -> u32 {
+        self.bytes.len() as u32
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Add a `Memory64ListStream` with the given ranges stored back to back
+    fn memory64(&mut self, ranges: &[(u64, &[u8])]) -> &mut Self {
+        let list = self.offset();
+        let data = list + 16 + 16 * ranges.len() as u32;
+
+        self.u64(ranges.len() as u64);
+        self.u64(data as u64);
+        for (address, bytes) in ranges {
+            self.u64(*address);
+            self.u64(bytes.len() as u64);
+        }
+        for (_, bytes) in ranges {
+            self.bytes.extend_from_slice(bytes);
+        }
+
+        self.streams.push((9, list));
+        self
+    }
+
+    /// Add a `MemoryListStream` with the given ranges
+    fn memory(&mut self, ranges: &[(u64, &[u8])]) -> &mut Self {
+        let list = self.offset();
+        let mut data = list + 4 + 16 * ranges.len() as u32;
+
+        self.u32(ranges.len() as u32);
+        for (address, bytes) in ranges {
+            self.u64(*address);
+            self.u32(bytes.len() as u32);
+            self.u32(data);
+            data += bytes.len() as u32;
+        }
+        for (_, bytes) in ranges {
+            self.bytes.extend_from_slice(bytes);
+        }
+
+        self.streams.push((5, list));
+        self
+    }
+
+    /// Add a `ModuleListStream` with the given `(base, size, name)` modules
+    fn modules(&mut self, modules: &[(u64, u32, &str)]) -> &mut Self {
+        let mut names = Vec::new();
+        for (_, _, name) in modules {
+            names.push(self.offset());
+
+            let name = name.encode_utf16().collect::<Vec<_>>();
+            self.u32(name.len() as u32 * 2);
+            for unit in name {
+                self.bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+
+        let list = self.offset();
+        self.u32(modules.len() as u32);
+        for ((base, size, _), name) in modules.iter().zip(names) {
+            let module = self.bytes.len();
+            self.bytes.resize(module + 108, 0);
+            self.bytes[module..module + 8].copy_from_slice(&base.to_le_bytes());
+            self.bytes[module + 8..module + 12].copy_from_slice(&size.to_le_bytes());
+            self.bytes[module + 20..module + 24].copy_from_slice(&name.to_le_bytes());
+        }
+
+        self.streams.push((4, list));
+        self
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        assert!(self.streams.len() <= MAX_STREAMS);
+
+        let mut bytes = self.bytes.clone();
+        bytes[0..4].copy_from_slice(b"MDMP");
+        bytes[4..8].copy_from_slice(&0xa793u32.to_le_bytes());
+        bytes[8..12].copy_from_slice(&(self.streams.len() as u32).to_le_bytes());
+        bytes[12..16].copy_from_slice(&32u32.to_le_bytes());
+
+        for (index, (kind, location)) in self.streams.iter().enumerate() {
+            let entry = 32 + 12 * index;
+            bytes[entry..entry + 4].copy_from_slice(&kind.to_le_bytes());
+            bytes[entry + 8..entry + 12].copy_from_slice(&location.to_le_bytes());
+        }
+
+        bytes
+    }
+}
+
+fn memory_with_match(len: usize, at: usize) -> Vec<u8> {
+    let mut memory = vec![0x00; len];
+    memory[at..at + MATCH.len()].copy_from_slice(&MATCH);
+    memory
+}
+
+#[test]
+fn memory64_list() {
+    let first = memory_with_match(0x100, 0x10);
+    let second = memory_with_match(0x100, 0x80);
+
+    let dump = Dump::new()
+        .modules(&[(0x7ff0_0000_0000, 0x1000, "C:\\Windows\\System32\\ntdll.dll")])
+        .memory64(&[(0x7ff0_0000_0000, &first), (0x2000_0000, &second)])
+        .finish();
+
+    let matches = Scanner::new(PATTERN).find_in_minidump(&dump).unwrap();
+    let data = dump.windows(4).position(|window| window == MATCH).unwrap() as u64;
+
+    assert_eq!(
+        matches,
+        [
+            DumpMatch {
+                address: 0x2000_0080,
+                file_offset: data - 0x10 + 0x100 + 0x80,
+                module: None,
+            },
+            DumpMatch {
+                address: 0x7ff0_0000_0010,
+                file_offset: data,
+                module: Some("C:\\Windows\\System32\\ntdll.dll".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn memory_list() {
+    let memory = memory_with_match(0x40, 0x3c);
+
+    let dump = Dump::new()
+        .memory(&[(0x1000, &memory)])
+        .modules(&[(0x800, 0x800, "a.dll"), (0x1000, 0x100, "b.dll")])
+        .finish();
+
+    let matches = Scanner::new(PATTERN).find_in_minidump(&dump).unwrap();
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].address, 0x103c);
+    assert_eq!(matches[0].module.as_deref(), Some("b.dll"));
+    assert_eq!(
+        &dump[matches[0].file_offset as usize..][..4],
+        MATCH.as_slice()
+    );
+}
+
+#[test]
+fn spanning_contiguous_ranges() {
+    let mut memory = vec![0x00; 0x200];
+    memory[0xfe..0x102].copy_from_slice(&MATCH);
+
+    let dump = Dump::new()
+        .memory64(&[(0x1000, &memory[..0x100]), (0x1100, &memory[0x100..])])
+        .finish();
+    let matches = Scanner::new(PATTERN).find_in_minidump(&dump).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].address, 0x10fe);
+
+    // not contiguous in memory
+    let dump = Dump::new()
+        .memory64(&[(0x1000, &memory[..0x100]), (0x2000, &memory[0x100..])])
+        .finish();
+    assert!(Scanner::new(PATTERN)
+        .find_in_minidump(&dump)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn truncated_range() {
+    let memory = memory_with_match(0x100, 0x20);
+    let dump = Dump::new().memory64(&[(0x1000, &memory)]).finish();
+    let data = dump.len() - memory.len();
+
+    let scanner = Scanner::new(PATTERN);
+    assert_eq!(scanner.find_in_minidump(&dump).unwrap().len(), 1);
+
+    // the match is still within the remaining bytes
+    let matches = scanner.find_in_minidump(&dump[..data + 0x24]).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].address, 0x1020);
+
+    // the match is cut off
+    assert!(scanner
+        .find_in_minidump(&dump[..data + 0x22])
+        .unwrap()
+        .is_empty());
+    // all of the memory is missing
+    assert!(scanner.find_in_minidump(&dump[..data]).unwrap().is_empty());
+}
+
+#[test]
+fn errors() {
+    let scanner = Scanner::new(PATTERN);
+
+    assert!(matches!(
+        scanner.find_in_minidump(b"MZ\x90\x00\x03\x00\x00\x00"),
+        Err(DumpError::InvalidSignature)
+    ));
+    assert!(matches!(
+        scanner.find_in_minidump(b"MDMP"),
+        Err(DumpError::Truncated { offset: 8 })
+    ));
+    assert!(matches!(
+        scanner.find_in_minidump(&Dump::new().modules(&[]).finish()),
+        Err(DumpError::NoMemoryList)
+    ));
+
+    let mut dump = Dump::new().memory64(&[(0x1000, &[0; 16])]).finish();
+    // claim more ranges than the dump holds
+    let list = 32 + 12 * MAX_STREAMS;
+    dump[list..list + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(matches!(
+        scanner.find_in_minidump(&dump),
+        Err(DumpError::Truncated { .. })
+    ));
+
+    assert_eq!(
+        DumpError::Truncated { offset: 0x20 }.to_string(),
+        "dump is truncated at offset 0x20"
+    );
+}
+
+#[test]
+fn dump_file() {
+    let memory = memory_with_match(0x100, 0x10);
+    let dump = Dump::new().memory64(&[(0x1000, &memory)]).finish();
+
+    let path = std::env::temp_dir().join(format!(
+        "lightningscanner-{}-minidump.dmp",
+        std::process::id()
+    ));
+    fs::write(&path, &dump).unwrap();
+
+    let matches = Scanner::new(PATTERN).find_in_minidump_file(&path).unwrap();
+    assert_eq!(
+        matches,
+        Scanner::new(PATTERN).find_in_minidump(&dump).unwrap()
+    );
+    assert_eq!(matches.len(), 1);
+
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        Scanner::new(PATTERN).find_in_minidump_file(&path),
+        Err(DumpError::Io(_))
+    ));
+}
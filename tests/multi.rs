@@ -0,0 +1,109 @@
+use lightningscanner::multi::MultiScanner;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+/// Random byte made of two nibbles below `alphabet`
+fn random_byte(rand: &mut Wyrand, alphabet: usize) -> u8 {
+    (rand.next_lim_usize(alphabet) << 4 | rand.next_lim_usize(alphabet)) as u8
+}
+
+/// Random pattern built from its bytes, as patterns of wildcards only can't be parsed
+fn random_pattern(rand: &mut Wyrand, alphabet: usize) -> Pattern {
+    let len = 1 + rand.next_lim_usize(12);
+    let (data, mask): (Vec<u8>, Vec<u8>) = (0..len)
+        .map(|_| {
+            let mask = match rand.next_lim_usize(10) {
+                0..=2 => 0x00,
+                3 => 0xf0,
+                4 => 0x0f,
+                _ => 0xff,
+            };
+            (random_byte(rand, alphabet), mask)
+        })
+        .unzip();
+
+    Pattern::from_masked_bytes(&data, &mask)
+}
+
+#[test]
+fn matches_independent_scans() {
+    let mut rand = Wyrand::seed(0xac0a);
+
+    for _ in 0..50 {
+        let alphabet = 2 + rand.next_lim_usize(4);
+
+        let patterns = (0..1 + rand.next_lim_usize(64))
+            .map(|_| random_pattern(&mut rand, alphabet))
+            .collect::<Vec<_>>();
+
+        let haystack = (0..rand.next_lim_usize(1024))
+            .map(|_| random_byte(&mut rand, alphabet))
+            .collect::<Vec<_>>();
+
+        let mut expected = patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(id, pattern)| {
+                Scanner::from(pattern.clone())
+                    .find_all_offsets(None, &haystack)
+                    .into_iter()
+                    .map(move |offset| (id, offset))
+            })
+            .collect::<Vec<_>>();
+        expected.sort_unstable_by_key(|&(id, offset)| (offset, id));
+
+        let scanner = MultiScanner::new(patterns.iter().cloned());
+        assert_eq!(
+            scanner.find_all(&haystack),
+            expected,
+            "patterns {:?}",
+            patterns
+        );
+        assert_eq!(
+            scanner.find_first_any(&haystack),
+            expected.first().copied(),
+            "patterns {:?}",
+            patterns
+        );
+    }
+}
+
+#[test]
+fn shared_and_unanchored_patterns() {
+    let scanner = MultiScanner::new([
+        Pattern::new("48 89 ?? 24"),
+        Pattern::new("?? 48 89"),
+        Pattern::from_masked_bytes(&[0; 2], &[0; 2]),
+        Pattern::new("48 89 5c 24"),
+    ]);
+    let haystack = [0xcc, 0x48, 0x89, 0x5c, 0x24];
+
+    assert_eq!(
+        scanner.find_all(&haystack),
+        [(1, 0), (2, 0), (0, 1), (2, 1), (3, 1), (2, 2), (2, 3)]
+    );
+    assert_eq!(scanner.find_first_any(&haystack), Some((1, 0)));
+    assert_eq!(scanner.find_first_any(&haystack[1..]), Some((0, 0)));
+    assert_eq!(scanner.patterns().len(), 4);
+}
+
+#[test]
+fn first_match_by_start() {
+    // the long pattern starts first, but its needle ends after the short one
+    let scanner = MultiScanner::new([
+        Pattern::new("c3 cc"),
+        Pattern::new("90 ?? ?? ?? c3 cc cc cc"),
+    ]);
+    let haystack = [0x90, 0x00, 0xc3, 0xcc, 0xc3, 0xcc, 0xcc, 0xcc];
+
+    assert_eq!(scanner.find_first_any(&haystack), Some((1, 0)));
+    assert_eq!(scanner.find_first_any(&haystack[1..]), Some((0, 1)));
+}
+
+#[test]
+fn no_patterns() {
+    let scanner = MultiScanner::new([]);
+    assert!(scanner.find_all(&[0x48, 0x89]).is_empty());
+    assert_eq!(scanner.find_first_any(&[0x48, 0x89]), None);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "no-simd")]
+
+use lightningscanner::{ScanMode, Scanner, UnsupportedMode};
+
+#[test]
+fn only_scalar_supported() {
+    assert!(ScanMode::Auto.is_supported());
+    assert!(ScanMode::Scalar.is_supported());
+    assert!(!ScanMode::Sse42.is_supported());
+    assert!(!ScanMode::Avx2.is_supported());
+}
+
+#[test]
+fn every_mode_scans() {
+    let mut binary = vec![0x00; 1000];
+    binary[700..708].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x6c]);
+
+    let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+    for mode in [
+        ScanMode::Auto,
+        ScanMode::Scalar,
+        ScanMode::Sse42,
+        ScanMode::Avx2,
+    ] {
+        assert_eq!(scanner.find_all_offsets(Some(mode), &binary), [700]);
+    }
+
+    // SAFETY: the vector is valid for its length
+    let result = unsafe { scanner.try_find(ScanMode::Avx2, binary.as_ptr(), binary.len()) };
+    assert_eq!(
+        result,
+        Err(UnsupportedMode {
+            mode: ScanMode::Avx2
+        })
+    );
+}
@@ -0,0 +1,118 @@
+use lightningscanner::aligned_bytes::AlignedBytes;
+use lightningscanner::pattern::Pattern;
+use lightningscanner::range_pattern::RangePattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn equivalent_spellings_share_a_canonical_string() {
+    let spellings = [
+        "48 8b ?? ?? c3",
+        "48 8B ? ? C3",
+        "  48\t8b ?{2}   c3 ",
+        "48 8b /* mov */ ?? ?? // load\n c3",
+    ];
+
+    for spelling in spellings {
+        assert_eq!(
+            Pattern::new(spelling).canonical_string(),
+            "48 8b ?? ?? c3",
+            "{:?}",
+            spelling
+        );
+    }
+}
+
+#[test]
+fn canonical_string_round_trips() {
+    for text in [
+        "48 8b 05 @disp ?? ?? ?? ?? @next c3",
+        "e8 ?? ?? ?? ?? ^",
+        "",
+        "?? ?? 90",
+    ] {
+        let pattern = Pattern::new(text);
+        assert_eq!(Pattern::new(&pattern.canonical_string()), pattern);
+        assert_eq!(format!("{}", pattern), text);
+    }
+
+    let pattern = RangePattern::new("[48-4f] 89 [00-7f]");
+    assert_eq!(pattern.pattern().canonical_string(), "[48-4f] 89 [00-7f]");
+}
+
+#[test]
+fn partial_masks() {
+    let data = [0x4a, 0x4a, 0x4a, 0x4a, 0x4a];
+    let mask = [0xf0, 0x0f, 0x81, 0xff, 0xfe];
+
+    let pattern = Pattern::from_parts(
+        AlignedBytes::<32>::new(&data),
+        AlignedBytes::<32>::new(&mask),
+        5,
+    );
+    assert_eq!(pattern.canonical_string(), "[40-4f] ?a ?? 4a [4a-4b]");
+}
+
+#[test]
+fn normalize_trims_trailing_wildcards() {
+    assert_eq!(
+        Pattern::new("48 8b ?? ??").normalize(),
+        Pattern::new("48 8b")
+    );
+    assert_eq!(
+        Pattern::new("?? 8b ?? ??").normalize(),
+        Pattern::new("?? 8b")
+    );
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0; 2], &[0; 2]).normalize(),
+        Pattern::new("")
+    );
+
+    let untrimmed = Pattern::new("48 8b ?? ??").normalize_with(false);
+    assert_eq!(untrimmed, Pattern::new("48 8b ?? ??"));
+    assert_eq!(untrimmed.len(), 4);
+}
+
+#[test]
+fn normalize_keeps_the_cursor_and_labels() {
+    let pattern = Pattern::new("e8 ?? ?? ?? ?? ^ ?? ??").normalize();
+    assert_eq!(pattern, Pattern::new("e8 ?? ?? ?? ?? ^"));
+    assert_eq!(pattern.cursor(), 5);
+
+    let pattern = Pattern::new("48 8b 05 @disp ?? ?? ?? ??").normalize();
+    assert_eq!(pattern, Pattern::new("48 8b 05 @disp"));
+    assert_eq!(pattern.labels().collect::<Vec<_>>(), [("disp", 3)]);
+}
+
+#[test]
+fn normalize_clears_ignored_bits_and_padding() {
+    let data = [0x48, 0xff, 0x4f];
+    let mask = [0xff, 0x00, 0xf8];
+
+    let pattern = Pattern::from_parts(
+        AlignedBytes::<32>::new(&data),
+        AlignedBytes::<32>::new(&mask),
+        3,
+    )
+    .pad_to(64);
+
+    let normalized = pattern.normalize_with(false);
+    assert_eq!(
+        normalized,
+        RangePattern::new("48 ?? [48-4f]").pattern().clone()
+    );
+    assert_eq!(normalized.padded_len(), Pattern::ALIGNMENT);
+}
+
+#[test]
+fn trimming_only_adds_matches_at_the_end() {
+    let pattern = Pattern::new("90 ?? 90 ?? ?? ??");
+    let full = Scanner::from(pattern.clone());
+    let trimmed = Scanner::from(pattern.normalize());
+
+    let binary = [0x90; 16];
+    let full_offsets = full.find_all_offsets(None, &binary);
+    let trimmed_offsets = trimmed.find_all_offsets(None, &binary);
+
+    assert_eq!(full_offsets, (0..=10).collect::<Vec<_>>());
+    assert_eq!(trimmed_offsets, (0..=13).collect::<Vec<_>>());
+}
@@ -0,0 +1,51 @@
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn find_all_is_strictly_ascending() {
+    let binary = [0x90; 64];
+
+    for pattern in ["90", "90 90 90", "?? 90", "90 ?? ?? 90"] {
+        let scanner = Scanner::new(pattern);
+
+        for mode in [None, Some(ScanMode::Scalar)] {
+            let offsets = scanner.find_all_offsets(mode, &binary);
+            assert!(
+                offsets.windows(2).all(|pair| pair[0] < pair[1]),
+                "{}",
+                pattern
+            );
+
+            // SAFETY: binary is a valid slice
+            let results = unsafe { scanner.find_all(mode, binary.as_ptr(), binary.len()) };
+            assert!(
+                results.windows(2).all(|pair| pair[0] < pair[1]),
+                "{}",
+                pattern
+            );
+            assert_eq!(results.len(), offsets.len());
+        }
+    }
+}
+
+#[test]
+fn results_sort_by_address() {
+    let binary = [0x48, 0x89, 0x5c, 0x24, 0x48, 0x89, 0xc3, 0xc3];
+
+    // SAFETY: binary is a valid slice
+    let mut results = unsafe {
+        let mut results = Scanner::new("c3").find_all(None, binary.as_ptr(), binary.len());
+        results.extend(Scanner::new("48 89").find_all(None, binary.as_ptr(), binary.len()));
+        results.push(Scanner::new("cc").find(None, binary.as_ptr(), binary.len()));
+        results
+    };
+    results.sort();
+
+    assert!(!results[0].is_valid());
+    assert_eq!(
+        results[1..]
+            .iter()
+            .map(|result| result.get_addr() as usize - binary.as_ptr() as usize)
+            .collect::<Vec<_>>(),
+        [0, 4, 6, 7]
+    );
+}
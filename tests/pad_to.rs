@@ -0,0 +1,63 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn padded_len() {
+    let pattern = Pattern::new("48 8b ?? c3 ^ @next 90");
+
+    for align in [1, 16, 32, Pattern::ALIGNMENT] {
+        let padded = pattern.pad_to(align);
+        assert_eq!(padded.padded_len(), Pattern::ALIGNMENT);
+        assert_eq!(padded, pattern);
+    }
+
+    for align in [128, 4096] {
+        let padded = pattern.pad_to(align);
+        assert_eq!(padded.padded_len(), align);
+        assert_eq!(padded.len(), pattern.len());
+        assert_eq!(padded.cursor(), pattern.cursor());
+        assert!(padded.labels().eq(pattern.labels()));
+        assert_eq!(padded, pattern);
+
+        // clones keep the padding
+        assert_eq!(padded.clone().padded_len(), align);
+    }
+
+    let long = Pattern::new(&["cc"; 130].join(" "));
+    assert_eq!(long.padded_len(), 192);
+    assert_eq!(long.pad_to(128).padded_len(), 256);
+
+    assert_eq!(Pattern::new("").padded_len(), Pattern::ALIGNMENT);
+    assert_eq!(Pattern::new("").pad_to(128).padded_len(), 128);
+}
+
+#[test]
+fn same_matches() {
+    let mut binary = vec![0x00; 1000];
+    binary[300..305].copy_from_slice(&[0x48, 0x8b, 0x05, 0xc3, 0x90]);
+    binary[995..].copy_from_slice(&[0x48, 0x8b, 0x06, 0xc3, 0x90]);
+
+    let pattern = Pattern::new("48 8b ?? c3 90");
+    let expected = Scanner::from(pattern.clone()).find_all_offsets(None, &binary);
+    assert_eq!(expected, [300, 995]);
+
+    for align in [16, 128, 1024] {
+        let scanner = Scanner::from(pattern.pad_to(align));
+
+        for mode in [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2] {
+            assert_eq!(scanner.find_all_offsets(Some(mode), &binary), expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn not_a_power_of_two() {
+    Pattern::new("48 8b").pad_to(48);
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn zero() {
+    Pattern::new("48 8b").pad_to(0);
+}
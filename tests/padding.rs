@@ -0,0 +1,93 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+
+const MODES: [Option<ScanMode>; 4] = [
+    None,
+    Some(ScanMode::Scalar),
+    Some(ScanMode::Sse42),
+    Some(ScanMode::Avx2),
+];
+
+/// Lengths around the vector sizes and the pattern alignment
+const LENGTHS: [usize; 13] = [1, 15, 16, 17, 31, 32, 33, 63, 64, 65, 127, 128, 129];
+
+/// Pattern of `len` bytes with every fifth byte a wildcard, and its bytes
+fn pattern(len: usize) -> (Pattern, Vec<u8>) {
+    let bytes = (0..len)
+        .map(|i| (i as u8).wrapping_mul(7).wrapping_add(1))
+        .collect::<Vec<_>>();
+    let mask = (0..len)
+        .map(|i| if i % 5 == 4 { 0x00 } else { 0xff })
+        .collect::<Vec<_>>();
+
+    (Pattern::from_masked_bytes(&bytes, &mask), bytes)
+}
+
+#[test]
+fn match_flush_with_the_end() {
+    for len in LENGTHS {
+        let (pattern, bytes) = pattern(len);
+
+        for filler in [0x00, 0xff] {
+            for prefix in [0, 1, 7, 64] {
+                let mut haystack = vec![filler; prefix];
+                haystack.extend_from_slice(&bytes);
+
+                let scanner = Scanner::from(pattern.clone());
+                for mode in MODES {
+                    assert_eq!(
+                        scanner.find_all_offsets(mode, &haystack),
+                        [prefix],
+                        "{} byte pattern after {} bytes of {:#04x} with {:?}",
+                        len,
+                        prefix,
+                        filler,
+                        mode
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bytes_after_the_match_are_ignored() {
+    for len in LENGTHS {
+        let (pattern, bytes) = pattern(len);
+
+        // a padding byte taking part in the match would see different bytes after it
+        for filler in [0x00, 0x55, 0xff] {
+            let mut haystack = vec![0xee; 3];
+            haystack.extend_from_slice(&bytes);
+            haystack.extend_from_slice(&[filler; 200]);
+
+            for padded in [pattern.clone(), pattern.pad_to(256)] {
+                let scanner = Scanner::from(padded);
+                for mode in MODES {
+                    assert_eq!(
+                        scanner.find_all_offsets(mode, &haystack),
+                        [3],
+                        "{} byte pattern followed by {:#04x} with {:?}",
+                        len,
+                        filler,
+                        mode
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn haystack_shorter_than_the_padding() {
+    // the pattern is padded to 64 bytes, the haystack holds nothing but the match
+    let scanner = Scanner::new("48 8b ?? c3");
+
+    for mode in MODES {
+        assert_eq!(
+            scanner.find_all_offsets(mode, &[0x48, 0x8b, 0x00, 0xc3]),
+            [0]
+        );
+        assert_eq!(scanner.find_all_offsets(mode, &[0x48, 0x8b, 0x00]), []);
+    }
+}
@@ -0,0 +1,47 @@
+use lightningscanner::{ScanResult, Scanner};
+
+/// Buffer aligned to a page, so offsets within it translate into known page bases
+#[repr(C, align(4096))]
+struct Pages([u8; 3 * 4096]);
+
+#[test]
+fn page_base() {
+    let mut pages = Box::new(Pages([0x00; 3 * 4096]));
+    pages.0[4096 + 100..4096 + 104].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let base = pages.0.as_ptr() as usize;
+    let scanner = Scanner::new("48 89 5c 24");
+
+    // SAFETY: the buffer is valid for its length
+    let result = unsafe { scanner.find(None, pages.0.as_ptr(), pages.0.len()) };
+
+    assert_eq!(result.page_base(4096), Some(base + 4096));
+    assert_eq!(result.default_page_base(), Some(base + 4096));
+    assert_eq!(
+        result.page_base(2 * 4096),
+        Some((base + 4096) & !(2 * 4096 - 1))
+    );
+    assert_eq!(result.page_base(1), Some(base + 4096 + 100));
+    assert_eq!(ScanResult::DEFAULT_PAGE_SIZE, 4096);
+}
+
+#[test]
+fn invalid_result() {
+    let binary = [0x00; 16];
+
+    // SAFETY: the array is valid for its length
+    let result = unsafe { Scanner::new("cc").find(None, binary.as_ptr(), binary.len()) };
+
+    assert_eq!(result.page_base(4096), None);
+    assert_eq!(result.default_page_base(), None);
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn not_a_power_of_two() {
+    let binary = [0xcc];
+
+    // SAFETY: the array is valid for its length
+    let result = unsafe { Scanner::new("cc").find(None, binary.as_ptr(), binary.len()) };
+    result.page_base(3000);
+}
@@ -0,0 +1,76 @@
+use lightningscanner::{ScanResult, Scanner};
+
+#[test]
+fn matches_grouped_by_page() {
+    let mut binary = vec![0x00; 0x4000];
+    for offset in [0x10, 0x800, 0x1ffe, 0x2000, 0x3fff] {
+        binary[offset] = 0xcc;
+    }
+
+    let scanner = Scanner::new("cc");
+    // SAFETY: binary is a valid slice
+    let pages = unsafe { scanner.find_all_by_page(None, binary.as_ptr(), binary.len(), 0x1000) };
+    // SAFETY: binary is a valid slice
+    let results = unsafe { scanner.find_all(None, binary.as_ptr(), binary.len()) };
+
+    let mut grouped = Vec::new();
+    for (&base, offsets) in &pages {
+        assert_eq!(base % 0x1000, 0);
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+
+        grouped.extend(offsets.iter().map(|offset| base + offset));
+    }
+
+    let addresses = results
+        .iter()
+        .map(|result| result.get_addr() as usize)
+        .collect::<Vec<_>>();
+    assert_eq!(grouped, addresses);
+
+    for result in results {
+        let base = result.page_base(0x1000).unwrap();
+        assert!(pages[&base].contains(&(result.get_addr() as usize - base)));
+    }
+}
+
+#[test]
+fn cursor_decides_the_page() {
+    let binary = vec![0x90; 0x2000];
+    let scanner = Scanner::new("90 90 ^ 90");
+
+    // SAFETY: binary is a valid slice
+    let pages = unsafe {
+        scanner.find_all_by_page(
+            None,
+            binary.as_ptr(),
+            binary.len(),
+            ScanResult::DEFAULT_PAGE_SIZE,
+        )
+    };
+
+    let count = pages.values().map(Vec::len).sum::<usize>();
+    assert_eq!(count, 0x2000 - 2);
+
+    let first = binary.as_ptr() as usize + 2;
+    let (&base, offsets) = pages.iter().next().unwrap();
+    assert_eq!(base + offsets[0], first);
+}
+
+#[test]
+fn no_matches() {
+    let binary = [0x00; 64];
+
+    // SAFETY: binary is a valid slice
+    let pages =
+        unsafe { Scanner::new("cc").find_all_by_page(None, binary.as_ptr(), binary.len(), 4096) };
+    assert!(pages.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn page_size_must_be_a_power_of_two() {
+    let binary = [0xcc; 4];
+
+    // SAFETY: binary is a valid slice
+    unsafe { Scanner::new("cc").find_all_by_page(None, binary.as_ptr(), binary.len(), 3000) };
+}
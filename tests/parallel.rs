@@ -0,0 +1,97 @@
+#![cfg(feature = "rayon")]
+
+use lightningscanner::parallel::ParallelConfig;
+use lightningscanner::Scanner;
+use rayon::ThreadPoolBuilder;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn matches_sequential_scan() {
+    let mut rand = Wyrand::seed(0x9a7a);
+    let binary = (0..64 * 1024)
+        .map(|_| rand.next_lim_u16(4) as u8)
+        .collect::<Vec<_>>();
+
+    for pattern in [
+        "00 01 ?? 02",
+        "03",
+        "01 ?? ?? ?? ?? ?? 01",
+        "00 00 00 00 00 00",
+    ] {
+        let scanner = Scanner::new(pattern);
+        let expected = scanner.find_all_offsets(None, &binary);
+
+        for threads in [1, 2, 3, 8] {
+            for chunk_bytes in [0, 1, 3, 4096, 1 << 20] {
+                let config = ParallelConfig {
+                    threads: Some(threads),
+                    chunk_bytes,
+                    use_current_pool: false,
+                };
+
+                assert_eq!(
+                    scanner.find_all_parallel(None, &binary, &config),
+                    expected,
+                    "{} with {} threads and {} byte chunks",
+                    pattern,
+                    threads,
+                    chunk_bytes
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn straddling_chunks() {
+    let mut binary = vec![0x00; 100];
+    binary[6..10].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    binary[62..66].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let scanner = Scanner::new("48 89 5c 24");
+    let config = ParallelConfig {
+        chunk_bytes: 8,
+        ..ParallelConfig::default()
+    };
+
+    assert_eq!(scanner.find_all_parallel(None, &binary, &config), [6, 62]);
+}
+
+#[test]
+fn current_pool() {
+    let binary = [0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x5c];
+    let scanner = Scanner::new("48 89 5c");
+
+    let pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    let config = ParallelConfig {
+        threads: Some(64),
+        chunk_bytes: 1,
+        use_current_pool: true,
+    };
+
+    let offsets = pool.install(|| {
+        assert_eq!(rayon::current_num_threads(), 2);
+        scanner.find_all_parallel(None, &binary, &config)
+    });
+
+    assert_eq!(offsets, [0, 5]);
+}
+
+#[test]
+fn edge_cases() {
+    let config = ParallelConfig {
+        chunk_bytes: 2,
+        ..ParallelConfig::default()
+    };
+
+    let scanner = Scanner::new("48 89");
+    assert!(scanner.find_all_parallel(None, &[], &config).is_empty());
+    assert!(scanner.find_all_parallel(None, &[0x48], &config).is_empty());
+
+    let empty = Scanner::new("");
+    assert_eq!(empty.find_all_parallel(None, &[], &config), [0]);
+    assert_eq!(
+        empty.find_all_parallel(None, &[1, 2, 3], &config),
+        empty.find_all_offsets(None, &[1, 2, 3])
+    );
+}
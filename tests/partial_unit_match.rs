@@ -0,0 +1,53 @@
+use lightningscanner::{ScanMode, Scanner};
+
+const MODES: [ScanMode; 3] = [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2];
+
+fn find_offset(scanner: &Scanner, mode: ScanMode, data: &[u8]) -> Option<usize> {
+    // SAFETY: data is a valid slice
+    let result = unsafe { scanner.find(Some(mode), data.as_ptr(), data.len()) };
+
+    if result.is_valid() {
+        Some(result.get_addr() as usize - data.as_ptr() as usize)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn match_inside_partial_match() {
+    // the first unit of the pattern already matches at offset 0,
+    // the full pattern only matches a few bytes later
+    let pattern = format!("{}bb", "aa ".repeat(32));
+    let scanner = Scanner::new(&pattern);
+
+    let mut data = [0xaa; 128];
+    data[36] = 0xbb;
+
+    for mode in MODES {
+        assert_eq!(find_offset(&scanner, mode, &data), Some(4), "{mode:?}");
+    }
+}
+
+#[test]
+fn match_at_binary_end() {
+    let scanner = Scanner::new("5c c9 44");
+
+    let mut data = [0x00; 64];
+    data[61..].copy_from_slice(&[0x5c, 0xc9, 0x44]);
+
+    for mode in MODES {
+        assert_eq!(find_offset(&scanner, mode, &data), Some(61), "{mode:?}");
+    }
+}
+
+#[test]
+fn truncated_match_at_binary_end() {
+    let scanner = Scanner::new("5c c9 44");
+
+    let mut data = [0x00; 64];
+    data[62..].copy_from_slice(&[0x5c, 0xc9]);
+
+    for mode in MODES {
+        assert_eq!(find_offset(&scanner, mode, &data), None, "{mode:?}");
+    }
+}
@@ -0,0 +1,106 @@
+use lightningscanner::patch::Patch;
+use lightningscanner::{ScanMode, Scanner};
+
+#[test]
+fn patches_every_site() {
+    let mut region = [
+        0x48, 0x85, 0xc0, 0x74, 0x05, 0x90, 0x48, 0x85, 0xc0, 0x74, 0x20,
+    ];
+
+    let scanner = Scanner::new("48 85 c0 74 ??");
+    let patched = scanner.patch_matches(&mut region, &Patch::new(3, vec![0xeb]));
+
+    assert_eq!(patched, 2);
+    assert_eq!(
+        region,
+        [0x48, 0x85, 0xc0, 0xeb, 0x05, 0x90, 0x48, 0x85, 0xc0, 0xeb, 0x20]
+    );
+}
+
+#[test]
+fn masked_patch() {
+    let mut region = [0xf0, 0x0f, 0xaa];
+
+    let scanner = Scanner::new("f0 0f");
+    let patch = Patch::new(0, vec![0x0a, 0xff]).with_mask(vec![0x0f, 0x00]);
+
+    assert_eq!(scanner.patch_matches(&mut region, &patch), 1);
+    assert_eq!(region, [0xfa, 0x0f, 0xaa]);
+}
+
+#[test]
+fn overlapping_matches_are_reverified() {
+    let mut region = [0xaa; 6];
+
+    // every position matches initially, but patching one site destroys the next one
+    let scanner = Scanner::new("aa aa");
+    let patched = scanner.patch_matches(&mut region, &Patch::new(1, vec![0x00]));
+
+    assert_eq!(patched, 3);
+    assert_eq!(region, [0xaa, 0x00, 0xaa, 0x00, 0xaa, 0x00]);
+}
+
+#[test]
+fn patch_past_end_is_skipped() {
+    let mut region = [0x74, 0x05, 0xcc, 0x74, 0x10];
+
+    let scanner = Scanner::new("74 ??");
+    let patch = Patch::new(1, vec![0x00, 0x00]);
+
+    assert_eq!(scanner.patch_matches(&mut region, &patch), 1);
+    assert_eq!(region, [0x74, 0x00, 0x00, 0x74, 0x10]);
+}
+
+#[test]
+fn raw_region() {
+    let mut region = vec![0xe8, 0x00, 0x00, 0x00, 0x00, 0xe8, 0x11, 0x22, 0x33, 0x44];
+
+    let scanner = Scanner::new("e8 ?? ?? ?? ??");
+    let patch = Patch::new(0, vec![0x90; 5]);
+    // SAFETY: region is a valid mutable slice
+    let patched = unsafe { scanner.patch_at_matches(region.as_mut_ptr(), region.len(), &patch) };
+
+    assert_eq!(patched, 2);
+    assert_eq!(region, [0x90; 10]);
+}
+
+#[test]
+fn find_and_patch_first_match() {
+    let mut data = [
+        0x90, 0x48, 0x85, 0xc0, 0x74, 0x05, 0x48, 0x85, 0xc0, 0x74, 0x20,
+    ];
+
+    let scanner = Scanner::new("48 85 c0 74 ??");
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        let mut data = data;
+        assert_eq!(
+            scanner.find_and_patch(&mut data, mode, &[0x31, 0xc0]),
+            Some(1)
+        );
+        assert_eq!(
+            data,
+            [0x90, 0x31, 0xc0, 0xc0, 0x74, 0x05, 0x48, 0x85, 0xc0, 0x74, 0x20]
+        );
+    }
+
+    // the first match is gone after patching, so the second one is next
+    assert_eq!(scanner.find_and_patch(&mut data, None, &[0x00]), Some(1));
+    assert_eq!(scanner.find_and_patch(&mut data, None, &[0x00]), Some(6));
+    assert_eq!(scanner.find_and_patch(&mut data, None, &[0x00]), None);
+}
+
+#[test]
+fn find_and_patch_refuses_to_overflow() {
+    let mut data = [0xcc, 0xcc, 0x74, 0x05];
+    let scanner = Scanner::new("74 ??");
+
+    assert_eq!(scanner.find_and_patch(&mut data, None, &[0x90; 3]), None);
+    assert_eq!(data, [0xcc, 0xcc, 0x74, 0x05]);
+
+    // a patch filling the rest of the buffer exactly is fine
+    assert_eq!(scanner.find_and_patch(&mut data, None, &[0x90; 2]), Some(2));
+    assert_eq!(data, [0xcc, 0xcc, 0x90, 0x90]);
+
+    assert_eq!(scanner.find_and_patch(&mut [], None, &[]), None);
+}
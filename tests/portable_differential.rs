@@ -0,0 +1,58 @@
+#![cfg(feature = "portable-simd")]
+
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+const ITERATIONS: usize = 2000;
+
+fn find(scanner: &Scanner, mode: ScanMode, haystack: &[u8]) -> Option<usize> {
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(Some(mode), haystack.as_ptr(), haystack.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - haystack.as_ptr() as usize)
+}
+
+#[test]
+fn matches_scalar() {
+    let mut rand = Wyrand::seed(0x5139d);
+
+    for _ in 0..ITERATIONS {
+        // a small alphabet makes partial and repeated matches likely
+        let alphabet = 2 + rand.next_lim_usize(6);
+
+        // patterns spanning several vector units exercise the multi-block comparison
+        let pattern_len = 1 + rand.next_lim_usize(96);
+        let pattern = (0..pattern_len)
+            .map(|_| {
+                if rand.next_bool(Probability::new(0.25)) {
+                    "??".to_string()
+                } else {
+                    format!("{:02x}", rand.next_lim_usize(alphabet))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let haystack_len = rand.next_lim_usize(512);
+        let haystack = (0..haystack_len)
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        // patterns of wildcards only are rejected
+        let Ok(parsed) = Pattern::try_new(&pattern) else {
+            continue;
+        };
+        let scanner = Scanner::from(parsed);
+
+        assert_eq!(
+            find(&scanner, ScanMode::Portable, &haystack),
+            find(&scanner, ScanMode::Scalar, &haystack),
+            "pattern `{}` in {:02x?}",
+            pattern,
+            haystack
+        );
+    }
+}
@@ -0,0 +1,196 @@
+#![cfg(target_os = "linux")]
+
+use lightningscanner::process::{self, ProcessScanOptions, ScanFilter};
+use lightningscanner::{ScanError, ScanMode, Scanner};
+use std::time::Duration;
+
+/// Build a signature at runtime, so it only exists on the heap
+fn signature() -> Vec<u8> {
+    (0..24u8).map(|i| i.wrapping_mul(37) ^ 0x5a).collect()
+}
+
+fn to_ida(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn regions() {
+    let regions = process::regions(std::process::id()).unwrap();
+
+    assert!(regions
+        .iter()
+        .any(|region| region.path.as_deref() == Some("[stack]")));
+    assert!(regions.iter().any(|region| region.executable));
+    assert!(regions.iter().all(|region| region.start <= region.end));
+}
+
+#[test]
+fn region_path_with_spaces() {
+    let path = std::env::temp_dir().join(format!("lightning scanner {}", std::process::id()));
+    std::fs::write(&path, [0x90; 0x1000]).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    // SAFETY: the file is mapped read-only and unmapped again below
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            0x1000,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            std::os::fd::AsRawFd::as_raw_fd(&file),
+            0,
+        )
+    };
+    assert_ne!(ptr, libc::MAP_FAILED);
+
+    let regions = process::regions(std::process::id()).unwrap();
+
+    // SAFETY: ptr was mapped above with the same length
+    unsafe { libc::munmap(ptr, 0x1000) };
+    std::fs::remove_file(&path).unwrap();
+
+    let region = regions
+        .iter()
+        .find(|region| region.start == ptr as usize)
+        .unwrap();
+    assert_eq!(region.path.as_deref(), path.to_str());
+}
+
+#[test]
+fn own_process() {
+    let mut target = vec![0u8; 0x1000];
+    target[0x100..0x118].copy_from_slice(&signature());
+    let address = target[0x100..].as_ptr() as usize;
+
+    for mode in [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2] {
+        let scanner = Scanner::new(&to_ida(&signature()));
+        let options = ProcessScanOptions {
+            preferred_scan_mode: Some(mode),
+            ..ProcessScanOptions::default()
+        };
+
+        let results = scanner
+            .find_all_in_process(std::process::id(), options)
+            .unwrap();
+        assert!(results.contains(&address));
+        assert!(results.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert!(scanner
+            .find_in_process(std::process::id(), options)
+            .unwrap()
+            .is_some());
+    }
+}
+
+#[test]
+fn timeout() {
+    let scanner = Scanner::new(&to_ida(&signature()));
+    let options = ProcessScanOptions {
+        timeout: Some(Duration::ZERO),
+        ..ProcessScanOptions::default()
+    };
+
+    let result = scanner.find_in_process(std::process::id(), options);
+    assert!(matches!(result, Err(ScanError::TimedOut)));
+}
+
+#[test]
+fn filter_permissions() {
+    let region = |writable, executable| process::Region {
+        start: 0x1000,
+        end: 0x2000,
+        readable: true,
+        writable,
+        executable,
+        path: None,
+    };
+
+    assert!(ScanFilter::default().matches(&region(true, false)));
+    assert!(ScanFilter::CODE.matches(&region(false, true)));
+    assert!(ScanFilter::CODE.matches(&region(true, true)));
+    assert!(!ScanFilter::CODE.matches(&region(true, false)));
+
+    let read_only = ScanFilter {
+        executable: false,
+        writable: Some(false),
+    };
+    assert!(read_only.matches(&region(false, false)));
+    assert!(read_only.matches(&region(false, true)));
+    assert!(!read_only.matches(&region(true, false)));
+}
+
+#[test]
+fn filtered_process_scan() {
+    let mut target = vec![0u8; 0x1000];
+    target[0x100..0x118].copy_from_slice(&signature());
+    let address = target[0x100..].as_ptr() as usize;
+
+    let scanner = Scanner::new(&to_ida(&signature()));
+    let scan = |filter| {
+        let options = ProcessScanOptions {
+            filter,
+            ..ProcessScanOptions::default()
+        };
+        scanner
+            .find_all_in_process(std::process::id(), options)
+            .unwrap()
+    };
+
+    // the heap is writable and not executable
+    assert!(scan(ScanFilter::CODE).is_empty());
+    assert!(!scan(ScanFilter {
+        executable: false,
+        writable: Some(false),
+    })
+    .contains(&address));
+    assert!(scan(ScanFilter {
+        executable: false,
+        writable: Some(true),
+    })
+    .contains(&address));
+}
+
+#[test]
+fn lazy_process_scan() {
+    let mut target = vec![0u8; 0x1000];
+    target[0x100..0x118].copy_from_slice(&signature());
+    target[0x800..0x818].copy_from_slice(&signature());
+    let addresses = [
+        target[0x100..].as_ptr() as usize,
+        target[0x800..].as_ptr() as usize,
+    ];
+
+    let scanner = Scanner::new(&to_ida(&signature()));
+    let found = scanner
+        .find_iter_process(std::process::id(), ScanFilter::default())
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+
+    assert!(addresses.iter().all(|address| found.contains(address)));
+    assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // stopping early
+    let first = scanner
+        .find_iter_process(std::process::id(), ScanFilter::default())
+        .find_map(Result::ok);
+    assert_eq!(first, found.first().copied());
+
+    assert!(scanner
+        .find_iter_process(std::process::id(), ScanFilter::CODE)
+        .filter_map(Result::ok)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn lazy_process_scan_of_missing_process() {
+    let scanner = Scanner::new(&to_ida(&signature()));
+    let mut results = scanner.find_iter_process(u32::MAX, ScanFilter::default());
+
+    assert!(results.next().unwrap().is_err());
+    assert!(results.next().is_none());
+}
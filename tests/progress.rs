@@ -0,0 +1,83 @@
+use lightningscanner::{ScanMode, Scanner};
+
+const INTERVAL: usize = Scanner::PROGRESS_INTERVAL;
+
+#[test]
+fn reports_every_interval() {
+    let binary = vec![0x00; 2 * INTERVAL + 100];
+    let mut reports = Vec::new();
+
+    let scanner = Scanner::new("48 89 5c 24");
+    // SAFETY: the vector is valid for its length
+    let result = unsafe {
+        scanner.find_with_progress(None, binary.as_ptr(), binary.len(), |scanned| {
+            reports.push(scanned)
+        })
+    };
+
+    assert!(!result.is_valid());
+    assert_eq!(reports, [INTERVAL, 2 * INTERVAL, 2 * INTERVAL + 100]);
+}
+
+#[test]
+fn stops_reporting_after_a_match() {
+    let mut binary = vec![0x00; 3 * INTERVAL];
+    binary[INTERVAL + 10..INTERVAL + 14].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        let mut reports = Vec::new();
+
+        let scanner = Scanner::new("48 89 ?? 24");
+        // SAFETY: the vector is valid for its length
+        let result = unsafe {
+            scanner.find_with_progress(mode, binary.as_ptr(), binary.len(), |scanned| {
+                reports.push(scanned)
+            })
+        };
+
+        assert_eq!(
+            result.get_addr() as usize - binary.as_ptr() as usize,
+            INTERVAL + 10
+        );
+        assert_eq!(reports, [INTERVAL]);
+    }
+}
+
+#[test]
+fn matches_across_intervals() {
+    let mut binary = vec![0x00; 2 * INTERVAL];
+    binary[INTERVAL - 2..INTERVAL + 2].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    let scanner = Scanner::new("48 89 5c ^ 24");
+    // SAFETY: the vector is valid for its length
+    let result = unsafe { scanner.find_with_progress(None, binary.as_ptr(), binary.len(), |_| {}) };
+
+    // SAFETY: the vector is valid for its length
+    let expected = unsafe { scanner.find(None, binary.as_ptr(), binary.len()) };
+    assert_eq!(result, expected);
+    assert_eq!(
+        result.get_addr() as usize - binary.as_ptr() as usize,
+        INTERVAL + 1
+    );
+}
+
+#[test]
+fn small_binaries() {
+    let binary = [0xcc, 0x48, 0x89];
+    let mut reports = Vec::new();
+
+    let scanner = Scanner::new("48 89");
+    // SAFETY: the array is valid for its length
+    let result = unsafe {
+        scanner.find_with_progress(None, binary.as_ptr(), binary.len(), |scanned| {
+            reports.push(scanned)
+        })
+    };
+
+    assert_eq!(result.get_addr(), binary[1..].as_ptr());
+    assert!(reports.is_empty());
+
+    // SAFETY: a null pointer is reported as no match
+    let result = unsafe { scanner.find_with_progress(None, std::ptr::null(), 16, |_| {}) };
+    assert!(!result.is_valid());
+}
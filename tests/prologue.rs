@@ -0,0 +1,53 @@
+use lightningscanner::{PrologueFilter, ScanMode, Scanner};
+
+const PATTERN: &str = "48 89 5c 24 ?? 57";
+
+const DATA_SET: [u8; 48] = [
+    0x48, 0x89, 0x5c, 0x24, 0x08, 0x57, 0x48, 0x83, 0xec, 0x20, 0x48, 0x89, 0x5c, 0x24, 0x10, 0x57,
+    0xc3, 0xcc, 0xcc, 0xcc, 0x48, 0x89, 0x5c, 0x24, 0x18, 0x57, 0xc3, 0x00, 0x00, 0x00, 0x00, 0x48,
+    0x89, 0x5c, 0x24, 0x20, 0x57, 0xc3, 0xcc, 0x48, 0x89, 0x5c, 0x24, 0x28, 0x57, 0xc3, 0xcc, 0xcc,
+];
+
+fn find(filter: PrologueFilter, mode: ScanMode, data: &[u8]) -> Option<usize> {
+    let scanner = Scanner::new(PATTERN);
+    // SAFETY: data is a valid slice
+    let result = unsafe { scanner.find_prologue(Some(mode), data.as_ptr(), data.len(), filter) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - data.as_ptr() as usize)
+}
+
+#[test]
+fn int3_padding() {
+    for mode in [ScanMode::Scalar, ScanMode::Sse42, ScanMode::Avx2] {
+        // skip the match at the very beginning of the buffer
+        let result = find(PrologueFilter::default(), mode, &DATA_SET[1..]).map(|offset| offset + 1);
+        assert_eq!(result, Some(0x14));
+    }
+}
+
+#[test]
+fn zero_padding() {
+    let filter = PrologueFilter {
+        min_padding: 4,
+        int3: false,
+        zero: true,
+    };
+
+    assert_eq!(find(filter, ScanMode::Scalar, &DATA_SET[1..]), Some(0x1e));
+}
+
+#[test]
+fn buffer_start() {
+    assert_eq!(
+        find(PrologueFilter::default(), ScanMode::Scalar, &DATA_SET),
+        Some(0)
+    );
+
+    let filter = PrologueFilter {
+        min_padding: 1,
+        ..PrologueFilter::default()
+    };
+    assert_eq!(find(filter, ScanMode::Scalar, &DATA_SET[0x26..]), Some(1));
+}
@@ -0,0 +1,104 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::proximity::{Direction, ProximityQuery};
+
+fn find(haystack: &[u8], max_distance: usize, direction: Direction) -> Option<(usize, usize)> {
+    ProximityQuery::new(
+        Pattern::new("aa ??"),
+        Pattern::new("bb bb"),
+        max_distance,
+        direction,
+    )
+    .find(haystack)
+    .map(|result| (result.a(), result.b()))
+}
+
+#[test]
+fn distance_is_inclusive() {
+    let mut haystack = vec![0x00; 64];
+    haystack[20] = 0xaa;
+    haystack[30..32].copy_from_slice(&[0xbb, 0xbb]);
+
+    assert_eq!(find(&haystack, 10, Direction::After), Some((20, 30)));
+    assert_eq!(find(&haystack, 9, Direction::After), None);
+    assert_eq!(find(&haystack, 10, Direction::Either), Some((20, 30)));
+    assert_eq!(find(&haystack, 100, Direction::Before), None);
+}
+
+#[test]
+fn directions() {
+    let mut haystack = vec![0x00; 64];
+    haystack[10..12].copy_from_slice(&[0xbb, 0xbb]);
+    haystack[20] = 0xaa;
+
+    assert_eq!(find(&haystack, 10, Direction::Before), Some((20, 10)));
+    assert_eq!(find(&haystack, 9, Direction::Before), None);
+    assert_eq!(find(&haystack, 10, Direction::After), None);
+    assert_eq!(find(&haystack, 10, Direction::Either), Some((20, 10)));
+}
+
+#[test]
+fn first_qualified_a_wins() {
+    let mut haystack = vec![0x00; 64];
+    // too far from the second pattern
+    haystack[2] = 0xaa;
+    haystack[30] = 0xaa;
+    haystack[40..42].copy_from_slice(&[0xbb, 0xbb]);
+    haystack[50] = 0xaa;
+
+    assert_eq!(find(&haystack, 10, Direction::Either), Some((30, 40)));
+    assert_eq!(find(&haystack, 10, Direction::Before), Some((50, 40)));
+}
+
+#[test]
+fn closest_b_wins() {
+    let mut haystack = vec![0x00; 64];
+    haystack[24..26].copy_from_slice(&[0xbb, 0xbb]);
+    haystack[30] = 0xaa;
+    haystack[34..36].copy_from_slice(&[0xbb, 0xbb]);
+    haystack[40..42].copy_from_slice(&[0xbb, 0xbb]);
+
+    assert_eq!(find(&haystack, 10, Direction::Either), Some((30, 34)));
+    assert_eq!(find(&haystack, 10, Direction::After), Some((30, 34)));
+    assert_eq!(find(&haystack, 10, Direction::Before), Some((30, 24)));
+
+    // a tie goes to the earlier match
+    haystack[26..28].copy_from_slice(&[0xbb, 0xbb]);
+    assert_eq!(find(&haystack, 10, Direction::Either), Some((30, 26)));
+}
+
+#[test]
+fn overlapping_matches() {
+    let haystack = [0xaa, 0xbb, 0xbb];
+
+    assert_eq!(find(&haystack, 1, Direction::After), Some((0, 1)));
+    assert_eq!(find(&haystack, 0, Direction::After), None);
+
+    let query = ProximityQuery::new(
+        Pattern::new("aa"),
+        Pattern::new("aa ??"),
+        0,
+        Direction::Either,
+    );
+    assert_eq!(query.find(&haystack).map(|result| result.b()), Some(0));
+}
+
+#[test]
+fn buffer_edges() {
+    let mut haystack = vec![0x00; 32];
+    haystack[0..2].copy_from_slice(&[0xbb, 0xbb]);
+    haystack[3] = 0xaa;
+    haystack[30..32].copy_from_slice(&[0xbb, 0xbb]);
+
+    // windows are clamped at the start of the buffer
+    assert_eq!(find(&haystack, 100, Direction::Before), Some((3, 0)));
+
+    // the second pattern is cut off by the end of the buffer
+    haystack[31] = 0x00;
+    haystack[28] = 0xaa;
+    assert_eq!(find(&haystack, usize::MAX, Direction::After), None);
+
+    // the first pattern is cut off by the end of the buffer
+    let haystack = [0xbb, 0xbb, 0xaa];
+    assert_eq!(find(&haystack, usize::MAX, Direction::Either), None);
+    assert_eq!(find(&[], 10, Direction::Either), None);
+}
@@ -0,0 +1,145 @@
+use lightningscanner::pattern::{Pattern, PatternError};
+use lightningscanner::range_pattern::{RangePattern, RangeScanner};
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn prefilter_keeps_the_shared_bits() {
+    let pattern = RangePattern::new("[48-4f] 89 [00-ff] [24-24]");
+    assert_eq!(
+        pattern.ranges(),
+        [(0, 0x48..=0x4f), (2, 0x00..=0xff), (3, 0x24..=0x24)]
+    );
+
+    // the range is aligned to its lowest bits, so the prefilter matches exactly the range
+    let prefilter = Scanner::from(pattern.pattern().clone());
+    for byte in 0..=u8::MAX {
+        let binary = [byte, 0x89, 0xee, 0x24];
+
+        assert_eq!(
+            prefilter.find_all_offsets(None, &binary) == [0],
+            (0x48..=0x4f).contains(&byte),
+            "{:#04x}",
+            byte
+        );
+    }
+}
+
+#[test]
+fn ranges_not_aligned_to_bits() {
+    // shares only the top bit, so most candidates have to be rejected by the range
+    let scanner = RangeScanner::new(RangePattern::new("c3 [81-82]"));
+    let binary = [0xc3, 0x80, 0xc3, 0x81, 0xc3, 0x82, 0xc3, 0x83, 0xc3, 0xff];
+
+    for mode in [None, Some(ScanMode::Scalar)] {
+        assert_eq!(scanner.find_all(mode, &binary), [2, 4]);
+    }
+    assert_eq!(scanner.find(None, &binary), Some(2));
+}
+
+#[test]
+fn matches_at_bounds() {
+    let pattern = RangePattern::new("48 [88-8b]");
+
+    assert!(pattern.matches_at(&[0x48, 0x88], 0));
+    assert!(pattern.matches_at(&[0x48, 0x8b], 0));
+    assert!(!pattern.matches_at(&[0x48, 0x8c], 0));
+    assert!(!pattern.matches_at(&[0x48, 0x88], 1));
+    assert!(!pattern.matches_at(&[0x48, 0x88], 2));
+}
+
+#[test]
+fn plain_patterns_are_accepted() {
+    let pattern = RangePattern::new("48 8b ?{2} ^ @disp c3");
+
+    assert_eq!(pattern.pattern(), &Pattern::new("48 8b ?{2} ^ @disp c3"));
+    assert!(pattern.ranges().is_empty());
+    assert_eq!(pattern.len(), 5);
+}
+
+#[test]
+fn named_classes() {
+    let pattern = RangePattern::new("[digit] [upper] [lower] [print]");
+    assert_eq!(
+        pattern.ranges(),
+        [
+            (0, 0x30..=0x39),
+            (1, 0x41..=0x5a),
+            (2, 0x61..=0x7a),
+            (3, 0x20..=0x7e)
+        ]
+    );
+    assert_eq!(
+        pattern,
+        RangePattern::new("[30-39] [41-5a] [61-7a] [20-7e]")
+    );
+
+    let scanner = RangeScanner::new(RangePattern::new("[upper][lower][lower] 3d [digit]"));
+    let binary = b"Abc=x Ab4=1 Xyz=9 xyz=0";
+    for mode in [None, Some(ScanMode::Scalar)] {
+        assert_eq!(scanner.find_all(mode, binary), [12]);
+    }
+}
+
+#[test]
+fn malformed_ranges() {
+    for (pattern, position) in [
+        ("48 [4f-48]", 3),
+        ("48 [4f-4]", 3),
+        ("48 [4f-4g]", 3),
+        ("48 [4f 48]", 3),
+        ("48 [4f-48", 3),
+        ("[", 0),
+        ("[48]", 0),
+        ("[digit", 0),
+        ("48 [alpha]", 3),
+        ("[DIGIT]", 0),
+    ] {
+        assert_eq!(
+            RangePattern::try_new(pattern).err(),
+            Some(PatternError::MalformedRange { position }),
+            "{}",
+            pattern
+        );
+    }
+
+    // plain patterns don't support ranges
+    assert_eq!(
+        Pattern::try_new("48 [48-4f]").err(),
+        Some(PatternError::InvalidByte { position: 3 })
+    );
+}
+
+#[test]
+fn matches_naive_search() {
+    let mut rand = Wyrand::seed(0x5eed);
+
+    for _ in 0..500 {
+        let lo = rand.next_u16() as u8;
+        let hi = lo.saturating_add(rand.next_lim_u16(64) as u8);
+        let scanner = RangeScanner::new(RangePattern::new(&format!("[{:02x}-{:02x}] 5c", lo, hi)));
+
+        let haystack = (0..128)
+            .map(|_| {
+                if rand.next_lim_u16(2) == 0 {
+                    0x5c
+                } else {
+                    lo.wrapping_add(rand.next_lim_u16(96) as u8)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let expected = (0..haystack.len() - 1)
+            .filter(|&i| (lo..=hi).contains(&haystack[i]) && haystack[i + 1] == 0x5c)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            scanner.find_all(None, &haystack),
+            expected,
+            "{:02x}-{:02x} {:02x?}",
+            lo,
+            hi,
+            haystack
+        );
+    }
+}
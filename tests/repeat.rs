@@ -0,0 +1,56 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn tiles_the_pattern() {
+    let entry = Pattern::new("48 ?? 05");
+
+    assert_eq!(entry.repeat(1), entry);
+    assert_eq!(entry.repeat(3), Pattern::new("48 ?? 05 48 ?? 05 48 ?? 05"));
+    assert_eq!(entry.repeat(0), Pattern::new(""));
+    assert_eq!(Pattern::new("").repeat(5), Pattern::new(""));
+}
+
+#[test]
+fn keeps_partial_masks() {
+    let entry = Pattern::from_masked_bytes(&[0x40, 0x05], &[0xf0, 0xff]);
+
+    assert_eq!(
+        entry.repeat(2),
+        Pattern::from_masked_bytes(&[0x40, 0x05, 0x40, 0x05], &[0xf0, 0xff, 0xf0, 0xff])
+    );
+}
+
+#[test]
+fn keeps_cursor_and_labels_of_first_copy() {
+    let repeated = Pattern::new("e8 ^ @disp ?? ?? ?? ??").repeat(3);
+
+    assert_eq!(repeated.len(), 15);
+    assert_eq!(repeated.cursor(), 1);
+    assert_eq!(repeated.labels().collect::<Vec<_>>(), [("disp", 1)]);
+}
+
+#[test]
+fn re_pads_long_patterns() {
+    let repeated = Pattern::new("?? ?? 40 00").repeat(40);
+
+    assert_eq!(repeated.len(), 160);
+    assert_eq!(repeated.padded_len() % Pattern::ALIGNMENT, 0);
+    assert!(repeated.padded_len() >= 160);
+
+    let mut haystack = vec![0xcc; 3];
+    for entry in 0..41u16 {
+        haystack.extend(entry.to_le_bytes());
+        haystack.extend([0x40, 0x00]);
+    }
+    assert_eq!(
+        Scanner::from(repeated).find_all_offsets(None, &haystack),
+        [3, 7]
+    );
+}
+
+#[test]
+#[should_panic(expected = "repeated pattern length overflows")]
+fn overflowing_length() {
+    Pattern::new("90 90").repeat(usize::MAX / 2 + 1);
+}
@@ -0,0 +1,29 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn double_reverse() {
+    for pattern in ["48 89 5c 24 ?? 57", "?? ?? c3", "cc", "", "48 ?{40} 8b 05"] {
+        let pattern = Pattern::new(pattern);
+        assert_eq!(pattern.reversed().reversed(), pattern);
+    }
+}
+
+#[test]
+fn reversed_order() {
+    assert_eq!(
+        Pattern::new("48 89 ?? 24 ?{2}").reversed(),
+        Pattern::new("?? ?? 24 ?? 89 48")
+    );
+    assert_ne!(Pattern::new("48 89").reversed(), Pattern::new("48 89"));
+}
+
+#[test]
+fn scan_reversed_data() {
+    let mut data = vec![0xcc; 0x80];
+    data[0x40..0x46].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0x08, 0x57]);
+    data.reverse();
+
+    let scanner = Scanner::from(Pattern::new("48 89 5c 24 ?? 57").reversed());
+    assert_eq!(scanner.find_all_offsets(None, &data), [0x80 - 0x46]);
+}
@@ -0,0 +1,77 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn match_wrapping_around() {
+    let scanner = Scanner::new("48 89 5c 24");
+    let logical = [0xee, 0x48, 0x89, 0x5c, 0x24, 0x00, 0x00, 0x00];
+
+    // every rotation of the same logical contents reports the same offset
+    for head in 0..logical.len() {
+        let mut ring = logical;
+        ring.rotate_right(head);
+
+        assert_eq!(scanner.find_ring(&ring, head), Some(1), "head {}", head);
+    }
+}
+
+#[test]
+fn logical_order_decides_the_first_match() {
+    let scanner = Scanner::new("cc ??");
+    let ring = [0xcc, 0x01, 0x00, 0xcc, 0x02, 0x00];
+
+    assert_eq!(scanner.find_ring(&ring, 0), Some(0));
+    assert_eq!(scanner.find_ring(&ring, 1), Some(2));
+    assert_eq!(scanner.find_ring(&ring, 4), Some(2));
+    // the match at 3 ends where the ring starts
+    assert_eq!(scanner.find_ring(&ring, 6), Some(0));
+}
+
+#[test]
+fn no_match_across_the_head() {
+    let scanner = Scanner::new("48 89");
+    // the newest byte at the end is followed by the oldest one at the start, which doesn't
+    // make a match
+    let ring = [0x89, 0x00, 0x48];
+    assert_eq!(scanner.find_ring(&ring, 0), None);
+    assert_eq!(scanner.find_ring(&ring, 3), None);
+
+    // the same around a head in the middle
+    let ring = [0x00, 0x48, 0x89, 0x00];
+    assert_eq!(scanner.find_ring(&ring, 2), None);
+    assert_eq!(scanner.find_ring(&ring, 1), Some(0));
+}
+
+#[test]
+fn matches_rotated_search() {
+    let mut rand = Wyrand::seed(0x5eed);
+
+    for _ in 0..1000 {
+        let pattern = (0..1 + rand.next_lim_usize(6))
+            .map(|_| format!("{:02x}", rand.next_lim_usize(3)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let scanner = Scanner::new(&pattern);
+
+        let ring = (0..rand.next_lim_usize(48))
+            .map(|_| rand.next_lim_usize(3) as u8)
+            .collect::<Vec<_>>();
+        let head = rand.next_lim_usize(ring.len() + 1);
+
+        let logical = [&ring[head..], &ring[..head]].concat();
+        assert_eq!(
+            scanner.find_ring(&ring, head),
+            scanner.find_all_offsets(None, &logical).first().copied(),
+            "{} in {:02x?} at {}",
+            pattern,
+            ring,
+            head
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "outside of a ring buffer")]
+fn head_out_of_bounds() {
+    Scanner::new("cc").find_ring(&[0xcc], 2);
+}
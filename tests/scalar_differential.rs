@@ -0,0 +1,109 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+const ITERATIONS: usize = 2000;
+
+/// Reference implementation: compare the pattern at every position
+fn naive_find(haystack: &[u8], pattern: &[(u8, u8)]) -> Option<usize> {
+    if haystack.len() < pattern.len() {
+        return None;
+    }
+
+    (0..=haystack.len() - pattern.len()).find(|&offset| {
+        pattern
+            .iter()
+            .enumerate()
+            .all(|(i, &(data, mask))| (haystack[offset + i] ^ data) & mask == 0)
+    })
+}
+
+/// Build the pattern from its bytes, patterns of wildcards only can't be parsed
+fn to_pattern(pattern: &[(u8, u8)]) -> Pattern {
+    let data = pattern.iter().map(|&(data, _)| data).collect::<Vec<_>>();
+    let mask = pattern.iter().map(|&(_, mask)| mask).collect::<Vec<_>>();
+
+    Pattern::from_masked_bytes(&data, &mask)
+}
+
+#[test]
+fn random_patterns() {
+    let mut rand = Wyrand::seed(0x5eed);
+
+    for _ in 0..ITERATIONS {
+        // few distinct nibbles make partial and repeated matches likely
+        let nibbles = 2 + rand.next_lim_usize(3);
+        let next_byte = |rand: &mut Wyrand| {
+            (rand.next_lim_usize(nibbles) << 4 | rand.next_lim_usize(nibbles)) as u8
+        };
+
+        let pattern_len = 1 + rand.next_lim_usize(48);
+        let pattern = (0..pattern_len)
+            .map(|_| {
+                let mask = match rand.next_lim_usize(8) {
+                    0 | 1 => 0x00,
+                    2 => 0xf0,
+                    3 => 0x0f,
+                    _ => 0xff,
+                };
+                (next_byte(&mut rand) & mask, mask)
+            })
+            .collect::<Vec<_>>();
+
+        // most haystacks are large enough for the skip table, the rest take the naive loop
+        let haystack_len = if rand.next_bool(Probability::new(0.25)) {
+            rand.next_lim_usize(4096)
+        } else {
+            4096 + rand.next_lim_usize(4096)
+        };
+        let mut haystack = (0..haystack_len)
+            .map(|_| next_byte(&mut rand))
+            .collect::<Vec<_>>();
+
+        if haystack_len >= pattern_len && rand.next_bool(Probability::new(0.5)) {
+            let at = rand.next_lim_usize(haystack_len - pattern_len + 1);
+            for (i, &(data, mask)) in pattern.iter().enumerate() {
+                haystack[at + i] = haystack[at + i] & !mask | data;
+            }
+        }
+
+        let scanner = Scanner::from(to_pattern(&pattern));
+        // SAFETY: haystack is a valid slice
+        let result =
+            unsafe { scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len()) };
+
+        let offset = result
+            .is_valid()
+            .then(|| result.get_addr() as usize - haystack.as_ptr() as usize);
+
+        assert_eq!(
+            offset,
+            naive_find(&haystack, &pattern),
+            "pattern {:02x?} in {:02x?}",
+            pattern,
+            haystack
+        );
+    }
+}
+
+#[test]
+fn trailing_wildcards() {
+    let haystack = [0x01, 0x02, 0x01, 0x02, 0x03, 0x04, 0x05];
+
+    let scanner = Scanner::new("01 02 03 ?? ??");
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len()) };
+
+    assert_eq!(result.get_addr() as usize - haystack.as_ptr() as usize, 2);
+}
+
+#[test]
+fn only_wildcards() {
+    let haystack = [0x01, 0x02, 0x03];
+
+    let scanner = Scanner::from(to_pattern(&[(0x00, 0x00), (0x00, 0x00)]));
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len()) };
+
+    assert_eq!(result.get_addr(), haystack.as_ptr());
+}
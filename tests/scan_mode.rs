@@ -0,0 +1,69 @@
+use lightningscanner::{ScanMode, Scanner};
+
+const MODES: [Option<ScanMode>; 5] = [
+    None,
+    Some(ScanMode::Auto),
+    Some(ScanMode::Scalar),
+    Some(ScanMode::Sse42),
+    Some(ScanMode::Avx2),
+];
+
+fn find(scanner: &Scanner, mode: Option<ScanMode>, haystack: &[u8]) -> Option<usize> {
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(mode, haystack.as_ptr(), haystack.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - haystack.as_ptr() as usize)
+}
+
+fn haystack() -> Vec<u8> {
+    let mut haystack = vec![0xcc; 0x300];
+    haystack[0x11..0x16].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0x08]);
+    haystack[0x1f0..0x1f5].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0x10]);
+    haystack[0x2fd..].copy_from_slice(&[0x48, 0x89, 0x5c]);
+    haystack
+}
+
+#[test]
+fn mode_matrix() {
+    let haystack = haystack();
+
+    let cases: [(&str, &[u8], Option<usize>); 6] = [
+        ("48 89 5c 24 ??", &haystack, Some(0x11)),
+        ("48 89 5c 24 10", &haystack, Some(0x1f0)),
+        ("48 89 5c 24 ??", &haystack[0x12..], Some(0x1f0 - 0x12)),
+        ("48 89 5c", &haystack[0x1f1..], Some(0x2fd - 0x1f1)),
+        ("48 89 5c 24", &haystack[0x1f1..], None),
+        ("48 ?? 5c", &haystack[..0x14], Some(0x11)),
+    ];
+
+    for (pattern, haystack, expected) in cases {
+        let scanner = Scanner::new(pattern);
+
+        for mode in MODES {
+            assert_eq!(
+                find(&scanner, mode, haystack),
+                expected,
+                "pattern `{}` with {:?}",
+                pattern,
+                mode
+            );
+        }
+    }
+}
+
+#[test]
+fn all_offsets_match() {
+    let haystack = haystack();
+    let scanner = Scanner::new("48 89 5c");
+
+    for mode in MODES {
+        assert_eq!(
+            scanner.find_all_offsets(mode, &haystack),
+            [0x11, 0x1f0, 0x2fd],
+            "{:?}",
+            mode
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::selectivity::Anchor;
+
+#[test]
+fn report() {
+    let report = Pattern::new("48 8b 05 ?? ?? ?? ?? c3 5a").selectivity();
+
+    assert_eq!(report.concrete_bytes, 5);
+    assert_eq!(report.longest_concrete_run, 3);
+    assert_eq!(report.constrained_bits, 40);
+    assert_eq!(report.false_positive_probability, 1.0 / 256f64.powi(5));
+    assert_eq!(
+        report.anchor,
+        Some(Anchor {
+            offset: 2,
+            byte: 0x05,
+            rank: 32
+        })
+    );
+}
+
+#[test]
+fn common_anchor() {
+    // 0x8b is less common than 0x48, so it is chosen
+    let report = Pattern::new("48 8b 48").selectivity();
+
+    assert_eq!(
+        report.anchor,
+        Some(Anchor {
+            offset: 1,
+            byte: 0x8b,
+            rank: 3
+        })
+    );
+}
+
+#[test]
+fn only_wildcards() {
+    let report = Pattern::from_masked_bytes(&[0; 2], &[0; 2]).selectivity();
+
+    assert_eq!(report.concrete_bytes, 0);
+    assert_eq!(report.longest_concrete_run, 0);
+    assert_eq!(report.false_positive_probability, 1.0);
+    assert_eq!(report.anchor, None);
+}
+
+#[test]
+fn weaker() {
+    let short = Pattern::new("48 8b ?? c3");
+    let long = Pattern::new("48 8b ?? c3 90");
+    let split = Pattern::new("48 ?? 8b ?? c3");
+
+    assert!(short.is_weaker_than(&long));
+    assert!(!long.is_weaker_than(&short));
+    assert!(split.is_weaker_than(&short));
+    assert!(!short.is_weaker_than(&short));
+}
+
+#[test]
+fn mask_density() {
+    assert_eq!(Pattern::new("48 8b 05 c3").mask_density(), 1.0);
+    assert_eq!(
+        Pattern::new("48 ?? ?? ?? ?? ?? ?? ??").mask_density(),
+        0.125
+    );
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0; 2], &[0; 2]).mask_density(),
+        0.0
+    );
+    assert_eq!(Pattern::new("").mask_density(), 0.0);
+}
+
+#[test]
+fn strict_constructor() {
+    use lightningscanner::pattern::PatternError;
+
+    // exactly at the threshold
+    assert!(Pattern::try_new_strict("48 ?? ?? ??").is_ok());
+    assert_eq!(
+        Pattern::try_new_strict("48 ?? ?? ?? ??").err(),
+        Some(PatternError::TooManyWildcards {
+            concrete: 1,
+            len: 5
+        })
+    );
+
+    // parse errors are still reported first
+    assert_eq!(
+        Pattern::try_new_strict("?{x}").err(),
+        Some(PatternError::MalformedQuantifier { position: 0 })
+    );
+
+    // the permissive constructors keep accepting sparse patterns
+    assert!(Pattern::try_new("48 ?? ?? ?? ??").is_ok());
+}
+
+#[test]
+fn byte_histogram() {
+    use lightningscanner::selectivity::byte_histogram;
+    use tinyrand::{Rand, Seeded, Wyrand};
+
+    let mut rand = Wyrand::seed(0x4157);
+
+    for len in [0, 1, 3, 4, 5, 1000, 4099] {
+        let data = (0..len)
+            .map(|_| rand.next_lim_u16(256) as u8)
+            .collect::<Vec<_>>();
+
+        let mut expected = [0u64; 256];
+        for &byte in &data {
+            expected[byte as usize] += 1;
+        }
+
+        assert_eq!(byte_histogram(&data), expected);
+    }
+
+    assert_eq!(byte_histogram(&[0xcc; 1001])[0xcc], 1001);
+}
+
+#[test]
+fn rarest_byte_offset() {
+    let mut histogram = [100u64; 256];
+    histogram[0x48] = 1000;
+    histogram[0x8b] = 10;
+    histogram[0x05] = 10;
+
+    assert_eq!(
+        Pattern::new("48 8b 05 ?? ?? ?? ??").rarest_byte_offset(&histogram),
+        Some(1)
+    );
+    assert_eq!(
+        Pattern::new("48 ?? c3").rarest_byte_offset(&histogram),
+        Some(2)
+    );
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0; 2], &[0; 2]).rarest_byte_offset(&histogram),
+        None
+    );
+    assert_eq!(Pattern::new("").rarest_byte_offset(&histogram), None);
+}
@@ -0,0 +1,50 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{self_check, BackendMismatch, ScanMode};
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+#[test]
+fn random_patterns_agree() {
+    let mut rand = Wyrand::seed(0x5e1f);
+
+    for _ in 0..1000 {
+        let alphabet = 2 + rand.next_lim_usize(4);
+
+        let pattern = (0..1 + rand.next_lim_usize(80))
+            .map(|_| {
+                if rand.next_bool(Probability::new(0.2)) {
+                    "??".to_string()
+                } else {
+                    format!("{:02x}", rand.next_lim_usize(alphabet))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let haystack = (0..rand.next_lim_usize(600))
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        // patterns of wildcards only are rejected
+        let Ok(parsed) = Pattern::try_new(&pattern) else {
+            continue;
+        };
+
+        if let Err(mismatch) = self_check(&parsed, &haystack) {
+            panic!("{} for `{}` in {:02x?}", mismatch, pattern, haystack);
+        }
+    }
+}
+
+#[test]
+fn mismatch_display() {
+    let mismatch = BackendMismatch {
+        mode: ScanMode::Avx2,
+        expected: Some(3),
+        found: None,
+    };
+
+    assert_eq!(
+        mismatch.to_string(),
+        "Avx2 scan mode reported a match at None where the scalar one reported Some(3)"
+    );
+}
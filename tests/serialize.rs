@@ -0,0 +1,92 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::serialize::DecodeError;
+use lightningscanner::{ScanMode, Scanner};
+
+const PATTERN: &str = "42 cd e7 f8 21 5b d6 b8 d1 be 12 0e 85 34 c4 ?? 03 7e bc 7b b9 29 b6 07 31 7e ?? dd 3e 0a e7 71 f3 b7 76 3f";
+
+const DATA_SET: [u8; 64] = [
+    0xdb, 0x2f, 0x16, 0x37, 0xd5, 0xff, 0x12, 0x74, 0x7c, 0xf2, 0x27, 0xed, 0x42, 0xcd, 0xe7, 0xf8,
+    0x21, 0x5b, 0xd6, 0xb8, 0xd1, 0xbe, 0x12, 0x0e, 0x85, 0x34, 0xc4, 0xf9, 0x03, 0x7e, 0xbc, 0x7b,
+    0xb9, 0x29, 0xb6, 0x07, 0x31, 0x7e, 0x69, 0xdd, 0x3e, 0x0a, 0xe7, 0x71, 0xf3, 0xb7, 0x76, 0x3f,
+    0x53, 0xf4, 0xeb, 0xcc, 0xf2, 0x04, 0x6d, 0x35, 0x5c, 0x88, 0xc3, 0x83, 0xdf, 0x9b, 0xc9, 0x44,
+];
+
+fn find(pattern: Pattern) -> usize {
+    let scanner = Scanner::from(pattern);
+    // SAFETY: DATA_SET is a valid slice
+    let result = unsafe { scanner.find(Some(ScanMode::Avx2), DATA_SET.as_ptr(), DATA_SET.len()) };
+
+    result.get_addr() as usize - DATA_SET.as_ptr() as usize
+}
+
+#[test]
+fn round_trip() {
+    let bytes = Pattern::new(PATTERN).to_bytes();
+    let pattern = Pattern::from_bytes(&bytes).unwrap();
+
+    assert_eq!(pattern.to_bytes(), bytes);
+    assert_eq!(find(pattern), 0x0c);
+}
+
+#[test]
+fn batch_round_trip() {
+    let patterns = [
+        Pattern::new(PATTERN),
+        Pattern::new("f3 b7 ?? 3f"),
+        Pattern::new(""),
+    ];
+
+    let bytes = Pattern::batch_to_bytes(&patterns);
+    let decoded = Pattern::batch_from_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded.len(), 3);
+    for (pattern, decoded) in patterns.iter().zip(&decoded) {
+        assert_eq!(pattern.to_bytes(), decoded.to_bytes());
+    }
+    assert_eq!(find(decoded.into_iter().nth(1).unwrap()), 0x2c);
+}
+
+#[test]
+fn corrupted() {
+    let bytes = Pattern::new("f3 b7 ?? 3f").to_bytes();
+
+    let mut magic = bytes.clone();
+    magic[0] = b'X';
+    assert_eq!(
+        Pattern::from_bytes(&magic).err(),
+        Some(DecodeError::InvalidMagic)
+    );
+
+    let mut version = bytes.clone();
+    version[4] = 2;
+    assert_eq!(
+        Pattern::from_bytes(&version).err(),
+        Some(DecodeError::UnsupportedVersion(2))
+    );
+
+    let mut length = bytes.clone();
+    length[5] = 0xff;
+    assert_eq!(
+        Pattern::from_bytes(&length).err(),
+        Some(DecodeError::UnexpectedEof)
+    );
+
+    assert_eq!(
+        Pattern::from_bytes(&bytes[..bytes.len() - 1]).err(),
+        Some(DecodeError::UnexpectedEof)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        Pattern::from_bytes(&trailing).err(),
+        Some(DecodeError::TrailingBytes)
+    );
+
+    let mut batch = Pattern::batch_to_bytes(&[Pattern::new("f3 b7 ?? 3f")]);
+    batch[5] = 0xff;
+    assert_eq!(
+        Pattern::batch_from_bytes(&batch).err(),
+        Some(DecodeError::UnexpectedEof)
+    );
+}
@@ -0,0 +1,96 @@
+use lightningscanner::sigfile::{SigFileError, SignatureFile, SyntaxErrorKind};
+
+const FILE: &str = r#"
+# player structure
+local_player = "a0 9e 87 00 ?? 5c", offset = +4, module = "game.dll"
+
+// tick function
+tick = "5c 3a ae"    # trailing comment
+before = "9e 87", offset = -1
+"#;
+
+const DATA_SET: [u8; 16] = [
+    0xaf, 0x47, 0x43, 0x9f, 0xa0, 0x9e, 0x87, 0x00, 0x76, 0x5c, 0x3a, 0xae, 0x40, 0x30, 0x7f, 0xc0,
+];
+
+fn syntax_error(contents: &str) -> (usize, SyntaxErrorKind) {
+    match SignatureFile::parse(contents) {
+        Err(SigFileError::Syntax { line, kind }) => (line, kind),
+        _ => panic!("expected a syntax error"),
+    }
+}
+
+#[test]
+fn parse() {
+    let file = SignatureFile::parse(FILE).unwrap();
+    assert_eq!(file.len(), 3);
+
+    let local_player = file.get("local_player").unwrap();
+    assert_eq!(local_player.pattern_text(), "a0 9e 87 00 ?? 5c");
+    assert_eq!(local_player.offset, 4);
+    assert_eq!(local_player.module.as_deref(), Some("game.dll"));
+    assert_eq!(local_player.find_in(&DATA_SET), Some(0x08));
+
+    let tick = file.get("tick").unwrap();
+    assert_eq!(tick.offset, 0);
+    assert_eq!(tick.module, None);
+    assert_eq!(tick.find_in(&DATA_SET), Some(0x09));
+
+    assert_eq!(file.get("before").unwrap().find_in(&DATA_SET), Some(0x04));
+}
+
+#[test]
+fn round_trip() {
+    let file = SignatureFile::parse(FILE).unwrap();
+    let written = file.to_string();
+    let reparsed = SignatureFile::parse(&written).unwrap();
+
+    assert_eq!(reparsed.to_string(), written);
+    for (signature, reparsed) in file.iter().zip(reparsed.iter()) {
+        assert_eq!(signature.name, reparsed.name);
+        assert_eq!(signature.pattern_text(), reparsed.pattern_text());
+        assert_eq!(signature.offset, reparsed.offset);
+        assert_eq!(signature.module, reparsed.module);
+    }
+
+    let path = std::env::temp_dir().join(format!("lightningscanner-{}.sig", std::process::id()));
+    file.save(&path).unwrap();
+    assert_eq!(SignatureFile::load(&path).unwrap().to_string(), written);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        syntax_error("\n\nname"),
+        (3, SyntaxErrorKind::MissingPattern)
+    );
+    assert_eq!(
+        syntax_error("name = 48 8b"),
+        (1, SyntaxErrorKind::MissingPattern)
+    );
+    assert_eq!(
+        syntax_error("my name = \"48\""),
+        (1, SyntaxErrorKind::InvalidName)
+    );
+    assert_eq!(
+        syntax_error("a = \"48\"\na = \"8b\""),
+        (2, SyntaxErrorKind::DuplicateName)
+    );
+    assert_eq!(
+        syntax_error("a = \"48\", offset = x"),
+        (1, SyntaxErrorKind::InvalidAttribute)
+    );
+    assert_eq!(
+        syntax_error("a = \"48\", size = 3"),
+        (1, SyntaxErrorKind::UnknownAttribute)
+    );
+    assert!(matches!(
+        syntax_error("a = \"48 ?{x}\"").1,
+        SyntaxErrorKind::InvalidPattern(_)
+    ));
+
+    let missing = SignatureFile::load("/nonexistent/file.sig");
+    assert!(matches!(missing, Err(SigFileError::Io(_))));
+    assert_eq!(missing.err().unwrap().line(), None);
+}
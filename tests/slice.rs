@@ -0,0 +1,63 @@
+use lightningscanner::pattern::{Pattern, SliceError};
+use lightningscanner::Scanner;
+
+#[test]
+fn sliced_pattern_matches_at_shifted_offsets() {
+    let mut binary = vec![0xcc; 200];
+    binary[100..108].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0x08, 0x57, 0x48, 0x83]);
+
+    // only the tail of the pattern is present
+    let pattern = Pattern::new("55 8b ec 48 89 5c 24 ?? 57 48 83");
+    assert_eq!(
+        Scanner::from(pattern.clone()).find_all_offsets(None, &binary),
+        []
+    );
+
+    for start in 3..pattern.len() - 1 {
+        let suffix = pattern.slice(start..pattern.len()).unwrap();
+
+        assert_eq!(
+            Scanner::from(suffix).find_all_offsets(None, &binary),
+            [100 + start - 3],
+            "suffix from {}",
+            start
+        );
+    }
+
+    let middle = pattern.slice(4..8).unwrap();
+    assert_eq!(middle, Pattern::new("89 5c 24 ??"));
+    assert_eq!(Scanner::from(middle).find_all_offsets(None, &binary), [101]);
+}
+
+#[test]
+fn out_of_range() {
+    let pattern = Pattern::new("48 8b ?? c3");
+
+    assert_eq!(
+        pattern.slice(2..5),
+        Err(SliceError {
+            start: 2,
+            end: 5,
+            len: 4
+        })
+    );
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = pattern.slice(3..1);
+    assert!(reversed.is_err());
+
+    assert_eq!(pattern.slice(0..4), Ok(pattern.clone()));
+    assert_eq!(pattern.slice(4..4), Ok(Pattern::new("")));
+}
+
+#[test]
+fn cursor_and_labels() {
+    let pattern = Pattern::new("e8 @rel ?? ?? ?? ?? ^ @next 48 8b");
+
+    let tail = pattern.slice(3..7).unwrap();
+    assert_eq!(tail.cursor(), 2);
+    assert_eq!(tail.labels().collect::<Vec<_>>(), [("next", 2)]);
+
+    let head = pattern.slice(0..3).unwrap();
+    assert_eq!(head.cursor(), 0);
+    assert_eq!(head.labels().collect::<Vec<_>>(), [("rel", 1)]);
+}
@@ -0,0 +1,54 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+fn find(scanner: &Scanner, mode: Option<ScanMode>, haystack: &[u8]) -> Option<usize> {
+    // SAFETY: haystack is a valid slice
+    let result = unsafe { scanner.find(mode, haystack.as_ptr(), haystack.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - haystack.as_ptr() as usize)
+}
+
+#[test]
+fn identical_across_sizes() {
+    let mut rand = Wyrand::seed(0x5a11);
+
+    for size in 0..=512 {
+        for _ in 0..4 {
+            let pattern_len = 1 + rand.next_lim_usize(24);
+            let pattern = (0..pattern_len)
+                .map(|_| {
+                    if rand.next_bool(Probability::new(0.25)) {
+                        "??".to_string()
+                    } else {
+                        format!("{:02x}", rand.next_lim_usize(3))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let haystack = (0..size)
+                .map(|_| rand.next_lim_usize(3) as u8)
+                .collect::<Vec<_>>();
+
+            // patterns of wildcards only are rejected
+            let Ok(parsed) = Pattern::try_new(&pattern) else {
+                continue;
+            };
+            let scanner = Scanner::from(parsed);
+            let expected = find(&scanner, Some(ScanMode::Scalar), &haystack);
+
+            for mode in [None, Some(ScanMode::Sse42), Some(ScanMode::Avx2)] {
+                assert_eq!(
+                    find(&scanner, mode, &haystack),
+                    expected,
+                    "pattern `{}` in {:02x?}",
+                    pattern,
+                    haystack
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Seeded, Wyrand};
+
+/// Short concrete patterns are searched for with `PCMPESTRI` by the SSE4.2 backend
+#[test]
+fn short_concrete_patterns_match_scalar() {
+    if !ScanMode::Sse42.is_supported() {
+        return;
+    }
+
+    let mut rand = Wyrand::seed(0x5eed);
+
+    for _ in 0..2000 {
+        // a small alphabet makes partial matches across unit boundaries likely
+        let alphabet = 2 + rand.next_lim_usize(4);
+
+        let pattern_len = 1 + rand.next_lim_usize(17);
+        let pattern = (0..pattern_len)
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        let haystack = (0..rand.next_lim_usize(256))
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        let text = pattern
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let scanner = Scanner::new(&text);
+
+        assert_eq!(
+            scanner.find_all_offsets(Some(ScanMode::Sse42), &haystack),
+            scanner.find_all_offsets(Some(ScanMode::Scalar), &haystack),
+            "pattern {} in {:02x?}",
+            text,
+            haystack
+        );
+    }
+}
+
+#[test]
+fn match_straddling_units() {
+    if !ScanMode::Sse42.is_supported() {
+        return;
+    }
+
+    let scanner = Scanner::new("48 89 5c 24 08");
+
+    for at in 0..40 {
+        let mut haystack = vec![0x48; 48];
+        haystack[at..at + 5].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24, 0x08]);
+
+        assert_eq!(
+            scanner.find_all_offsets(Some(ScanMode::Sse42), &haystack),
+            [at]
+        );
+    }
+}
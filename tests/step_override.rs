@@ -0,0 +1,56 @@
+#![cfg(feature = "test-hooks")]
+
+use lightningscanner::{set_step_override, ScanMode, Scanner};
+
+fn find_offset(scanner: &Scanner, mode: ScanMode, data: &[u8]) -> Option<usize> {
+    // SAFETY: data is a valid slice
+    let result = unsafe { scanner.find(Some(mode), data.as_ptr(), data.len()) };
+
+    result
+        .is_valid()
+        .then(|| result.get_addr() as usize - data.as_ptr() as usize)
+}
+
+#[test]
+fn skips_offsets_between_steps() {
+    // the wildcard keeps SSE4.2 from taking its string search for short concrete patterns
+    let scanner = Scanner::new("48 89 ?? 24");
+    let modes = [ScanMode::Sse42, ScanMode::Avx2]
+        .into_iter()
+        .filter(|mode| mode.is_supported())
+        .collect::<Vec<_>>();
+
+    let mut aligned = vec![0x00; 1024];
+    aligned[64..68].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    let mut unaligned = vec![0x00; 1024];
+    unaligned[65..69].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+    let mut tail = vec![0x00; 1024];
+    tail[1020..].copy_from_slice(&[0x48, 0x89, 0x5c, 0x24]);
+
+    set_step_override(Some(4));
+    for &mode in &modes {
+        assert_eq!(
+            find_offset(&scanner, mode, &aligned),
+            Some(64),
+            "{:?}",
+            mode
+        );
+        assert_eq!(find_offset(&scanner, mode, &unaligned), None, "{:?}", mode);
+    }
+
+    // the scalar tail after the vector loop is still scanned
+    set_step_override(Some(1 << 20));
+    for &mode in &modes {
+        assert_eq!(find_offset(&scanner, mode, &tail), Some(1020), "{:?}", mode);
+    }
+
+    set_step_override(None);
+    for &mode in &modes {
+        assert_eq!(
+            find_offset(&scanner, mode, &unaligned),
+            Some(65),
+            "{:?}",
+            mode
+        );
+    }
+}
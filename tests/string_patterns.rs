@@ -0,0 +1,46 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn question_marks_are_concrete() {
+    let pattern = Pattern::from_ascii_str("a?b");
+
+    assert_eq!(pattern.len(), 3);
+    assert_eq!(pattern.canonical_string(), "61 3f 62");
+
+    let scanner = Scanner::from(pattern);
+    assert_eq!(scanner.find_all_offsets(None, b"axb a?b a??b"), [4]);
+}
+
+#[test]
+fn ida_question_marks_stay_wildcards() {
+    let scanner = Scanner::new("61 ? 62");
+    assert_eq!(scanner.find_all_offsets(None, b"axb a?b a??b"), [0, 4]);
+}
+
+#[test]
+fn wide_strings() {
+    let pattern = Pattern::from_utf16_str("a?b");
+
+    assert_eq!(pattern.canonical_string(), "61 00 3f 00 62 00");
+
+    let haystack = "xa?b"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Scanner::from(pattern).find_all_offsets(None, &haystack),
+        [2]
+    );
+
+    // characters outside of the basic multilingual plane take a surrogate pair
+    assert_eq!(Pattern::from_utf16_str("\u{1f600}").len(), 4);
+}
+
+#[test]
+fn new_string_matches_from_ascii_str() {
+    assert_eq!(
+        Pattern::new_string("S^me??T&stD/ta;?"),
+        Pattern::from_ascii_str("S^me??T&stD/ta;?")
+    );
+}
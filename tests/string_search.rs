@@ -19,7 +19,7 @@ const EXPECTED_FIND: usize = 0x80;
 #[test]
 #[cfg(target_feature = "avx2")]
 fn avx2() {
-    let scanner = Scanner::new(PATTERN);
+    let scanner = Scanner::new_from_str(PATTERN);
     // SAFETY: DATA_SET is a valid slice
     let result = unsafe { scanner.find(Some(ScanMode::Avx2), DATA_SET.as_ptr(), DATA_SET.len()) };
 
@@ -32,7 +32,7 @@ fn avx2() {
 #[test]
 #[cfg(target_feature = "sse4.2")]
 fn sse42() {
-    let scanner = Scanner::new(PATTERN);
+    let scanner = Scanner::new_from_str(PATTERN);
     // SAFETY: DATA_SET is a valid slice
     let result = unsafe { scanner.find(Some(ScanMode::Sse42), DATA_SET.as_ptr(), DATA_SET.len()) };
 
@@ -0,0 +1,107 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::pattern_set::{PatternSet, Redundancy};
+
+#[test]
+fn subsumes() {
+    let general = Pattern::new("48 8b ?? ?? c3");
+
+    assert!(general.subsumes(&Pattern::new("48 8b 05 ?? c3")));
+    assert!(general.subsumes(&Pattern::new("48 8b ?? ?? c3")));
+    assert!(general.subsumes(&Pattern::new("48 8b ?? ?? c3 90")));
+
+    // different concrete byte
+    assert!(!general.subsumes(&Pattern::new("48 89 ?? ?? c3")));
+    // wildcard where the general pattern requires a byte
+    assert!(!general.subsumes(&Pattern::new("48 ?? 05 ?? c3")));
+    // too short to require every byte of the general pattern
+    assert!(!general.subsumes(&Pattern::new("48 8b ?? ??")));
+}
+
+#[test]
+fn prefix() {
+    let prefix = Pattern::new("40 53");
+    let full = Pattern::new("40 53 48 83 ec 20");
+
+    assert!(prefix.subsumes(&full));
+    assert!(!full.subsumes(&prefix));
+    assert!(Pattern::new("").subsumes(&prefix));
+}
+
+#[test]
+fn is_subpattern_of() {
+    let general = Pattern::new("48 8b ?? ?? c3");
+
+    // more specific: a concrete byte in place of a wildcard, or extra trailing bytes
+    assert!(Pattern::new("48 8b 05 ?? c3").is_subpattern_of(&general));
+    assert!(Pattern::new("48 8b ?? ?? c3 90").is_subpattern_of(&general));
+
+    // identical patterns are subpatterns of each other
+    assert!(general.is_subpattern_of(&general.clone()));
+
+    // less specific
+    assert!(!general.is_subpattern_of(&Pattern::new("48 8b 05 ?? c3")));
+    assert!(!Pattern::new("48 8b ?? ??").is_subpattern_of(&general));
+    assert!(!Pattern::new("48 ?? 05 ?? c3").is_subpattern_of(&general));
+    // conflicting byte
+    assert!(!Pattern::new("48 89 ?? ?? c3").is_subpattern_of(&general));
+
+    // everything is a subpattern of the empty pattern
+    assert!(general.is_subpattern_of(&Pattern::new("")));
+}
+
+#[test]
+fn find_redundant() {
+    let mut set = PatternSet::new();
+    set.insert("prologue", Pattern::new("40 53 48 83 ec 20"));
+    set.insert("push", Pattern::new("40 53"));
+    set.insert("unrelated", Pattern::new("e8 ?? ?? ?? ??"));
+    set.insert("copy", Pattern::new("40 53 48 83 ec 20"));
+    set.insert("wild", Pattern::new("40 ?? 48"));
+
+    assert_eq!(
+        set.find_redundant(),
+        [
+            Redundancy {
+                general: "push",
+                specific: "prologue"
+            },
+            Redundancy {
+                general: "prologue",
+                specific: "copy"
+            },
+            Redundancy {
+                general: "wild",
+                specific: "prologue"
+            },
+            Redundancy {
+                general: "push",
+                specific: "copy"
+            },
+            Redundancy {
+                general: "wild",
+                specific: "copy"
+            },
+        ]
+    );
+}
+
+#[test]
+fn common_prefix_len() {
+    let pattern = Pattern::new("48 8b ?? ?? c3");
+
+    assert_eq!(
+        pattern.common_prefix_len(&Pattern::new("48 8b ?? ?? c3")),
+        5
+    );
+    assert_eq!(
+        pattern.common_prefix_len(&Pattern::new("48 8b ?? ?? c3 90")),
+        5
+    );
+    assert_eq!(
+        pattern.common_prefix_len(&Pattern::new("48 8b ?? 05 c3")),
+        3
+    );
+    assert_eq!(pattern.common_prefix_len(&Pattern::new("48 89")), 1);
+    assert_eq!(pattern.common_prefix_len(&Pattern::new("90")), 0);
+    assert_eq!(pattern.common_prefix_len(&Pattern::new("")), 0);
+}
@@ -0,0 +1,37 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::pattern_set::PatternSet;
+use lightningscanner::{ScanError, Scanner};
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn types_are_send_sync() {
+    assert_send_sync::<Scanner>();
+    assert_send_sync::<Pattern>();
+    assert_send_sync::<PatternSet>();
+    assert_send_sync::<ScanError>();
+}
+
+#[test]
+fn shared_scanner() {
+    let binary = (0..0x4000u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .chain([0x48, 0x89, 0x5c, 0x24, 0x10])
+        .collect::<Vec<_>>();
+
+    let scanner = Scanner::new("48 89 5c 24 ??");
+    let expected = scanner.find_all_offsets(None, &binary);
+
+    thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| scope.spawn(|| scanner.find_all_offsets(None, &binary)))
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    });
+
+    assert!(expected.contains(&0x4000));
+}
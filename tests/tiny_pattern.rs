@@ -0,0 +1,59 @@
+use lightningscanner::{ScanMode, Scanner};
+use tinyrand::{Rand, Seeded, Wyrand};
+
+const TINY_PATTERNS: [&str; 7] = ["cc", "0f 05", "?? cc", "cc ??", "?? 05", "0f ??", "cc cc"];
+
+fn assert_matches_general(scanner: &Scanner, pattern: &str, haystack: &[u8]) {
+    // SAFETY: haystack is a valid slice
+    let fast = unsafe { scanner.find(None, haystack.as_ptr(), haystack.len()) };
+    // SAFETY: haystack is a valid slice
+    let general =
+        unsafe { scanner.find(Some(ScanMode::Scalar), haystack.as_ptr(), haystack.len()) };
+
+    assert_eq!(
+        fast.get_addr(),
+        general.get_addr(),
+        "pattern `{}` in {:02x?}",
+        pattern,
+        haystack
+    );
+    assert_eq!(
+        scanner.find_all_offsets(None, haystack),
+        scanner.find_all_offsets(Some(ScanMode::Scalar), haystack),
+        "pattern `{}` in {:02x?}",
+        pattern,
+        haystack
+    );
+}
+
+#[test]
+fn matches_general_path() {
+    let mut rand = Wyrand::seed(0x7171);
+    let alphabet = [0x0f, 0x05, 0xcc, 0x90];
+
+    for _ in 0..500 {
+        let haystack = (0..rand.next_lim_usize(300))
+            .map(|_| alphabet[rand.next_lim_usize(alphabet.len())])
+            .collect::<Vec<_>>();
+
+        for pattern in TINY_PATTERNS {
+            assert_matches_general(&Scanner::new(pattern), pattern, &haystack);
+        }
+    }
+}
+
+#[test]
+fn wildcard_at_edges() {
+    let scanner = Scanner::new("?? cc");
+
+    // the concrete byte at offset 0 has no room for the leading wildcard
+    assert_eq!(scanner.find_all_offsets(None, &[0xcc, 0x90, 0xcc]), [1]);
+    assert_eq!(scanner.find_all_offsets(None, &[0xcc]), []);
+
+    let scanner = Scanner::new("cc ??");
+
+    // the concrete byte at the very end has no room for the trailing wildcard
+    assert_eq!(scanner.find_all_offsets(None, &[0xcc, 0x90, 0xcc]), [0]);
+    assert_eq!(scanner.find_all_offsets(None, &[]), []);
+    assert_eq!(scanner.find_with_hint(&[0x90, 0xcc, 0xcc], 2, 0), Some(1));
+}
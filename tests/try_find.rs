@@ -0,0 +1,60 @@
+use lightningscanner::{ScanMode, Scanner, UnsupportedMode};
+
+fn modes() -> Vec<ScanMode> {
+    vec![
+        ScanMode::Auto,
+        ScanMode::Scalar,
+        ScanMode::Sse42,
+        ScanMode::Avx2,
+        #[cfg(feature = "portable-simd")]
+        ScanMode::Portable,
+    ]
+}
+
+fn cpu_supports(mode: ScanMode) -> bool {
+    match mode {
+        ScanMode::Auto | ScanMode::Scalar => true,
+        #[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+        ScanMode::Sse42 => is_x86_feature_detected!("sse4.2"),
+        #[cfg(all(target_arch = "x86_64", not(feature = "no-simd")))]
+        ScanMode::Avx2 => is_x86_feature_detected!("avx2"),
+        #[cfg(all(feature = "portable-simd", not(feature = "no-simd")))]
+        ScanMode::Portable => true,
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
+}
+
+#[test]
+fn supported_modes_scan() {
+    let binary = [0xab, 0xec, 0x48, 0x89, 0x5c, 0x24, 0xee, 0x48, 0x89, 0x6c];
+    let scanner = Scanner::new("48 89 5c 24 ?? 48 89 6c");
+
+    for mode in modes() {
+        assert_eq!(mode.is_supported(), cpu_supports(mode), "{:?}", mode);
+
+        // SAFETY: the array is valid for its length
+        let result = unsafe { scanner.try_find(mode, binary.as_ptr(), binary.len()) };
+
+        if cpu_supports(mode) {
+            assert_eq!(
+                result.map(|result| result.get_addr()),
+                Ok(binary[2..].as_ptr())
+            );
+        } else {
+            assert_eq!(result, Err(UnsupportedMode { mode }));
+        }
+    }
+}
+
+#[test]
+fn unsupported_mode_error() {
+    let err = UnsupportedMode {
+        mode: ScanMode::Avx2,
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "Avx2 scan mode is not supported by this CPU"
+    );
+}
@@ -0,0 +1,63 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+use tinyrand::{Probability, Rand, Seeded, Wyrand};
+
+#[test]
+fn matches_across_blocks() {
+    let mut haystack = vec![0x00; 200 * 1024];
+    // the anchor is the rarest byte, the match starts in the block before it
+    let at = 64 * 1024 - 2;
+    haystack[at..at + 4].copy_from_slice(&[0x48, 0x8b, 0x05, 0xc3]);
+
+    let scanner = Scanner::new("48 8b ?? c3");
+    assert_eq!(scanner.find_two_pass(&haystack), Some(at));
+    assert_eq!(scanner.find_two_pass(&haystack[..at + 3]), None);
+}
+
+#[test]
+fn anchor_before_the_start() {
+    // the anchor occurs too early for the pattern to start before it
+    let scanner = Scanner::new("48 ?? c3");
+    assert_eq!(scanner.find_two_pass(&[0xc3, 0x48, 0x00, 0xc3]), Some(1));
+    assert_eq!(scanner.find_two_pass(&[0x00, 0xc3]), None);
+}
+
+#[test]
+fn without_concrete_bytes() {
+    let pattern = Pattern::from_masked_bytes(&[0x40, 0x00], &[0xf0, 0x00]);
+    let scanner = Scanner::from(pattern);
+
+    assert_eq!(scanner.find_two_pass(&[0x00, 0x4c, 0x00]), Some(1));
+    assert_eq!(Scanner::new("").find_two_pass(&[]), Some(0));
+}
+
+#[test]
+fn matches_single_pass() {
+    let mut rand = Wyrand::seed(0x2a55);
+
+    for _ in 0..1000 {
+        let alphabet = 2 + rand.next_lim_usize(4);
+        let pattern = (0..1 + rand.next_lim_usize(12))
+            .map(|i| {
+                if i != 0 && rand.next_bool(Probability::new(0.3)) {
+                    "??".to_string()
+                } else {
+                    format!("{:02x}", rand.next_lim_usize(alphabet))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let haystack = (0..rand.next_lim_usize(512))
+            .map(|_| rand.next_lim_usize(alphabet) as u8)
+            .collect::<Vec<_>>();
+
+        let scanner = Scanner::new(&pattern);
+        assert_eq!(
+            scanner.find_two_pass(&haystack),
+            scanner.find_all_offsets(None, &haystack).first().copied(),
+            "`{}` in {:02x?}",
+            pattern,
+            haystack
+        );
+    }
+}
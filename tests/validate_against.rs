@@ -0,0 +1,43 @@
+use lightningscanner::pattern::Pattern;
+
+const DATA_SET: [u8; 64] = [
+    0xdb, 0x2f, 0x16, 0x37, 0xd5, 0xff, 0x12, 0x74, 0x7c, 0xf2, 0x27, 0xed, 0x7b, 0x2e, 0x54, 0x9a,
+    0xe2, 0xec, 0x73, 0x9e, 0xbb, 0xd1, 0x42, 0xc2, 0x0c, 0x9e, 0xa3, 0xa1, 0x10, 0xb3, 0x97, 0xf2,
+    0xaf, 0x47, 0x43, 0x9f, 0xa0, 0x9e, 0x87, 0x00, 0x76, 0x5c, 0x3a, 0xae, 0x40, 0x30, 0x7f, 0xc0,
+    0x53, 0xf4, 0xeb, 0xcc, 0xf2, 0x04, 0x6d, 0x35, 0xa0, 0x9e, 0x87, 0x00, 0x11, 0x5c, 0xc9, 0x44,
+];
+
+#[test]
+fn unique() {
+    let result = Pattern::new("a0 9e 87 00 ?? 5c 3a").validate_against(&DATA_SET);
+
+    assert_eq!(result.count, 1);
+    assert_eq!(result.first, Some(0x24));
+    assert!(result.is_unique());
+}
+
+#[test]
+fn multiple() {
+    let result = Pattern::new("a0 9e 87 00 ?? 5c").validate_against(&DATA_SET);
+
+    assert_eq!(result.count, 2);
+    assert_eq!(result.first, Some(0x24));
+    assert!(!result.is_unique());
+}
+
+#[test]
+fn missing() {
+    let result = Pattern::new("a0 9e 87 00 ?? 5d").validate_against(&DATA_SET);
+
+    assert_eq!(result.count, 0);
+    assert_eq!(result.first, None);
+}
+
+#[test]
+fn match_at_buffer_end() {
+    let result = Pattern::new("5c c9 44").validate_against(&DATA_SET);
+    assert_eq!(result.first, Some(0x3d));
+
+    let result = Pattern::new("5c c9 44 00").validate_against(&DATA_SET);
+    assert_eq!(result.count, 0);
+}
@@ -0,0 +1,60 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::Scanner;
+
+#[test]
+fn wildcards_the_given_bytes() {
+    let pattern = Pattern::new("48 8b 05 10 20 30 40 c3");
+
+    assert_eq!(pattern.wildcard_at(&[]), pattern);
+    assert_eq!(
+        pattern.wildcard_at(&[0, 7]),
+        Pattern::new("?? 8b 05 10 20 30 40 ??")
+    );
+    // repeated and already wildcarded indices are fine
+    assert_eq!(
+        Pattern::new("48 ?? 05").wildcard_at(&[1, 2, 2]),
+        Pattern::new("48 ?? ??")
+    );
+    // partially masked bytes become full wildcards
+    assert_eq!(
+        Pattern::from_masked_bytes(&[0x48, 0x05], &[0xff, 0x0f]).wildcard_at(&[1]),
+        Pattern::new("48 ??")
+    );
+}
+
+#[test]
+fn keeps_cursor_and_labels() {
+    let pattern = Pattern::new("e8 @call ?? ?? ?? ?? ^ 48 8b").wildcard_at(&[5]);
+
+    assert_eq!(pattern.cursor(), 5);
+    assert_eq!(pattern.labels().collect::<Vec<_>>(), [("call", 1)]);
+    assert_eq!(pattern.len(), 7);
+}
+
+#[test]
+fn wildcards_the_differences() {
+    let old = [0x48, 0x8b, 0x05, 0x10, 0x20, 0x30, 0x40, 0xc3];
+    let new = [0x90, 0x48, 0x8b, 0x05, 0x18, 0x24, 0x30, 0x40, 0xc3];
+
+    let pattern = Pattern::new("48 8b 05 10 20 30 40 c3");
+    assert_eq!(
+        Scanner::from(pattern.clone()).find_all_offsets(None, &new),
+        []
+    );
+
+    let indices = pattern
+        .diff_at(&new, 1)
+        .into_iter()
+        .map(|(index, _, _)| index)
+        .collect::<Vec<_>>();
+    let refined = Scanner::from(pattern.wildcard_at(&indices));
+
+    assert_eq!(refined.find_all_offsets(None, &old), [0]);
+    assert_eq!(refined.find_all_offsets(None, &new), [1]);
+}
+
+#[test]
+#[should_panic(expected = "index 3 is outside of a pattern of 3 bytes")]
+fn index_out_of_bounds() {
+    Pattern::new("48 8b 05").wildcard_at(&[3]);
+}
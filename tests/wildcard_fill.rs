@@ -0,0 +1,84 @@
+use lightningscanner::pattern::Pattern;
+use lightningscanner::{ScanMode, Scanner};
+use std::collections::HashSet;
+
+#[test]
+fn fills_wildcards_only() {
+    let pattern = Pattern::new_with_wildcard_fill("48 00 ?? ?{2} c3", 0x2a);
+
+    assert_eq!(pattern.raw_data(), [0x48, 0x00, 0x2a, 0x2a, 0x2a, 0xc3]);
+    assert_eq!(pattern.raw_mask(), [0xff, 0xff, 0x00, 0x00, 0x00, 0xff]);
+    assert_eq!(
+        Pattern::new("48 00 ?? c3").raw_data(),
+        [0x48, 0x00, 0x00, 0xc3]
+    );
+}
+
+#[test]
+fn fill_is_ignored_when_matching() {
+    let pattern = Pattern::new_with_wildcard_fill("48 8b ?? ?? ?? ?? c3", 0xff);
+    let plain = Scanner::new("48 8b ?? ?? ?? ?? c3");
+    let filled = Scanner::from(pattern.clone());
+
+    let mut haystack = vec![0x90; 200];
+    for (index, start) in [3, 50, 97, 150].into_iter().enumerate() {
+        haystack[start..start + 7].copy_from_slice(&[
+            0x48,
+            0x8b,
+            index as u8,
+            0x00,
+            0x7f,
+            0xff,
+            0xc3,
+        ]);
+    }
+
+    for mode in [
+        None,
+        Some(ScanMode::Scalar),
+        Some(ScanMode::Sse42),
+        Some(ScanMode::Avx2),
+    ] {
+        assert_eq!(
+            filled.find_all_offsets(mode, &haystack),
+            plain.find_all_offsets(mode, &haystack)
+        );
+    }
+    assert_eq!(plain.find_all_offsets(None, &haystack), [3, 50, 97, 150]);
+
+    // padded buffers keep the fill
+    let padded = Scanner::from(pattern.pad_to(128));
+    assert_eq!(padded.find_all_offsets(None, &haystack), [3, 50, 97, 150]);
+}
+
+#[test]
+fn fill_is_ignored_when_comparing() {
+    let filled = Pattern::new_with_wildcard_fill("e8 ?? ?? ?? ?? ^ 84 c0", 0x2a);
+    let plain = Pattern::new("e8 ?? ?? ?? ?? ^ 84 c0");
+
+    assert_eq!(filled, plain);
+    assert_eq!(filled.fingerprint(), plain.fingerprint());
+    assert_eq!(HashSet::from([filled.clone(), plain.clone()]).len(), 1);
+
+    let mut patterns = vec![filled.clone(), plain];
+    Pattern::dedup_by_fingerprint(&mut patterns, true);
+    assert_eq!(patterns.len(), 1);
+
+    // derived patterns store wildcards as zero again
+    assert_eq!(filled.reversed().raw_data(), [0xc0, 0x84, 0, 0, 0, 0, 0xe8]);
+    assert_eq!(
+        filled.normalize().raw_data(),
+        [0xe8, 0, 0, 0, 0, 0x84, 0xc0]
+    );
+}
+
+#[test]
+fn fill_is_not_serialized() {
+    let filled = Pattern::new_with_wildcard_fill("48 8b ?? c3", 0x2a);
+
+    assert_eq!(filled.to_bytes(), Pattern::new("48 8b ?? c3").to_bytes());
+    assert_eq!(
+        Pattern::from_bytes(&filled.to_bytes()).unwrap().raw_data(),
+        [0x48, 0x8b, 0x00, 0xc3]
+    );
+}
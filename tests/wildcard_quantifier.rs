@@ -0,0 +1,41 @@
+use lightningscanner::create_pattern;
+use lightningscanner::pattern::{Pattern, PatternError};
+
+#[test]
+fn expansion() {
+    let quantified = Pattern::new("48 8b ?{4} c3");
+    let expanded = Pattern::new("48 8b ?? ?? ?? ?? c3");
+
+    assert_eq!(quantified.to_bytes(), expanded.to_bytes());
+    assert_eq!(
+        Pattern::new("?{1}48?{12}").to_bytes(),
+        Pattern::new("?? 48 ?? ?? ?? ?? ?? ?? ?? ?? ?? ?? ?? ??").to_bytes()
+    );
+}
+
+#[test]
+fn compile_time_expansion() {
+    let quantified = create_pattern!("48 8b ?{4} c3");
+    let expanded = Pattern::new("48 8b ?? ?? ?? ?? c3");
+
+    assert_eq!(quantified.to_bytes(), expanded.to_bytes());
+}
+
+#[test]
+fn malformed() {
+    for pattern in [
+        "48 ?{",
+        "48 ?{}",
+        "48 ?{x}",
+        "48 ?{0}",
+        "48 ?{4",
+        "48 ?{99999999999999999999}",
+    ] {
+        assert_eq!(
+            Pattern::try_new(pattern).err(),
+            Some(PatternError::MalformedQuantifier { position: 3 }),
+            "{}",
+            pattern
+        );
+    }
+}
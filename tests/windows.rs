@@ -0,0 +1,67 @@
+use lightningscanner::Scanner;
+use tinyrand::{Rand, Seeded, Wyrand};
+
+#[test]
+fn windows_overlap_by_pattern_len() {
+    let haystack = (0..10).collect::<Vec<u8>>();
+    let scanner = Scanner::new("00 ?? 02");
+
+    let windows = scanner.windows(&haystack, 4).collect::<Vec<_>>();
+    assert_eq!(
+        windows,
+        [
+            (0, &haystack[0..6]),
+            (4, &haystack[4..10]),
+            (8, &haystack[8..10]),
+        ]
+    );
+}
+
+#[test]
+fn empty_haystack() {
+    assert_eq!(Scanner::new("90").windows(&[], 16).count(), 0);
+}
+
+#[test]
+fn window_larger_than_haystack() {
+    let haystack = [0x90; 8];
+
+    let windows = Scanner::new("90 90")
+        .windows(&haystack, 1024)
+        .collect::<Vec<_>>();
+    assert_eq!(windows, [(0, &haystack[..])]);
+}
+
+#[test]
+fn merged_windows_match_whole_scan() {
+    let mut rand = Wyrand::seed(0x3d0f);
+
+    for _ in 0..500 {
+        let scanner = Scanner::new(&format!(
+            "{:02x} ?? {:02x}",
+            rand.next_lim_u16(2),
+            rand.next_lim_u16(2)
+        ));
+        let haystack = (0..rand.next_lim_usize(128))
+            .map(|_| rand.next_lim_u16(2) as u8)
+            .collect::<Vec<_>>();
+        let window = 1 + rand.next_lim_usize(16);
+
+        let merged = scanner
+            .windows(&haystack, window)
+            .flat_map(|(base, window)| {
+                let offsets = scanner.find_all_offsets(None, window);
+                offsets.into_iter().map(move |offset| base + offset)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(merged, scanner.find_all_offsets(None, &haystack));
+    }
+}
+
+#[test]
+#[should_panic(expected = "window size must not be zero")]
+fn zero_window() {
+    // the window size is checked before the first window
+    let _ = Scanner::new("90").windows(&[0x90], 0);
+}